@@ -0,0 +1,52 @@
+//! Benchmarks `is_noncharacter()` against the list of every noncharacter
+//! that it used to compare codepoints with, over every scalar value and
+//! over mostly ASCII text.
+
+mod timing;
+
+use timing::{black_box, Bencher};
+use whatwg_infra::is_noncharacter;
+
+#[rustfmt::skip]
+const fn is_noncharacter_list(c: char) -> bool {
+	matches!(c,
+		| '\u{FDD0}'..='\u{FDEF}'
+		| '\u{FFFE}' | '\u{FFFF}' | '\u{1FFFE}' | '\u{1FFFF}'
+		| '\u{2FFFE}' | '\u{2FFFF}' | '\u{3FFFE}' | '\u{3FFFF}'
+		| '\u{4FFFE}' | '\u{4FFFF}' | '\u{5FFFE}' | '\u{5FFFF}'
+		| '\u{6FFFE}' | '\u{6FFFF}' | '\u{7FFFE}' | '\u{7FFFF}'
+		| '\u{8FFFE}' | '\u{8FFFF}' | '\u{9FFFE}' | '\u{9FFFF}'
+		| '\u{AFFFE}' | '\u{AFFFF}' | '\u{BFFFE}' | '\u{BFFFF}'
+		| '\u{CFFFE}' | '\u{CFFFF}' | '\u{DFFFE}' | '\u{DFFFF}'
+		| '\u{EFFFE}' | '\u{EFFFF}' | '\u{FFFFE}' | '\u{FFFFF}'
+		| '\u{10FFFE}' | '\u{10FFFF}'
+	)
+}
+
+fn count(chars: &[char], predicate: impl Fn(char) -> bool) -> usize {
+	chars.iter().filter(|&&c| predicate(c)).count()
+}
+
+fn main() {
+	let bencher = Bencher::from_args();
+
+	let scalar_values: Vec<char> = (0..=0x10FFFF).filter_map(char::from_u32).collect();
+	let text: Vec<char> = "Lorem ipsum dolor sit amet, café \u{FDD0} naïve \u{1F431}\n"
+		.repeat(1000)
+		.chars()
+		.collect();
+	assert_eq!(
+		count(&scalar_values, is_noncharacter),
+		count(&scalar_values, is_noncharacter_list)
+	);
+
+	for (name, chars) in [("scalar_values", &scalar_values), ("text", &text)] {
+		let bytes = chars.len() * 4;
+		bencher.bench(&format!("is_noncharacter/{}", name), bytes, || {
+			count(black_box(chars), is_noncharacter)
+		});
+		bencher.bench(&format!("is_noncharacter_list/{}", name), bytes, || {
+			count(black_box(chars), is_noncharacter_list)
+		});
+	}
+}
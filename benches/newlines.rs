@@ -0,0 +1,85 @@
+//! Benchmarks `normalize_newlines()` against the two `String::replace()`
+//! calls it used to be, on documents with CRLF, CR, and LF line endings.
+//!
+//! Run them with and without the `simd` feature to compare the scans:
+//! `cargo bench --bench newlines` and
+//! `cargo bench --bench newlines --features simd`.
+//!
+//! With the `parallel` feature, the parallel variants are also timed on a
+//! 16 MiB document: `cargo bench --bench newlines --features parallel`.
+
+mod timing;
+
+use timing::{black_box, Bencher};
+use whatwg_infra::{normalize_newlines, strip_newlines};
+
+const LINE: &str = "<p class=\"note\">Lorem ipsum dolor sit amet, consectetur.</p>";
+
+fn document(newline: &str, len: usize) -> String {
+	let mut document = String::with_capacity(len + LINE.len() + newline.len());
+	while document.len() < len {
+		document.push_str(LINE);
+		document.push_str(newline);
+	}
+	document
+}
+
+fn normalize_newlines_with_replace(s: &str) -> String {
+	s.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+fn main() {
+	let bencher = Bencher::from_args();
+	println!(
+		"simd feature: {}",
+		if cfg!(feature = "simd") {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+
+	for (size_name, len) in [("1kib", 1024), ("64kib", 64 * 1024), ("1mib", 1024 * 1024)] {
+		for (newline_name, newline) in [("crlf", "\r\n"), ("cr", "\r"), ("lf", "\n")] {
+			let input = document(newline, len);
+			let bytes = input.len();
+
+			bencher.bench(
+				&format!("normalize_newlines/{}/{}", newline_name, size_name),
+				bytes,
+				|| normalize_newlines(black_box(&input)),
+			);
+			bencher.bench(
+				&format!("replace_twice/{}/{}", newline_name, size_name),
+				bytes,
+				|| normalize_newlines_with_replace(black_box(&input)),
+			);
+			bencher.bench(
+				&format!("strip_newlines/{}/{}", newline_name, size_name),
+				bytes,
+				|| strip_newlines(black_box(&input)),
+			);
+		}
+	}
+
+	#[cfg(feature = "parallel")]
+	{
+		use whatwg_infra::parallel;
+
+		let input = document("\r\n", 16 * 1024 * 1024);
+		let bytes = input.len();
+
+		bencher.bench("normalize_newlines/crlf/16mib", bytes, || {
+			normalize_newlines(black_box(&input))
+		});
+		bencher.bench("parallel::normalize_newlines/crlf/16mib", bytes, || {
+			parallel::normalize_newlines(black_box(&input))
+		});
+		bencher.bench("strip_newlines/crlf/16mib", bytes, || {
+			strip_newlines(black_box(&input))
+		});
+		bencher.bench("parallel::strip_newlines/crlf/16mib", bytes, || {
+			parallel::strip_newlines(black_box(&input))
+		});
+	}
+}
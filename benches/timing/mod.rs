@@ -0,0 +1,89 @@
+//! A small timing loop for the benchmarks, which keeps them free of
+//! dependencies. Each benchmark is run for a number of samples of about
+//! 20 ms each, and the median time per iteration is reported.
+//!
+//! Arguments that don't start with `-` filter the benchmarks by name, like
+//! `cargo bench --bench newlines -- crlf`.
+
+use std::time::{Duration, Instant};
+
+/// Keeps the optimizer from removing a value, or from assuming anything
+/// about it, like `std::hint::black_box()`, which is newer than the
+/// crate's minimum supported Rust version
+pub fn black_box<T>(value: T) -> T {
+	// SAFETY: The value is read from a valid reference, and the original is
+	// forgotten so that it's only dropped once
+	unsafe {
+		let copy = std::ptr::read_volatile(&value);
+		std::mem::forget(value);
+		copy
+	}
+}
+
+const SAMPLES: usize = 15;
+const SAMPLE_TIME: Duration = Duration::from_millis(20);
+
+/// Runs the benchmarks whose names contain one of the filters given on the
+/// command line, or all of them without filters
+pub struct Bencher {
+	filters: Vec<String>,
+}
+
+impl Bencher {
+	pub fn from_args() -> Self {
+		Self {
+			filters: std::env::args()
+				.skip(1)
+				.filter(|arg| !arg.starts_with('-'))
+				.collect(),
+		}
+	}
+
+	/// Times a function, and reports the throughput over `bytes` bytes of
+	/// input per iteration if it isn't 0
+	pub fn bench<T>(&self, name: &str, bytes: usize, mut f: impl FnMut() -> T) {
+		if !self.filters.is_empty()
+			&& !self
+				.filters
+				.iter()
+				.any(|filter| name.contains(filter.as_str()))
+		{
+			return;
+		}
+
+		// Find how many iterations fill a sample, doubling from 1
+		let mut iterations = 1u64;
+		loop {
+			let start = Instant::now();
+			for _ in 0..iterations {
+				black_box(f());
+			}
+			if start.elapsed() >= SAMPLE_TIME / 4 {
+				break;
+			}
+			iterations *= 2;
+		}
+		iterations *= 4;
+
+		let mut samples = [0f64; SAMPLES];
+		for sample in &mut samples {
+			let start = Instant::now();
+			for _ in 0..iterations {
+				black_box(f());
+			}
+			*sample = start.elapsed().as_nanos() as f64 / iterations as f64;
+		}
+		samples.sort_by(f64::total_cmp);
+		let median = samples[SAMPLES / 2];
+
+		if bytes == 0 {
+			println!("{:<48} {:>12.1} ns/iter", name, median);
+		} else {
+			let throughput = bytes as f64 / median * 1e9 / (1024.0 * 1024.0);
+			println!(
+				"{:<48} {:>12.1} ns/iter {:>10.1} MiB/s",
+				name, median, throughput
+			);
+		}
+	}
+}
@@ -0,0 +1,346 @@
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::error::{Found, InfraParseError, ViolationPolicy};
+use crate::strings::is_ascii_fast;
+
+/// An owned [ASCII string][whatwg-infra-dfn], whose codepoints are all in
+/// the range U+0000 NULL to U+007F DELETE, inclusive.
+///
+/// Each byte of an ASCII string is one codepoint, so it can be used as a
+/// string or as a byte sequence without converting it. It dereferences to
+/// [`str`], and compares, orders, and hashes the same way.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-string
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{AsciiString, Found};
+///
+/// let method: AsciiString = "GET".parse().unwrap();
+/// assert_eq!(method.as_bytes(), b"GET");
+/// assert_eq!(method.to_lowercase(), "get");
+///
+/// let error = "caf\u{E9}".parse::<AsciiString>().unwrap_err();
+/// assert_eq!(error.found(), Found::CodePoint('\u{E9}'));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiString {
+	inner: String,
+}
+
+impl AsciiString {
+	/// Creates a new, empty ASCII string
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			inner: String::new(),
+		}
+	}
+
+	/// Creates an ASCII string from codepoints, and handles the codepoints
+	/// that aren't ASCII with a [`ViolationPolicy`].
+	///
+	/// # Errors
+	/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+	/// found the first codepoint that isn't ASCII. Its offset is the length
+	/// of the ASCII string up to that codepoint.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{AsciiString, ViolationPolicy};
+	///
+	/// let chars = "na\u{EF}ve".chars();
+	/// let s = AsciiString::from_chars_with_policy(chars.clone(), ViolationPolicy::Ignore);
+	/// assert_eq!(s.unwrap(), "nave");
+	/// assert!(AsciiString::from_chars_with_policy(chars, ViolationPolicy::Error).is_err());
+	/// ```
+	pub fn from_chars_with_policy<I>(
+		chars: I,
+		policy: ViolationPolicy,
+	) -> Result<Self, InfraParseError>
+	where
+		I: IntoIterator<Item = char>,
+	{
+		let mut s = Self::new();
+		for (index, c) in chars.into_iter().enumerate() {
+			if s.try_push(c).is_ok() {
+				continue;
+			}
+			match policy {
+				ViolationPolicy::Error => {
+					let error = InfraParseError::new(
+						"create an ASCII string",
+						s.len(),
+						"an ASCII codepoint",
+						Found::CodePoint(c),
+					);
+					return Err(error.with_code_point_index(index));
+				}
+				ViolationPolicy::Replace => s.inner.push('?'),
+				ViolationPolicy::Ignore => {}
+			}
+		}
+		Ok(s)
+	}
+
+	/// Creates an ASCII string from codepoints, replacing each codepoint
+	/// that isn't ASCII with `?`
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::AsciiString;
+	///
+	/// assert_eq!(AsciiString::from_chars_lossy("caf\u{E9}".chars()), "caf?");
+	/// ```
+	#[must_use]
+	pub fn from_chars_lossy<I>(chars: I) -> Self
+	where
+		I: IntoIterator<Item = char>,
+	{
+		let mut s = Self::new();
+		for c in chars {
+			if s.try_push(c).is_err() {
+				s.inner.push('?');
+			}
+		}
+		s
+	}
+
+	/// Appends a codepoint to the end of the ASCII string.
+	///
+	/// # Panics
+	/// Panics if the codepoint isn't ASCII.
+	pub fn push(&mut self, c: char) {
+		assert!(
+			c.is_ascii(),
+			"an AsciiString can only contain ASCII codepoints"
+		);
+		self.inner.push(c);
+	}
+
+	/// Appends a codepoint to the end of the ASCII string, or returns it
+	/// back if it isn't ASCII
+	pub fn try_push(&mut self, c: char) -> Result<(), char> {
+		if !c.is_ascii() {
+			return Err(c);
+		}
+		self.inner.push(c);
+		Ok(())
+	}
+
+	/// Returns the ASCII string as a string slice
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.inner
+	}
+
+	/// Returns the ASCII string as a byte sequence
+	#[must_use]
+	pub fn as_bytes(&self) -> &[u8] {
+		self.inner.as_bytes()
+	}
+
+	/// Converts the ASCII string into a `String`
+	#[must_use]
+	pub fn into_string(self) -> String {
+		self.inner
+	}
+
+	/// Converts the ASCII string into a byte sequence, without copying it
+	#[must_use]
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.inner.into_bytes()
+	}
+
+	/// Checks that a string is ASCII, and returns an error that found its
+	/// first codepoint that isn't
+	fn validate(s: &str) -> Result<(), InfraParseError> {
+		if is_ascii_fast(s) {
+			return Ok(());
+		}
+		match s
+			.char_indices()
+			.enumerate()
+			.find(|(_, (_, c))| !c.is_ascii())
+		{
+			Some((index, (offset, c))) => {
+				let error = InfraParseError::new(
+					"create an ASCII string",
+					offset,
+					"an ASCII codepoint",
+					Found::CodePoint(c),
+				);
+				Err(error.with_code_point_index(index))
+			}
+			None => Ok(()),
+		}
+	}
+}
+
+impl Extend<char> for AsciiString {
+	/// Appends each codepoint of an iterator.
+	///
+	/// # Panics
+	/// Panics if one of the codepoints isn't ASCII, after appending the
+	/// codepoints before it.
+	fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+		for c in iter {
+			self.push(c);
+		}
+	}
+}
+
+impl Deref for AsciiString {
+	type Target = str;
+
+	fn deref(&self) -> &str {
+		&self.inner
+	}
+}
+
+impl AsRef<str> for AsciiString {
+	fn as_ref(&self) -> &str {
+		&self.inner
+	}
+}
+
+impl AsRef<[u8]> for AsciiString {
+	fn as_ref(&self) -> &[u8] {
+		self.inner.as_bytes()
+	}
+}
+
+impl Borrow<str> for AsciiString {
+	fn borrow(&self) -> &str {
+		&self.inner
+	}
+}
+
+impl FromStr for AsciiString {
+	type Err = InfraParseError;
+
+	fn from_str(s: &str) -> Result<Self, InfraParseError> {
+		Self::validate(s)?;
+		Ok(Self {
+			inner: String::from(s),
+		})
+	}
+}
+
+impl TryFrom<String> for AsciiString {
+	type Error = InfraParseError;
+
+	/// Converts a string into an ASCII string without copying it, or returns
+	/// an error that found its first codepoint that isn't ASCII
+	fn try_from(s: String) -> Result<Self, InfraParseError> {
+		Self::validate(&s)?;
+		Ok(Self { inner: s })
+	}
+}
+
+impl TryFrom<&str> for AsciiString {
+	type Error = InfraParseError;
+
+	fn try_from(s: &str) -> Result<Self, InfraParseError> {
+		s.parse()
+	}
+}
+
+impl From<AsciiString> for String {
+	fn from(s: AsciiString) -> Self {
+		s.into_string()
+	}
+}
+
+impl From<AsciiString> for Vec<u8> {
+	fn from(s: AsciiString) -> Self {
+		s.into_bytes()
+	}
+}
+
+impl PartialEq<str> for AsciiString {
+	fn eq(&self, other: &str) -> bool {
+		self.inner == other
+	}
+}
+
+impl PartialEq<&str> for AsciiString {
+	fn eq(&self, other: &&str) -> bool {
+		self.inner == *other
+	}
+}
+
+impl fmt::Display for AsciiString {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(&self.inner, f)
+	}
+}
+
+impl fmt::Debug for AsciiString {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&self.inner, f)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::collections::BTreeSet;
+
+	#[test]
+	fn test_ascii_string_conversions() {
+		let s = AsciiString::try_from(String::from("Host")).unwrap();
+		assert_eq!(s, "Host");
+		assert_eq!(s.len(), 4);
+		assert_eq!(alloc::format!("{} {:?}", s, s), "Host \"Host\"");
+		assert_eq!(Vec::from(s.clone()), b"Host");
+		assert_eq!(AsciiString::try_from("\u{7F}").unwrap().as_str(), "\u{7F}");
+
+		let error = AsciiString::try_from("a\u{1F431}\u{E9}").unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (1, Some(1)));
+		assert_eq!(error.found(), Found::CodePoint('\u{1F431}'));
+	}
+
+	#[test]
+	fn test_ascii_string_from_chars() {
+		let chars = || "\u{E9}t\u{1F431}!".chars();
+		let error =
+			AsciiString::from_chars_with_policy(chars(), ViolationPolicy::Error).unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (0, Some(0)));
+		let error = AsciiString::from_chars_with_policy("ab\u{E9}".chars(), ViolationPolicy::Error)
+			.unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (2, Some(2)));
+		assert_eq!(
+			AsciiString::from_chars_with_policy(chars(), ViolationPolicy::Replace),
+			Ok(AsciiString::from_chars_lossy(chars()))
+		);
+		assert_eq!(AsciiString::from_chars_lossy(chars()), "?t?!");
+
+		let mut s = AsciiString::new();
+		assert_eq!(s.try_push('\u{E9}'), Err('\u{E9}'));
+		s.extend("ok".chars());
+		assert_eq!(s, "ok");
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_ascii_string_push_non_ascii() {
+		AsciiString::new().push('\u{80}');
+	}
+
+	#[test]
+	fn test_ascii_string_borrow() {
+		let mut set = BTreeSet::new();
+		set.insert("b".parse::<AsciiString>().unwrap());
+		set.insert("a".parse::<AsciiString>().unwrap());
+		assert!(set.contains("a"));
+		assert_eq!(set.iter().next().unwrap(), "a");
+		assert_eq!(AsciiString::new(), AsciiString::default());
+	}
+}
@@ -0,0 +1,409 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// A set of ASCII codepoints, stored as a 128-bit bitset so that checking
+/// if it contains a codepoint takes a shift and a mask.
+///
+/// Sets can be built in `const` context, from the codepoint classes of the
+/// [WHATWG Infra Standard][whatwg-infra-dfn] that are provided as
+/// constants, plus any other codepoints. They can then be used to trim,
+/// split, and collect strings, or as the predicate of
+/// [`collect_codepoints()`](crate::collect_codepoints) with
+/// [`AsciiSet::contains_char()`].
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#code-points
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::AsciiSet;
+///
+/// const TOKEN_SEPARATORS: AsciiSet = AsciiSet::ASCII_WHITESPACE.add(b',').add(b';');
+///
+/// assert!(TOKEN_SEPARATORS.contains(b'\t'));
+/// assert!(TOKEN_SEPARATORS.contains_char(';'));
+/// assert!(!TOKEN_SEPARATORS.contains_char('\u{00A0}'));
+///
+/// let tokens: Vec<&str> = TOKEN_SEPARATORS.split(" a, b;;c ").collect();
+/// assert_eq!(tokens, vec!["a", "b", "c"]);
+/// assert_eq!(TOKEN_SEPARATORS.trim(", a b ;"), "a b");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AsciiSet {
+	bits: u128,
+}
+
+impl AsciiSet {
+	/// The set without any codepoints
+	pub const EMPTY: Self = Self { bits: 0 };
+
+	/// The [ASCII tab or newline][whatwg-infra-dfn] codepoints: U+0009 TAB,
+	/// U+000A LF, and U+000D CR
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-tab-or-newline
+	pub const ASCII_TAB_OR_NEWLINE: Self = Self::from_bytes(b"\t\n\r");
+
+	/// The [ASCII whitespace][whatwg-infra-dfn] codepoints: U+0009 TAB,
+	/// U+000A LF, U+000C FF, U+000D CR, and U+0020 SPACE
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-whitespace
+	pub const ASCII_WHITESPACE: Self = Self::from_bytes(b"\t\n\x0C\r ");
+
+	/// The [C0 control][whatwg-infra-dfn] codepoints, U+0000 to U+001F
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#c0-control
+	pub const C0_CONTROL: Self = Self::from_range(0x00, 0x1F);
+
+	/// The [C0 control or space][whatwg-infra-dfn] codepoints, U+0000 to
+	/// U+0020
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#c0-control-or-space
+	pub const C0_CONTROL_OR_SPACE: Self = Self::C0_CONTROL.add(b' ');
+
+	/// The [ASCII digits][whatwg-infra-dfn], U+0030 (0) to U+0039 (9)
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-digit
+	pub const ASCII_DIGIT: Self = Self::from_range(b'0', b'9');
+
+	/// The [ASCII upper hex digits][whatwg-infra-dfn]: the ASCII digits and
+	/// U+0041 (A) to U+0046 (F)
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-upper-hex-digit
+	pub const ASCII_UPPER_HEX_DIGIT: Self = Self::ASCII_DIGIT.union(Self::from_range(b'A', b'F'));
+
+	/// The [ASCII lower hex digits][whatwg-infra-dfn]: the ASCII digits and
+	/// U+0061 (a) to U+0066 (f)
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-lower-hex-digit
+	pub const ASCII_LOWER_HEX_DIGIT: Self = Self::ASCII_DIGIT.union(Self::from_range(b'a', b'f'));
+
+	/// The [ASCII hex digits][whatwg-infra-dfn]: the ASCII upper and lower
+	/// hex digits
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-hex-digit
+	pub const ASCII_HEX_DIGIT: Self =
+		Self::ASCII_UPPER_HEX_DIGIT.union(Self::ASCII_LOWER_HEX_DIGIT);
+
+	/// The [ASCII upper alphas][whatwg-infra-dfn], U+0041 (A) to U+005A (Z)
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-upper-alpha
+	pub const ASCII_UPPER_ALPHA: Self = Self::from_range(b'A', b'Z');
+
+	/// The [ASCII lower alphas][whatwg-infra-dfn], U+0061 (a) to U+007A (z)
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-lower-alpha
+	pub const ASCII_LOWER_ALPHA: Self = Self::from_range(b'a', b'z');
+
+	/// The [ASCII alphas][whatwg-infra-dfn]: the ASCII upper and lower alphas
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-alpha
+	pub const ASCII_ALPHA: Self = Self::ASCII_UPPER_ALPHA.union(Self::ASCII_LOWER_ALPHA);
+
+	/// The [ASCII alphanumerics][whatwg-infra-dfn]: the ASCII digits and
+	/// alphas
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-alphanumeric
+	pub const ASCII_ALPHANUMERIC: Self = Self::ASCII_DIGIT.union(Self::ASCII_ALPHA);
+
+	/// Creates a set of the given bytes.
+	///
+	/// # Panics
+	/// Panics if a byte isn't ASCII.
+	#[must_use]
+	pub const fn from_bytes(bytes: &[u8]) -> Self {
+		let mut set = Self::EMPTY;
+		let mut i = 0;
+		while i < bytes.len() {
+			set = set.add(bytes[i]);
+			i += 1;
+		}
+		set
+	}
+
+	/// Creates a set of the bytes from `start` to `end`, inclusive.
+	///
+	/// # Panics
+	/// Panics if `end` isn't ASCII.
+	#[must_use]
+	pub const fn from_range(start: u8, end: u8) -> Self {
+		let mut set = Self::EMPTY;
+		let mut byte = start;
+		while byte <= end {
+			set = set.add(byte);
+			byte += 1;
+		}
+		set
+	}
+
+	/// Returns the set with a byte added to it.
+	///
+	/// # Panics
+	/// Panics if the byte isn't ASCII.
+	#[must_use]
+	pub const fn add(self, byte: u8) -> Self {
+		assert!(byte.is_ascii(), "an AsciiSet can only contain ASCII bytes");
+		Self {
+			bits: self.bits | 1 << byte,
+		}
+	}
+
+	/// Returns the set with a byte added to it, or `None` if the byte isn't
+	/// ASCII
+	#[must_use]
+	pub const fn try_add(self, byte: u8) -> Option<Self> {
+		if !byte.is_ascii() {
+			return None;
+		}
+		Some(self.add(byte))
+	}
+
+	/// Returns the set with a byte removed from it
+	#[must_use]
+	pub const fn remove(self, byte: u8) -> Self {
+		if !byte.is_ascii() {
+			return self;
+		}
+		Self {
+			bits: self.bits & !(1 << byte),
+		}
+	}
+
+	/// Returns the set of bytes that are in either set
+	#[must_use]
+	pub const fn union(self, other: Self) -> Self {
+		Self {
+			bits: self.bits | other.bits,
+		}
+	}
+
+	/// Returns the set of bytes that are in both sets
+	#[must_use]
+	pub const fn intersection(self, other: Self) -> Self {
+		Self {
+			bits: self.bits & other.bits,
+		}
+	}
+
+	/// Returns the set of ASCII bytes that aren't in the set
+	#[must_use]
+	pub const fn complement(self) -> Self {
+		Self { bits: !self.bits }
+	}
+
+	/// Checks if the set contains a byte
+	#[must_use]
+	#[inline]
+	pub const fn contains(self, byte: u8) -> bool {
+		byte.is_ascii() && self.bits & 1 << byte != 0
+	}
+
+	/// Checks if the set contains a codepoint
+	#[must_use]
+	#[inline]
+	pub const fn contains_char(self, c: char) -> bool {
+		c.is_ascii() && self.contains(c as u8)
+	}
+
+	/// Returns the number of bytes in the set
+	#[must_use]
+	pub const fn len(self) -> usize {
+		self.bits.count_ones() as usize
+	}
+
+	/// Checks if the set has no bytes
+	#[must_use]
+	pub const fn is_empty(self) -> bool {
+		self.bits == 0
+	}
+
+	/// Returns an iterator over the bytes of the set, in ascending order
+	pub fn iter(self) -> impl Iterator<Item = u8> {
+		(0..0x80).filter(move |&byte| self.contains(byte))
+	}
+
+	/// Removes the codepoints of the set from before and after a string
+	#[must_use]
+	pub fn trim(self, s: &str) -> &str {
+		self.trim_end(self.trim_start(s))
+	}
+
+	/// Removes the codepoints of the set from before a string
+	#[must_use]
+	pub fn trim_start(self, s: &str) -> &str {
+		// The set only has ASCII bytes, which are always at char boundaries
+		let start = s
+			.bytes()
+			.position(|byte| !self.contains(byte))
+			.unwrap_or(s.len());
+		&s[start..]
+	}
+
+	/// Removes the codepoints of the set from after a string
+	#[must_use]
+	pub fn trim_end(self, s: &str) -> &str {
+		let end = s
+			.bytes()
+			.rposition(|byte| !self.contains(byte))
+			.map_or(0, |i| i + 1);
+		&s[..end]
+	}
+
+	/// Splits a string into the tokens between the codepoints of the set,
+	/// skipping empty tokens, like
+	/// [`split_on_ascii_whitespace()`](crate::split_on_ascii_whitespace)
+	/// does for ASCII whitespace
+	pub fn split(self, s: &str) -> SplitOnAsciiSet<'_> {
+		SplitOnAsciiSet { rest: s, set: self }
+	}
+}
+
+impl fmt::Debug for AsciiSet {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_set().entries(self.iter().map(char::from)).finish()
+	}
+}
+
+/// An iterator over the tokens of a string between the codepoints of an
+/// [`AsciiSet`], created by [`AsciiSet::split()`]
+#[derive(Clone, Debug)]
+pub struct SplitOnAsciiSet<'a> {
+	rest: &'a str,
+	set: AsciiSet,
+}
+
+impl<'a> Iterator for SplitOnAsciiSet<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let rest = self.set.trim_start(self.rest);
+		if rest.is_empty() {
+			self.rest = rest;
+			return None;
+		}
+		let end = rest
+			.bytes()
+			.position(|byte| self.set.contains(byte))
+			.unwrap_or(rest.len());
+		let (token, rest) = rest.split_at(end);
+		self.rest = rest;
+		Some(token)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Each token but the last is followed by at least one byte of the set
+		(0, Some((self.rest.len() + 1) / 2))
+	}
+}
+
+impl DoubleEndedIterator for SplitOnAsciiSet<'_> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let rest = self.set.trim_end(self.rest);
+		if rest.is_empty() {
+			self.rest = rest;
+			return None;
+		}
+		let start = rest
+			.bytes()
+			.rposition(|byte| self.set.contains(byte))
+			.map_or(0, |i| i + 1);
+		let (rest, token) = rest.split_at(start);
+		self.rest = rest;
+		Some(token)
+	}
+}
+
+impl FusedIterator for SplitOnAsciiSet<'_> {}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+	use crate::codepoints::scalar::{is_ascii_tab_newline, is_c0_control, is_c0_control_space};
+
+	#[test]
+	fn test_ascii_set_classes() {
+		for c in (0..=0x7Fu8)
+			.map(char::from)
+			.chain(['\u{80}', '\u{FF}', '\u{1F431}'])
+		{
+			let check =
+				|set: AsciiSet, expected: bool| assert_eq!(set.contains_char(c), expected, "{c:?}");
+			check(AsciiSet::ASCII_TAB_OR_NEWLINE, is_ascii_tab_newline(c));
+			check(AsciiSet::ASCII_WHITESPACE, c.is_ascii_whitespace());
+			check(AsciiSet::C0_CONTROL, is_c0_control(c));
+			check(AsciiSet::C0_CONTROL_OR_SPACE, is_c0_control_space(c));
+			check(AsciiSet::ASCII_DIGIT, c.is_ascii_digit());
+			check(
+				AsciiSet::ASCII_UPPER_HEX_DIGIT,
+				c.is_ascii_digit() || ('A'..='F').contains(&c),
+			);
+			check(
+				AsciiSet::ASCII_LOWER_HEX_DIGIT,
+				c.is_ascii_digit() || ('a'..='f').contains(&c),
+			);
+			check(AsciiSet::ASCII_HEX_DIGIT, c.is_ascii_hexdigit());
+			check(AsciiSet::ASCII_UPPER_ALPHA, c.is_ascii_uppercase());
+			check(AsciiSet::ASCII_LOWER_ALPHA, c.is_ascii_lowercase());
+			check(AsciiSet::ASCII_ALPHA, c.is_ascii_alphabetic());
+			check(AsciiSet::ASCII_ALPHANUMERIC, c.is_ascii_alphanumeric());
+		}
+	}
+
+	#[test]
+	fn test_ascii_set_operations() {
+		let set = AsciiSet::from_bytes(b"ab")
+			.add(b'c')
+			.remove(b'a')
+			.remove(0xFF);
+		assert_eq!(set.iter().collect::<alloc::vec::Vec<_>>(), [b'b', b'c']);
+		assert_eq!(set.len(), 2);
+		assert!(!set.contains(0xFF));
+		assert!(AsciiSet::EMPTY.is_empty());
+		assert_eq!(AsciiSet::ASCII_ALPHA.complement().len(), 128 - 52);
+		assert_eq!(
+			AsciiSet::ASCII_ALPHA
+				.intersection(AsciiSet::ASCII_HEX_DIGIT)
+				.len(),
+			12
+		);
+		assert_eq!(AsciiSet::from_range(0x7F, 0x7F).len(), 1);
+		assert_eq!(alloc::format!("{set:?}"), "{'b', 'c'}");
+	}
+
+	#[test]
+	fn test_ascii_set_try_add() {
+		assert_eq!(
+			AsciiSet::EMPTY.try_add(b'a'),
+			Some(AsciiSet::from_bytes(b"a"))
+		);
+		assert_eq!(AsciiSet::EMPTY.try_add(0x80), None);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_ascii_set_add_non_ascii() {
+		let _ = AsciiSet::EMPTY.add(0x80);
+	}
+
+	#[test]
+	fn test_ascii_set_trim_split() {
+		let set = AsciiSet::from_bytes(b"-");
+		assert_eq!(set.trim("--a-b--"), "a-b");
+		assert_eq!(set.trim_start("--\u{E9}-"), "\u{E9}-");
+		assert_eq!(set.trim_end("-\u{E9}--"), "-\u{E9}");
+		assert_eq!(set.trim("---"), "");
+		assert_eq!(
+			set.split("--\u{E9}--b-").collect::<alloc::vec::Vec<_>>(),
+			["\u{E9}", "b"]
+		);
+		assert_eq!(set.split("").next(), None);
+		assert_eq!(
+			set.split("-a--\u{E9}-b")
+				.rev()
+				.collect::<alloc::vec::Vec<_>>(),
+			["b", "\u{E9}", "a"]
+		);
+		let mut split = set.split("a-b-c");
+		assert_eq!((split.next(), split.next_back()), (Some("a"), Some("c")));
+		assert_eq!((split.next_back(), split.next()), (Some("b"), None));
+	}
+}
@@ -0,0 +1,251 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::codepoints::scalar::is_noncharacter_codepoint;
+use crate::cursor::CodePointCursor;
+use crate::error::InfraParseError;
+
+/// A [codepoint][whatwg-infra-dfn], which is any value from U+0000 to
+/// U+10FFFF, inclusive.
+///
+/// Unlike [`char`], which can only hold scalar values, a codepoint can also
+/// be a surrogate, like the lone surrogates of ill-formed UTF-16. It's
+/// formatted as `U+` and its value in four to six uppercase hex digits, the
+/// way the Infra Standard writes codepoints, and parses from the same form.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#code-point
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::CodePoint;
+///
+/// let surrogate = CodePoint::new(0xD800).unwrap();
+/// assert!(surrogate.is_surrogate());
+/// assert_eq!(surrogate.to_char(), None);
+/// assert_eq!(surrogate.to_string(), "U+D800");
+///
+/// let cat: CodePoint = "U+1F431".parse().unwrap();
+/// assert_eq!(cat, CodePoint::from('\u{1F431}'));
+/// assert_eq!(CodePoint::new(0x110000), None);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CodePoint(u32);
+
+impl CodePoint {
+	/// The largest codepoint, U+10FFFF
+	pub const MAX: Self = Self(0x10FFFF);
+
+	/// Creates a codepoint from its value, or returns `None` if the value is
+	/// above U+10FFFF
+	#[must_use]
+	pub const fn new(value: u32) -> Option<Self> {
+		if value > Self::MAX.0 {
+			return None;
+		}
+		Some(Self(value))
+	}
+
+	/// Creates a codepoint from a scalar value
+	#[must_use]
+	pub const fn from_char(c: char) -> Self {
+		Self(c as u32)
+	}
+
+	/// Returns the value of the codepoint
+	#[must_use]
+	pub const fn value(self) -> u32 {
+		self.0
+	}
+
+	/// Returns the codepoint as a [`char`], or `None` if it's a surrogate
+	#[must_use]
+	pub fn to_char(self) -> Option<char> {
+		char::from_u32(self.0)
+	}
+
+	/// Checks if the codepoint is a [surrogate][whatwg-infra-dfn], in the
+	/// range U+D800 to U+DFFF, inclusive.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#surrogate
+	#[must_use]
+	pub const fn is_surrogate(self) -> bool {
+		matches!(self.0, 0xD800..=0xDFFF)
+	}
+
+	/// Checks if the codepoint is a [leading surrogate][whatwg-infra-dfn],
+	/// in the range U+D800 to U+DBFF, inclusive.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#leading-surrogate
+	#[must_use]
+	pub const fn is_leading_surrogate(self) -> bool {
+		matches!(self.0, 0xD800..=0xDBFF)
+	}
+
+	/// Checks if the codepoint is a [trailing surrogate][whatwg-infra-dfn],
+	/// in the range U+DC00 to U+DFFF, inclusive.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#trailing-surrogate
+	#[must_use]
+	pub const fn is_trailing_surrogate(self) -> bool {
+		matches!(self.0, 0xDC00..=0xDFFF)
+	}
+
+	/// Checks if the codepoint is a [scalar value][whatwg-infra-dfn], which
+	/// is any codepoint that isn't a surrogate.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#scalar-value
+	#[must_use]
+	pub const fn is_scalar_value(self) -> bool {
+		!self.is_surrogate()
+	}
+
+	/// Checks if the codepoint is a [noncharacter][whatwg-infra-dfn], like
+	/// [`is_noncharacter()`](crate::codepoints::scalar::is_noncharacter).
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#noncharacter
+	#[must_use]
+	pub const fn is_noncharacter(self) -> bool {
+		is_noncharacter_codepoint(self.0)
+	}
+
+	/// Checks if the codepoint is an [ASCII codepoint][whatwg-infra-dfn],
+	/// in the range U+0000 NULL to U+007F DELETE, inclusive.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-code-point
+	#[must_use]
+	pub const fn is_ascii(self) -> bool {
+		self.0 <= 0x7F
+	}
+}
+
+impl From<char> for CodePoint {
+	fn from(c: char) -> Self {
+		Self::from_char(c)
+	}
+}
+
+impl From<u16> for CodePoint {
+	/// Converts a UTF-16 code unit into the codepoint with its value, which
+	/// is a surrogate if the code unit is one
+	fn from(unit: u16) -> Self {
+		Self(u32::from(unit))
+	}
+}
+
+impl From<CodePoint> for u32 {
+	fn from(cp: CodePoint) -> Self {
+		cp.0
+	}
+}
+
+impl TryFrom<u32> for CodePoint {
+	type Error = u32;
+
+	/// Creates a codepoint from its value, or returns the value back if
+	/// it's above U+10FFFF
+	fn try_from(value: u32) -> Result<Self, u32> {
+		Self::new(value).ok_or(value)
+	}
+}
+
+impl TryFrom<CodePoint> for char {
+	type Error = CodePoint;
+
+	/// Converts a codepoint into a [`char`], or returns the codepoint back
+	/// if it's a surrogate
+	fn try_from(cp: CodePoint) -> Result<Self, CodePoint> {
+		cp.to_char().ok_or(cp)
+	}
+}
+
+impl PartialEq<char> for CodePoint {
+	fn eq(&self, other: &char) -> bool {
+		self.0 == *other as u32
+	}
+}
+
+impl FromStr for CodePoint {
+	type Err = InfraParseError;
+
+	/// Parses a codepoint written as `U+` and four to six uppercase hex
+	/// digits, like `U+000A` or `U+10FFFF`
+	fn from_str(s: &str) -> Result<Self, InfraParseError> {
+		const OPERATION: &str = "parse a codepoint";
+
+		let mut cursor = CodePointCursor::new(s);
+		cursor.require_str("U+")?;
+		let digits = cursor.consume_while_max(|c| matches!(c, '0'..='9' | 'A'..='F'), 6);
+		if digits.len() < 4 {
+			return Err(cursor.error(OPERATION, "an ASCII upper hex digit"));
+		}
+		if !cursor.is_past_end() {
+			return Err(cursor.error(OPERATION, "the end of the input"));
+		}
+
+		// Up to six hex digits always fit in a `u32`
+		let value = u32::from_str_radix(digits, 16).unwrap_or(u32::MAX);
+		Self::new(value).ok_or_else(|| {
+			let mut cursor = CodePointCursor::new(s);
+			cursor.advance_by(2);
+			cursor.error(OPERATION, "a codepoint of U+10FFFF or below")
+		})
+	}
+}
+
+impl fmt::Display for CodePoint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "U+{:04X}", self.0)
+	}
+}
+
+impl fmt::Debug for CodePoint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+	use alloc::string::ToString;
+
+	#[test]
+	fn test_code_point_classes() {
+		let cp = |value| CodePoint::new(value).unwrap();
+		assert!(cp(0xD800).is_leading_surrogate() && !cp(0xD800).is_trailing_surrogate());
+		assert!(cp(0xDFFF).is_trailing_surrogate() && !cp(0xDFFF).is_scalar_value());
+		assert!(cp(0xE000).is_scalar_value() && !cp(0xE000).is_surrogate());
+		assert!(cp(0x10FFFF).is_noncharacter() && !cp(0x10FFFD).is_noncharacter());
+		assert!(cp(0x7F).is_ascii() && !cp(0x80).is_ascii());
+
+		assert_eq!(char::try_from(cp(0xDC00)), Err(cp(0xDC00)));
+		assert_eq!(char::try_from(cp(0x41)), Ok('A'));
+		assert_eq!(CodePoint::try_from(0x110000u32), Err(0x110000));
+		assert_eq!(CodePoint::from(0xD800u16), cp(0xD800));
+		assert!(cp(0x41) == 'A');
+	}
+
+	#[test]
+	fn test_code_point_display_parse() {
+		for (value, text) in [(0x0, "U+0000"), (0xFFFF, "U+FFFF"), (0x10FFFF, "U+10FFFF")] {
+			let cp = CodePoint::new(value).unwrap();
+			assert_eq!(cp.to_string(), text);
+			assert_eq!(alloc::format!("{:?}", cp), text);
+			assert_eq!(text.parse(), Ok(cp));
+		}
+
+		for (text, offset) in [
+			("", 0),
+			("u+0041", 0),
+			("U+41", 4),
+			("U+00a0", 4),
+			("U+0000000", 8),
+			("U+0041 ", 6),
+			("U+110000", 2),
+		] {
+			let error = text.parse::<CodePoint>().unwrap_err();
+			assert_eq!(error.offset(), offset, "{:?}", text);
+		}
+	}
+}
@@ -0,0 +1,92 @@
+use crate::codepoints::code_point::CodePoint;
+use crate::codepoints::scalar::is_noncharacter_codepoint;
+
+mod sealed {
+	pub trait Sealed {}
+}
+
+/// A representation of a codepoint that the codepoint predicates accept:
+/// [`char`], `&char`, [`u32`], and [`CodePoint`].
+///
+/// It's implemented by [`InfraScalarValue`](crate::InfraScalarValue) for
+/// each of them, so a predicate can be called on whichever representation a
+/// parser already has. A `u32` above U+10FFFF isn't a codepoint, so no
+/// predicate matches it.
+///
+/// This trait is sealed, and can't be implemented outside of this crate.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{CodePoint, CodePointLike, InfraScalarValue};
+///
+/// fn count_c0_controls<C: CodePointLike>(input: impl IntoIterator<Item = C>) -> usize {
+///     input.into_iter().filter(|c| c.is_c0_control()).count()
+/// }
+///
+/// assert_eq!(count_c0_controls("a\tb\n".chars()), 2);
+/// assert_eq!(count_c0_controls(['\0', ' '].iter()), 1);
+/// assert_eq!(count_c0_controls([0x1Fu32, 0xFDD0]), 1);
+/// assert_eq!(count_c0_controls([CodePoint::from('\r')]), 1);
+/// ```
+pub trait CodePointLike: Copy + sealed::Sealed {
+	/// Returns the value of the codepoint
+	fn code_point_value(self) -> u32;
+}
+
+impl sealed::Sealed for char {}
+impl CodePointLike for char {
+	#[inline]
+	fn code_point_value(self) -> u32 {
+		self as u32
+	}
+}
+
+impl sealed::Sealed for &char {}
+impl CodePointLike for &char {
+	#[inline]
+	fn code_point_value(self) -> u32 {
+		*self as u32
+	}
+}
+
+impl sealed::Sealed for u32 {}
+impl CodePointLike for u32 {
+	#[inline]
+	fn code_point_value(self) -> u32 {
+		self
+	}
+}
+
+impl sealed::Sealed for CodePoint {}
+impl CodePointLike for CodePoint {
+	#[inline]
+	fn code_point_value(self) -> u32 {
+		self.value()
+	}
+}
+
+/// Checks if a codepoint value is a noncharacter, and isn't above U+10FFFF
+#[inline]
+pub(crate) fn is_noncharacter_value(cp: u32) -> bool {
+	cp <= CodePoint::MAX.value() && is_noncharacter_codepoint(cp)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::codepoints::scalar::InfraScalarValue;
+
+	#[test]
+	fn test_code_point_like_predicates() {
+		assert!('\u{FDD0}'.is_noncharacter() && (&'\u{FFFF}').is_noncharacter());
+		assert!(0x10FFFFu32.is_noncharacter() && !0x11FFFFu32.is_noncharacter());
+		assert!(0x1Fu32.is_c0_control() && !0x7Fu32.is_c0_control());
+		assert!(0x20u32.is_c0_control_space() && !0x110000u32.is_c0_control_space());
+		assert!(0x0Du32.is_ascii_tab_newline() && !0x0Cu32.is_ascii_tab_newline());
+
+		let surrogate = CodePoint::new(0xDC00).unwrap();
+		assert!(!surrogate.is_c0_control_space() && !surrogate.is_ascii_tab_newline());
+		assert!(CodePoint::from('\t').is_ascii_tab_newline());
+		assert!(CodePoint::from('\0').is_c0_control());
+	}
+}
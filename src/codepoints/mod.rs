@@ -0,0 +1,24 @@
+/// Detection of UTF-16 surrogate codepoints for `u16`
+///
+/// This module exposes predicate functions for detecting surrogates,
+/// including trailing and leading surrogates, and functions for splitting
+/// codepoints into surrogate pairs and combining them back.
+pub mod surrogates;
+pub use self::surrogates::*;
+
+/// Module for Unicode scalar values
+pub mod scalar;
+pub use self::scalar::*;
+
+/// The [`CodePoint`] type, for codepoints that may be surrogates
+pub mod code_point;
+pub use self::code_point::CodePoint;
+
+/// The [`CodePointLike`] trait, for the representations of a codepoint that
+/// the predicates accept
+pub mod code_point_like;
+pub use self::code_point_like::CodePointLike;
+
+/// Module for sets of ASCII codepoints
+pub mod ascii_set;
+pub use self::ascii_set::*;
@@ -1,4 +1,7 @@
-/// Methods from the WHATWG Infra Standard for Unicode codepoints
+use crate::codepoints::code_point_like::{is_noncharacter_value, CodePointLike};
+
+/// Methods from the WHATWG Infra Standard for Unicode codepoints, for each
+/// [`CodePointLike`] representation of a codepoint
 #[allow(clippy::wrong_self_convention)]
 pub trait InfraScalarValue {
 	/// See the documentation for [`is_ascii_tab_newline()`]
@@ -11,21 +14,21 @@ pub trait InfraScalarValue {
 	fn is_noncharacter(self) -> bool;
 }
 
-impl InfraScalarValue for char {
+impl<C: CodePointLike> InfraScalarValue for C {
 	fn is_ascii_tab_newline(self) -> bool {
-		is_ascii_tab_newline(self)
+		matches!(self.code_point_value(), 0x09 | 0x0A | 0x0D)
 	}
 
 	fn is_c0_control(self) -> bool {
-		is_c0_control(self)
+		self.code_point_value() <= 0x1F
 	}
 
 	fn is_c0_control_space(self) -> bool {
-		is_c0_control_space(self)
+		self.code_point_value() <= 0x20
 	}
 
 	fn is_noncharacter(self) -> bool {
-		is_noncharacter(self)
+		is_noncharacter_value(self.code_point_value())
 	}
 }
 
@@ -40,7 +43,7 @@ impl InfraScalarValue for char {
 /// > U+FFFFF, U+10FFFE, or U+10FFFF.
 ///
 /// Essentially, a noncharacter includes:
-///  - the 36 codepoints from U+FDD0 to U+FDEF,
+///  - the 32 codepoints from U+FDD0 to U+FDEF,
 ///  - the U+..FFFE U+..FFFF codepoints in all 17 Unicode planes which are
 ///    guaranteed to never encode as anything, per the Unicode Standard
 ///    (in [Section 3.2, Conformance Requirements][unicode-s3-2] and
@@ -62,22 +65,21 @@ impl InfraScalarValue for char {
 /// assert!(is_noncharacter('\u{10FFFF}'));
 /// ```
 #[allow(clippy::wrong_self_convention)]
-#[rustfmt::skip]
 #[must_use]
 #[inline]
 pub const fn is_noncharacter(c: char) -> bool {
-	matches!(c,
-		| '\u{FDD0}'..='\u{FDEF}'
-		| '\u{FFFE}' | '\u{FFFF}' | '\u{1FFFE}' | '\u{1FFFF}'
-		| '\u{2FFFE}' | '\u{2FFFF}' | '\u{3FFFE}' | '\u{3FFFF}'
-		| '\u{4FFFE}' | '\u{4FFFF}' | '\u{5FFFE}' | '\u{5FFFF}'
-		| '\u{6FFFE}' | '\u{6FFFF}' | '\u{7FFFE}' | '\u{7FFFF}'
-		| '\u{8FFFE}' | '\u{8FFFF}' | '\u{9FFFE}' | '\u{9FFFF}'
-		| '\u{AFFFE}' | '\u{AFFFF}' | '\u{BFFFE}' | '\u{BFFFF}'
-		| '\u{CFFFE}' | '\u{CFFFF}' | '\u{DFFFE}' | '\u{DFFFF}'
-		| '\u{EFFFE}' | '\u{EFFFF}' | '\u{FFFFE}' | '\u{FFFFF}'
-		| '\u{10FFFE}' | '\u{10FFFF}'
-	)
+	is_noncharacter_codepoint(c as u32)
+}
+
+/// Checks if a codepoint value is a noncharacter, for callers that decode
+/// codepoints themselves, like the `const` string scanners
+#[inline]
+pub(crate) const fn is_noncharacter_codepoint(cp: u32) -> bool {
+	// The last two codepoints of each plane end in 0xFFFE or 0xFFFF, and
+	// U+FDD0 to U+FDEF are the 32 codepoints at most 0x1F above U+FDD0,
+	// which wraps around for the codepoints below it. Combining the checks
+	// with `|` instead of `||` keeps them free of branches.
+	(cp & 0xFFFE == 0xFFFE) | (cp.wrapping_sub(0xFDD0) < 0x20)
 }
 
 /// Checks if a character is a **C0 control**, as originally defined
@@ -166,6 +168,26 @@ mod test {
 		assert!('\u{10FFFF}'.is_noncharacter());
 	}
 
+	#[test]
+	#[rustfmt::skip]
+	fn test_is_noncharacter_all_codepoints() {
+		let expected = |c: char| matches!(c,
+			| '\u{FDD0}'..='\u{FDEF}'
+			| '\u{FFFE}' | '\u{FFFF}' | '\u{1FFFE}' | '\u{1FFFF}'
+			| '\u{2FFFE}' | '\u{2FFFF}' | '\u{3FFFE}' | '\u{3FFFF}'
+			| '\u{4FFFE}' | '\u{4FFFF}' | '\u{5FFFE}' | '\u{5FFFF}'
+			| '\u{6FFFE}' | '\u{6FFFF}' | '\u{7FFFE}' | '\u{7FFFF}'
+			| '\u{8FFFE}' | '\u{8FFFF}' | '\u{9FFFE}' | '\u{9FFFF}'
+			| '\u{AFFFE}' | '\u{AFFFF}' | '\u{BFFFE}' | '\u{BFFFF}'
+			| '\u{CFFFE}' | '\u{CFFFF}' | '\u{DFFFE}' | '\u{DFFFF}'
+			| '\u{EFFFE}' | '\u{EFFFF}' | '\u{FFFFE}' | '\u{FFFFF}'
+			| '\u{10FFFE}' | '\u{10FFFF}'
+		);
+		for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+			assert_eq!(is_noncharacter(c), expected(c), "{c:?}");
+		}
+	}
+
 	#[test]
 	fn test_is_c0_control() {
 		assert!('\u{0000}'.is_c0_control());
@@ -0,0 +1,228 @@
+/// Methods from the WHATWG Infra Standard for UTF-16 surrogates
+#[allow(clippy::wrong_self_convention)]
+pub trait InfraUtf16Surrogate {
+	/// The minimum UTF-16 codepoint that can be represented as a leading surrogate
+	const LEADING_SURROGATE_MIN: u16;
+	/// The minimum UTF-16 codepoint that can be represented as a leading surrogate
+	const LEADING_SURROGATE_MAX: u16;
+
+	/// The minimum UTF-16 codepoint that can be represented as a trailing surrogate
+	const TRAILING_SURROGATE_MIN: u16;
+	/// The maximum UTF-16 codepoint that can be represented as a trailing surrogate
+	const TRAILING_SURROGATE_MAX: u16;
+
+	/// The minimum UTF-16 codepoint that can be represented as a surrogate
+	const SURROGATE_MIN: u16;
+	/// The maximum UTF-16 codepoint that can be represented as a surrogate
+	const SURROGATE_MAX: u16;
+
+	/// See the documentation for [`is_surrogate_utf16()`]
+	fn is_surrogate_utf16(self) -> bool;
+	/// See the documentation for [`is_leading_surrogate_utf16()`]
+	fn is_leading_surrogate_utf16(self) -> bool;
+	/// See the documentation for [`is_trailing_surrogate_utf16()`]
+	fn is_trailing_surrogate_utf16(self) -> bool;
+}
+
+impl InfraUtf16Surrogate for u16 {
+	const LEADING_SURROGATE_MIN: u16 = 0xD800u16;
+	const LEADING_SURROGATE_MAX: u16 = 0xDBFFu16;
+	const TRAILING_SURROGATE_MIN: u16 = 0xDC00u16;
+	const TRAILING_SURROGATE_MAX: u16 = 0xDFFFu16;
+	const SURROGATE_MIN: u16 = Self::LEADING_SURROGATE_MIN;
+	const SURROGATE_MAX: u16 = Self::TRAILING_SURROGATE_MAX;
+
+	fn is_surrogate_utf16(self) -> bool {
+		is_surrogate_utf16(self)
+	}
+
+	fn is_leading_surrogate_utf16(self) -> bool {
+		is_leading_surrogate_utf16(self)
+	}
+
+	fn is_trailing_surrogate_utf16(self) -> bool {
+		is_trailing_surrogate_utf16(self)
+	}
+}
+
+/// Checks if a `u16` is a UTF-16 codepoint defined in the range of
+/// U+D800 to U+DFFF, inclusive.
+///
+/// See also:[WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// **Note**: This function substitutes the nightly-gated feature in Rust,
+/// [`utf16_extra`][utf16-extra-feat] ([issue #94919][utf16-extra-gh]).
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#surrogate
+/// [utf16-extra-gh]: https://github.com/rust-lang/rust/issues/94919
+/// [utf16-extra-feat]: https://doc.rust-lang.org/unstable-book/library-features/utf16-extra.html
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::surrogates::is_surrogate_utf16;
+///
+/// assert!(!is_surrogate_utf16(0xD799u16));
+/// assert!(is_surrogate_utf16(0xD809u16));
+/// assert!(is_surrogate_utf16(0xDB99u16));
+/// assert!(is_surrogate_utf16(0xDFFFu16));
+/// assert!(!is_surrogate_utf16(0xE000u16));
+/// ```
+#[allow(clippy::wrong_self_convention)]
+#[must_use]
+#[inline]
+pub const fn is_surrogate_utf16(c: u16) -> bool {
+	//matches!(c, 0xD800u16..=0xDFFFu16)
+	matches!(c, u16::SURROGATE_MIN..=u16::SURROGATE_MAX)
+}
+
+/// Checks if a `u16` is a UTF-16 codepoint defined in the range of
+/// U+D800 to U+DBFF, inclusive.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#leading-surrogate
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::surrogates::is_leading_surrogate_utf16;
+///
+/// assert!(!is_leading_surrogate_utf16(0xD799u16));
+/// assert!(is_leading_surrogate_utf16(0xD800u16));
+/// assert!(is_leading_surrogate_utf16(0xDBFFu16));
+/// assert!(!is_leading_surrogate_utf16(0xDC00u16));
+/// ```
+#[allow(clippy::wrong_self_convention)]
+#[must_use]
+#[inline]
+pub const fn is_leading_surrogate_utf16(c: u16) -> bool {
+	matches!(c, u16::LEADING_SURROGATE_MIN..=u16::LEADING_SURROGATE_MAX)
+}
+
+/// Checks if a `u16` is a UTF-16 codepoint defined in the range of
+/// U+DC00 to U+DFFF, inclusive.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#trailing-surrogate
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::surrogates::is_trailing_surrogate_utf16;
+///
+/// assert!(!is_trailing_surrogate_utf16(0xDB99u16));
+/// assert!(is_trailing_surrogate_utf16(0xDC00u16));
+/// assert!(is_trailing_surrogate_utf16(0xDFFFu16));
+/// assert!(!is_trailing_surrogate_utf16(0xE000u16));
+/// ```
+#[allow(clippy::wrong_self_convention)]
+#[must_use]
+#[inline]
+pub const fn is_trailing_surrogate_utf16(c: u16) -> bool {
+	matches!(c, u16::TRAILING_SURROGATE_MIN..=u16::TRAILING_SURROGATE_MAX)
+}
+
+/// Splits a supplementary codepoint, from U+10000 to U+10FFFF, inclusive,
+/// into the leading and trailing surrogates that encode it in UTF-16.
+/// Returns `None` for any other value, which isn't encoded as a pair.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#surrogate-pair
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::surrogates::split_into_surrogate_pair;
+///
+/// assert_eq!(split_into_surrogate_pair(0x1F431), Some((0xD83D, 0xDC31)));
+/// assert_eq!(split_into_surrogate_pair(0x10FFFF), Some((0xDBFF, 0xDFFF)));
+/// assert_eq!(split_into_surrogate_pair(0xFFFF), None);
+/// assert_eq!(split_into_surrogate_pair(0x110000), None);
+/// ```
+#[must_use]
+#[inline]
+pub const fn split_into_surrogate_pair(cp: u32) -> Option<(u16, u16)> {
+	if cp < 0x10000 || cp > 0x10FFFF {
+		return None;
+	}
+	let value = cp - 0x10000;
+	let leading = u16::LEADING_SURROGATE_MIN | (value >> 10) as u16;
+	let trailing = u16::TRAILING_SURROGATE_MIN | (value & 0x3FF) as u16;
+	Some((leading, trailing))
+}
+
+/// Combines a leading surrogate and a trailing surrogate into the
+/// supplementary codepoint that they encode as a surrogate pair. Returns
+/// `None` if the first code unit isn't a leading surrogate, or the second
+/// isn't a trailing surrogate.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#surrogate-pair
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::surrogates::combine_surrogate_pair;
+///
+/// assert_eq!(combine_surrogate_pair(0xD83D, 0xDC31), Some(0x1F431));
+/// assert_eq!(combine_surrogate_pair(0xD800, 0xDC00), Some(0x10000));
+/// assert_eq!(combine_surrogate_pair(0xDC31, 0xD83D), None);
+/// assert_eq!(combine_surrogate_pair(0xD83D, 0x61), None);
+/// ```
+#[must_use]
+#[inline]
+pub const fn combine_surrogate_pair(leading: u16, trailing: u16) -> Option<u32> {
+	if !is_leading_surrogate_utf16(leading) || !is_trailing_surrogate_utf16(trailing) {
+		return None;
+	}
+	let high = (leading - u16::LEADING_SURROGATE_MIN) as u32;
+	let low = (trailing - u16::TRAILING_SURROGATE_MIN) as u32;
+	Some(0x10000 + (high << 10) + low)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_is_surrogate_utf16() {
+		assert!(!is_surrogate_utf16(0xD799u16));
+		assert!(is_surrogate_utf16(0xD809u16));
+		assert!(is_surrogate_utf16(0xDB99u16));
+		assert!(is_surrogate_utf16(0xDFFFu16));
+		assert!(!0xE000u16.is_surrogate_utf16());
+	}
+
+	#[test]
+	fn test_is_leading_surrogate_utf16() {
+		assert!(!is_leading_surrogate_utf16(0xD799u16));
+		assert!(is_leading_surrogate_utf16(0xD800u16));
+		assert!(is_leading_surrogate_utf16(0xDBFFu16));
+		assert!(!0xDC00u16.is_leading_surrogate_utf16());
+	}
+
+	#[test]
+	fn test_surrogate_pairs() {
+		for cp in 0x10000..=0x10FFFF {
+			let (leading, trailing) = split_into_surrogate_pair(cp).unwrap();
+			assert!(is_leading_surrogate_utf16(leading));
+			assert!(is_trailing_surrogate_utf16(trailing));
+			assert_eq!(combine_surrogate_pair(leading, trailing), Some(cp));
+		}
+		assert_eq!(split_into_surrogate_pair(0), None);
+		assert_eq!(split_into_surrogate_pair(0xD800), None);
+		assert_eq!(combine_surrogate_pair(0xD7FF, 0xDC00), None);
+		assert_eq!(combine_surrogate_pair(0xDBFF, 0xE000), None);
+		assert_eq!(combine_surrogate_pair(0xD800, 0xD800), None);
+
+		const PAIR: Option<(u16, u16)> = split_into_surrogate_pair(0x1F431);
+		assert_eq!(PAIR, Some((0xD83D, 0xDC31)));
+	}
+
+	#[test]
+	fn test_is_trailing_surrogate_utf16() {
+		assert!(!is_trailing_surrogate_utf16(0xDB99u16));
+		assert!(is_trailing_surrogate_utf16(0xDC00u16));
+		assert!(is_trailing_surrogate_utf16(0xDFFFu16));
+		assert!(!0xE000u16.is_trailing_surrogate_utf16());
+	}
+}
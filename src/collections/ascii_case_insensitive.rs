@@ -0,0 +1,227 @@
+extern crate alloc;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::collections::DeepClone;
+
+/// A string that's compared, ordered, and hashed
+/// [ASCII case-insensitively][whatwg-infra-dfn], while keeping its
+/// original case.
+///
+/// This makes it usable as a key of an [`OrderedMap`] for structures
+/// whose keys are case-insensitive, like header names, without
+/// normalizing the keys when they're set. Lookups can borrow a `&str` as
+/// an `&AsciiCaseInsensitive<str>` with [`AsciiCaseInsensitive::new()`].
+///
+/// Ordering compares the ASCII lowercase forms of the strings byte by byte.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-case-insensitive
+/// [`OrderedMap`]: crate::collections::OrderedMap
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{AsciiCaseInsensitive, OrderedMap};
+///
+/// let mut headers = OrderedMap::new();
+/// headers.set(AsciiCaseInsensitive(String::from("Content-Type")), "text/html");
+/// headers.set(AsciiCaseInsensitive(String::from("content-type")), "text/plain");
+///
+/// assert_eq!(headers.size(), 1);
+/// assert_eq!(headers.get(AsciiCaseInsensitive::new("CONTENT-TYPE")), Some(&"text/plain"));
+/// assert_eq!(headers.keys().next().unwrap().as_ref(), "Content-Type");
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct AsciiCaseInsensitive<S: ?Sized>(pub S);
+
+impl AsciiCaseInsensitive<str> {
+	/// Borrows a string slice as an ASCII case-insensitive string
+	#[must_use]
+	pub fn new(s: &str) -> &Self {
+		// SAFETY: `AsciiCaseInsensitive` is `repr(transparent)`, so it has
+		// the same layout as the `str` it wraps
+		unsafe { &*(s as *const str as *const Self) }
+	}
+}
+
+impl<S> AsciiCaseInsensitive<S> {
+	/// Returns the wrapped string
+	#[must_use]
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+impl<S: AsRef<str> + ?Sized> AsciiCaseInsensitive<S> {
+	/// Returns the wrapped string, with its original case
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		self.0.as_ref()
+	}
+}
+
+impl<S: AsRef<str> + ?Sized> AsRef<str> for AsciiCaseInsensitive<S> {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<S: ?Sized> Deref for AsciiCaseInsensitive<S> {
+	type Target = S;
+
+	fn deref(&self) -> &S {
+		&self.0
+	}
+}
+
+impl Borrow<AsciiCaseInsensitive<str>> for AsciiCaseInsensitive<String> {
+	fn borrow(&self) -> &AsciiCaseInsensitive<str> {
+		AsciiCaseInsensitive::new(&self.0)
+	}
+}
+
+impl Borrow<AsciiCaseInsensitive<str>> for AsciiCaseInsensitive<Box<str>> {
+	fn borrow(&self) -> &AsciiCaseInsensitive<str> {
+		AsciiCaseInsensitive::new(&self.0)
+	}
+}
+
+impl Borrow<AsciiCaseInsensitive<str>> for AsciiCaseInsensitive<&str> {
+	fn borrow(&self) -> &AsciiCaseInsensitive<str> {
+		AsciiCaseInsensitive::new(self.0)
+	}
+}
+
+impl ToOwned for AsciiCaseInsensitive<str> {
+	type Owned = AsciiCaseInsensitive<String>;
+
+	fn to_owned(&self) -> Self::Owned {
+		AsciiCaseInsensitive(String::from(&self.0))
+	}
+}
+
+impl<S: AsRef<str> + ?Sized, T: AsRef<str> + ?Sized> PartialEq<AsciiCaseInsensitive<T>>
+	for AsciiCaseInsensitive<S>
+{
+	fn eq(&self, other: &AsciiCaseInsensitive<T>) -> bool {
+		self.as_str().eq_ignore_ascii_case(other.as_str())
+	}
+}
+
+impl<S: AsRef<str> + ?Sized> Eq for AsciiCaseInsensitive<S> {}
+
+impl<S: AsRef<str> + ?Sized, T: AsRef<str> + ?Sized> PartialOrd<AsciiCaseInsensitive<T>>
+	for AsciiCaseInsensitive<S>
+{
+	fn partial_cmp(&self, other: &AsciiCaseInsensitive<T>) -> Option<Ordering> {
+		Some(cmp_ascii_lowercase(self.as_str(), other.as_str()))
+	}
+}
+
+impl<S: AsRef<str> + ?Sized> Ord for AsciiCaseInsensitive<S> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		cmp_ascii_lowercase(self.as_str(), other.as_str())
+	}
+}
+
+impl<S: AsRef<str> + ?Sized> Hash for AsciiCaseInsensitive<S> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		for byte in self.as_str().bytes() {
+			state.write_u8(byte.to_ascii_lowercase());
+		}
+		// Matches the terminator that `str` hashes with, so that a sequence of
+		// keys can't hash the same as their concatenation
+		state.write_u8(0xff);
+	}
+}
+
+impl<S: fmt::Debug + ?Sized> fmt::Debug for AsciiCaseInsensitive<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<S: fmt::Display + ?Sized> fmt::Display for AsciiCaseInsensitive<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<S> From<S> for AsciiCaseInsensitive<S> {
+	fn from(s: S) -> Self {
+		Self(s)
+	}
+}
+
+impl<S: DeepClone> DeepClone for AsciiCaseInsensitive<S> {
+	fn clone_deep(&self) -> Self {
+		Self(self.0.clone_deep())
+	}
+}
+
+fn cmp_ascii_lowercase(a: &str, b: &str) -> Ordering {
+	let a = a.bytes().map(|byte| byte.to_ascii_lowercase());
+	let b = b.bytes().map(|byte| byte.to_ascii_lowercase());
+	a.cmp(b)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::string::ToString;
+
+	struct Fnv(u64);
+
+	impl Hasher for Fnv {
+		fn finish(&self) -> u64 {
+			self.0
+		}
+
+		fn write(&mut self, bytes: &[u8]) {
+			for &byte in bytes {
+				self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3);
+			}
+		}
+	}
+
+	fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+		let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+		value.hash(&mut hasher);
+		hasher.finish()
+	}
+
+	#[test]
+	fn test_ascii_case_insensitive_eq_hash() {
+		let a = AsciiCaseInsensitive(String::from("Content-Type"));
+		let b = AsciiCaseInsensitive::new("cONTENT-tYPE");
+		assert!(a == *b);
+		assert_eq!(hash(&a), hash(b));
+		assert_eq!(hash(&a), hash::<AsciiCaseInsensitive<str>>(a.borrow()));
+
+		// Only ASCII letters are folded
+		assert!(AsciiCaseInsensitive("\u{212A}") != AsciiCaseInsensitive("k"));
+		assert!(AsciiCaseInsensitive("É") != AsciiCaseInsensitive("é"));
+	}
+
+	#[test]
+	fn test_ascii_case_insensitive_ord() {
+		// Letters compare as lowercase, so they all sort after '_'
+		assert!(AsciiCaseInsensitive("_") < AsciiCaseInsensitive("A"));
+		assert!(AsciiCaseInsensitive("_") < AsciiCaseInsensitive("a"));
+		assert_eq!(
+			AsciiCaseInsensitive("abc").cmp(&AsciiCaseInsensitive("ABD")),
+			Ordering::Less
+		);
+		assert_eq!(
+			AsciiCaseInsensitive("abc").cmp(&AsciiCaseInsensitive("ABC")),
+			Ordering::Equal
+		);
+		assert_eq!(AsciiCaseInsensitive("Abc").to_string(), "Abc");
+	}
+}
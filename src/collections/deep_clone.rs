@@ -0,0 +1,191 @@
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::rc::Rc;
+use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::collections::{List, OrderedMap, OrderedMultiMap, OrderedSet, Queue, Stack};
+#[cfg(feature = "json")]
+use crate::json::{InfraNumber, InfraValue};
+
+/// Clones a value along with everything it points to, including values
+/// behind shared pointers like [`Rc`] and [`Arc`].
+///
+/// The WHATWG Infra Standard's [clone][whatwg-infra-dfn] of a list or map
+/// is shallow: the new container holds the same items as the original.
+/// The containers' `clone_shallow()` methods (and their [`Clone`] impls)
+/// follow it, so an `Rc` item of a clone still points at the same value
+/// as the original. `clone_deep()` gives each `Rc` and `Arc` its own
+/// copy instead.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-clone
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use whatwg_infra::{DeepClone, List};
+///
+/// let mut list = List::new();
+/// list.append(Rc::new(String::from("a")));
+///
+/// let shallow = list.clone_shallow();
+/// let deep = list.clone_deep();
+/// assert!(Rc::ptr_eq(&list[0], &shallow[0]));
+/// assert!(!Rc::ptr_eq(&list[0], &deep[0]));
+/// assert_eq!(list, deep);
+/// ```
+pub trait DeepClone {
+	/// Returns a copy of the value that doesn't share anything with it
+	#[must_use]
+	fn clone_deep(&self) -> Self;
+}
+
+macro_rules! impl_deep_clone_with_clone {
+	($($ty:ty),* $(,)?) => {
+		$(
+			impl DeepClone for $ty {
+				#[inline]
+				fn clone_deep(&self) -> Self {
+					self.clone()
+				}
+			}
+		)*
+	};
+}
+
+impl_deep_clone_with_clone!(
+	bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, String,
+);
+
+#[cfg(feature = "json")]
+impl_deep_clone_with_clone!(InfraNumber);
+
+impl<T: ?Sized> DeepClone for &T {
+	/// References are copied, since the value they point to isn't owned
+	#[inline]
+	fn clone_deep(&self) -> Self {
+		self
+	}
+}
+
+impl<T: DeepClone> DeepClone for Box<T> {
+	fn clone_deep(&self) -> Self {
+		Box::new((**self).clone_deep())
+	}
+}
+
+impl<T: DeepClone> DeepClone for Rc<T> {
+	fn clone_deep(&self) -> Self {
+		Rc::new((**self).clone_deep())
+	}
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: DeepClone> DeepClone for Arc<T> {
+	fn clone_deep(&self) -> Self {
+		Arc::new((**self).clone_deep())
+	}
+}
+
+impl<T: DeepClone> DeepClone for Option<T> {
+	fn clone_deep(&self) -> Self {
+		self.as_ref().map(T::clone_deep)
+	}
+}
+
+impl<A: DeepClone, B: DeepClone> DeepClone for (A, B) {
+	fn clone_deep(&self) -> Self {
+		(self.0.clone_deep(), self.1.clone_deep())
+	}
+}
+
+impl<T: DeepClone> DeepClone for Vec<T> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(T::clone_deep).collect()
+	}
+}
+
+impl<T: DeepClone> DeepClone for VecDeque<T> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(T::clone_deep).collect()
+	}
+}
+
+impl<T: DeepClone> DeepClone for List<T> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(T::clone_deep).collect()
+	}
+}
+
+impl<T: DeepClone> DeepClone for OrderedSet<T> {
+	fn clone_deep(&self) -> Self {
+		// Deep clones of distinct items stay distinct, so there's nothing to deduplicate
+		OrderedSet::from_unique_items(self.iter().map(T::clone_deep).collect())
+	}
+}
+
+impl<T: DeepClone> DeepClone for Stack<T> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(T::clone_deep).collect()
+	}
+}
+
+impl<T: DeepClone> DeepClone for Queue<T> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(T::clone_deep).collect()
+	}
+}
+
+impl<K: DeepClone, V: DeepClone> DeepClone for OrderedMap<K, V> {
+	fn clone_deep(&self) -> Self {
+		// Deep clones of distinct keys stay distinct, so there's nothing to deduplicate
+		OrderedMap::from_unique_entries(
+			self.iter()
+				.map(|(k, v)| (k.clone_deep(), v.clone_deep()))
+				.collect(),
+		)
+	}
+}
+
+impl<K: DeepClone, V: DeepClone> DeepClone for OrderedMultiMap<K, V> {
+	fn clone_deep(&self) -> Self {
+		self.iter().map(DeepClone::clone_deep).collect()
+	}
+}
+
+#[cfg(feature = "json")]
+impl DeepClone for InfraValue {
+	fn clone_deep(&self) -> Self {
+		match self {
+			Self::Null => Self::Null,
+			Self::Boolean(b) => Self::Boolean(*b),
+			Self::Number(n) => Self::Number(*n),
+			Self::String(s) => Self::String(s.clone()),
+			Self::List(items) => Self::List(items.clone_deep()),
+			Self::Map(map) => Self::Map(map.clone_deep()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_deep_clone_unshares_pointers() {
+		let shared = Rc::new(1);
+		let mut map = OrderedMap::new();
+		map.set(String::from("a"), (Rc::clone(&shared), Box::new(Some(2))));
+
+		let deep = map.clone_deep();
+		assert_eq!(deep, map);
+		assert!(!Rc::ptr_eq(&deep.get("a").unwrap().0, &shared));
+		assert!(Rc::ptr_eq(
+			&map.clone_shallow().get("a").unwrap().0,
+			&shared
+		));
+	}
+}
@@ -0,0 +1,679 @@
+extern crate alloc;
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::ops::{Index, IndexMut, Range};
+use core::slice;
+
+/// A finite ordered sequence of items, as described by the
+/// [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Each method maps onto one of the list operations defined by the
+/// standard, so spec algorithms can be translated one step at a time.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::List;
+///
+/// let mut list = List::new();
+/// list.append("b");
+/// list.prepend("a");
+/// list.extend(["c", "d"]);
+///
+/// assert_eq!(list.size(), 4);
+/// assert!(list.contains(&"c"));
+/// assert_eq!(list.as_slice(), &["a", "b", "c", "d"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct List<T> {
+	items: Vec<T>,
+}
+
+impl<T> List<T> {
+	/// Creates a new, empty list
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { items: Vec::new() }
+	}
+
+	/// Creates a new, empty list with space for at least `capacity` items
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			items: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Adds an item to the end of the list.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-append
+	pub fn append(&mut self, item: T) {
+		self.items.push(item);
+	}
+
+	/// Appends each item of another list or iterator, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-extend
+	pub fn extend<I>(&mut self, items: I)
+	where
+		I: IntoIterator<Item = T>,
+	{
+		self.items.extend(items);
+	}
+
+	/// Adds an item to the beginning of the list.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-prepend
+	pub fn prepend(&mut self, item: T) {
+		self.items.insert(0, item);
+	}
+
+	/// Replaces every item that matches the condition with a copy of the
+	/// given item, or does nothing if none match.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-replace
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut list = List::new();
+	/// list.extend([1, 2, 3, 4]);
+	/// list.replace(|&n| n % 2 == 0, 0);
+	/// assert_eq!(list.as_slice(), &[1, 0, 3, 0]);
+	/// ```
+	pub fn replace<F>(&mut self, mut condition: F, item: T)
+	where
+		F: FnMut(&T) -> bool,
+		T: Clone,
+	{
+		for existing in self.items.iter_mut() {
+			if condition(existing) {
+				*existing = item.clone();
+			}
+		}
+	}
+
+	/// Inserts an item before the given index. An index equal to the size
+	/// of the list adds the item to the end.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-insert
+	///
+	/// # Panics
+	/// Panics if `index` is greater than the size of the list.
+	pub fn insert(&mut self, index: usize, item: T) {
+		self.items.insert(index, item);
+	}
+
+	/// Inserts an item before the given index like [`List::insert()`], but
+	/// returns the item back instead of panicking if `index` is greater than
+	/// the size of the list.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut list = List::from(["a", "c"]);
+	/// assert_eq!(list.try_insert(1, "b"), Ok(()));
+	/// assert_eq!(list.try_insert(4, "d"), Err("d"));
+	/// assert_eq!(list.as_slice(), &["a", "b", "c"]);
+	/// ```
+	pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), T> {
+		if index > self.items.len() {
+			return Err(item);
+		}
+		self.items.insert(index, item);
+		Ok(())
+	}
+
+	/// Removes every item that's equal to the given item, or does nothing
+	/// if there are none.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-remove
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut list = List::new();
+	/// list.extend(["a", "b", "c", "a"]);
+	/// list.remove(&"a");
+	/// assert_eq!(list.as_slice(), &["b", "c"]);
+	/// ```
+	pub fn remove(&mut self, item: &T)
+	where
+		T: PartialEq,
+	{
+		self.items.retain(|existing| existing != item);
+	}
+
+	/// Removes every item that matches the given condition, and returns
+	/// the removed items in the order they were in.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-remove
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut headers = List::new();
+	/// headers.extend([("accept", "*/*"), ("cookie", "a=1"), ("cookie", "b=2")]);
+	///
+	/// let cookies = headers.remove_if(|(name, _)| *name == "cookie");
+	/// assert_eq!(headers.as_slice(), &[("accept", "*/*")]);
+	/// assert_eq!(cookies.as_slice(), &[("cookie", "a=1"), ("cookie", "b=2")]);
+	/// ```
+	pub fn remove_if<F>(&mut self, mut condition: F) -> List<T>
+	where
+		F: FnMut(&T) -> bool,
+	{
+		let (removed, kept) = core::mem::take(&mut self.items)
+			.into_iter()
+			.partition(|item| condition(item));
+		self.items = kept;
+		Self { items: removed }
+	}
+
+	/// Keeps only the items that match the given condition, removing the
+	/// rest without returning them.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut list = List::new();
+	/// list.extend(1..=6);
+	/// list.retain(|n| n % 2 == 0);
+	/// assert_eq!(list.as_slice(), &[2, 4, 6]);
+	/// ```
+	pub fn retain<F>(&mut self, condition: F)
+	where
+		F: FnMut(&T) -> bool,
+	{
+		self.items.retain(condition);
+	}
+
+	/// Removes all items from the list.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-empty
+	pub fn empty(&mut self) {
+		self.items.clear();
+	}
+
+	/// Checks if the list contains an item that's equal to the given item.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-contain
+	#[must_use]
+	pub fn contains(&self, item: &T) -> bool
+	where
+		T: PartialEq,
+	{
+		self.items.contains(item)
+	}
+
+	/// Returns the number of items in the list.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-size
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if the list has a size of zero.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-is-empty
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Returns the range of indices of the list, from 0 up to (but not
+	/// including) its size.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-get-the-indices
+	#[must_use]
+	pub fn indices(&self) -> Range<usize> {
+		0..self.items.len()
+	}
+
+	/// Sorts the list in ascending order, using `less_than` to decide if
+	/// one item is less than another. Sorting is stable, so items that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-sort-in-ascending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two items are each less than the other, since the sort can
+	/// detect the inconsistency.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::List;
+	///
+	/// let mut list = List::new();
+	/// list.extend([(2, "a"), (1, "b"), (2, "c")]);
+	/// list.sort_ascending(|a, b| a.0 < b.0);
+	/// assert_eq!(list.as_slice(), &[(1, "b"), (2, "a"), (2, "c")]);
+	/// ```
+	pub fn sort_ascending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut(&T, &T) -> bool,
+	{
+		self.items
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, a, b));
+	}
+
+	/// Sorts the list in descending order, using `less_than` to decide if
+	/// one item is less than another. Sorting is stable, so items that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-sort-in-descending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two items are each less than the other, since the sort can
+	/// detect the inconsistency.
+	pub fn sort_descending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut(&T, &T) -> bool,
+	{
+		self.items
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, b, a));
+	}
+
+	/// Returns a cursor that starts at the first item of the list, and can
+	/// remove and insert items as it moves through the list
+	pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+		CursorMut {
+			list: self,
+			index: 0,
+		}
+	}
+
+	/// Returns a new list with the same items, in the same order.
+	///
+	/// This is the same as [`Clone::clone()`]: items are cloned with their
+	/// own `Clone` impls, so items that are shared pointers like `Rc` point
+	/// at the same values as the originals. See [`DeepClone`] for a clone
+	/// that copies those values too.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-clone
+	///
+	/// [`DeepClone`]: crate::collections::DeepClone
+	#[must_use]
+	pub fn clone_shallow(&self) -> Self
+	where
+		Self: Clone,
+	{
+		self.clone()
+	}
+
+	/// Returns a reference to the item at the given index, if it exists
+	#[must_use]
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.items.get(index)
+	}
+
+	/// Returns a mutable reference to the item at the given index, if it exists
+	#[must_use]
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+		self.items.get_mut(index)
+	}
+
+	/// Returns an iterator over the items of the list, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-iterate
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.items.iter()
+	}
+
+	/// Returns an iterator that allows modifying each item of the list, in order
+	pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+		self.items.iter_mut()
+	}
+
+	/// Returns the items of the list as a slice
+	#[must_use]
+	pub fn as_slice(&self) -> &[T] {
+		&self.items
+	}
+
+	/// Converts the list into a vector of its items
+	#[must_use]
+	pub fn into_vec(self) -> Vec<T> {
+		self.items
+	}
+}
+
+impl<T> Default for List<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.items.iter()).finish()
+	}
+}
+
+impl<T> Index<usize> for List<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		&self.items[index]
+	}
+}
+
+impl<T> IndexMut<usize> for List<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T {
+		&mut self.items[index]
+	}
+}
+
+impl<T> From<Vec<T>> for List<T> {
+	fn from(items: Vec<T>) -> Self {
+		Self { items }
+	}
+}
+
+impl<T, const N: usize> From<[T; N]> for List<T> {
+	fn from(items: [T; N]) -> Self {
+		Self::from(Vec::from(items))
+	}
+}
+
+impl<T> From<List<T>> for Vec<T> {
+	fn from(value: List<T>) -> Self {
+		value.into_vec()
+	}
+}
+
+impl<T> FromIterator<T> for List<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		Self {
+			items: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<T> Extend<T> for List<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		List::extend(self, iter);
+	}
+}
+
+impl<T> IntoIterator for List<T> {
+	type Item = T;
+	type IntoIter = vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+	type Item = &'a T;
+	type IntoIter = slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+	type Item = &'a mut T;
+	type IntoIter = slice::IterMut<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter_mut()
+	}
+}
+
+/// A cursor over a [`List`] that can remove and insert items while
+/// traversing it, created by [`List::cursor_mut()`].
+///
+/// The cursor points at one item at a time, starting at the first one.
+/// Removing the current item moves the cursor on to the item after it.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::List;
+///
+/// let mut list = List::new();
+/// list.extend([1, 2, 3, 4]);
+///
+/// let mut cursor = list.cursor_mut();
+/// while let Some(&item) = cursor.current() {
+///     if item % 2 == 0 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.insert_after(item * 10);
+///         cursor.move_next();
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(list.as_slice(), &[1, 10, 3, 30]);
+/// ```
+#[derive(Debug)]
+pub struct CursorMut<'a, T> {
+	list: &'a mut List<T>,
+	index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+	/// Returns the index of the current item, which is the size of the
+	/// list if the cursor is past the end
+	#[must_use]
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the current item, or `None` if the cursor is past the end
+	#[must_use]
+	pub fn current(&self) -> Option<&T> {
+		self.list.items.get(self.index)
+	}
+
+	/// Returns a mutable reference to the current item, or `None` if the
+	/// cursor is past the end
+	#[must_use]
+	pub fn current_mut(&mut self) -> Option<&mut T> {
+		self.list.items.get_mut(self.index)
+	}
+
+	/// Moves the cursor to the next item, or does nothing if the cursor is
+	/// past the end
+	pub fn move_next(&mut self) {
+		if self.index < self.list.items.len() {
+			self.index += 1;
+		}
+	}
+
+	/// Removes the current item and returns it, moving the cursor to the
+	/// item after it. Returns `None` if the cursor is past the end.
+	pub fn remove_current(&mut self) -> Option<T> {
+		if self.index < self.list.items.len() {
+			Some(self.list.items.remove(self.index))
+		} else {
+			None
+		}
+	}
+
+	/// Inserts an item before the current one. The cursor keeps pointing
+	/// at the same item, so the inserted item isn't visited.
+	pub fn insert_before(&mut self, item: T) {
+		self.list.items.insert(self.index, item);
+		self.index += 1;
+	}
+
+	/// Inserts an item after the current one, so that it's visited
+	/// next. If the cursor is past the end, the item is appended and
+	/// becomes the current item.
+	pub fn insert_after(&mut self, item: T) {
+		let index = (self.index + 1).min(self.list.items.len());
+		self.list.items.insert(index, item);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn list_of<const N: usize>(items: [i32; N]) -> List<i32> {
+		let mut list = List::new();
+		list.extend(items);
+		list
+	}
+
+	#[test]
+	fn test_list_append_prepend_insert() {
+		let mut list = List::new();
+		list.append(2);
+		list.prepend(0);
+		list.insert(1, 1);
+		list.insert(3, 3);
+
+		assert_eq!(list.as_slice(), &[0, 1, 2, 3]);
+		assert_eq!(list.indices(), 0..4);
+		assert_eq!(list[2], 2);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_list_insert_out_of_bounds() {
+		let mut list = list_of([1]);
+		list.insert(2, 0);
+	}
+
+	#[test]
+	fn test_list_replace_remove() {
+		let mut list = list_of([1, 2, 1, 3]);
+		list.replace(|&n| n == 1, 5);
+		assert_eq!(list.as_slice(), &[5, 2, 5, 3]);
+
+		list.remove(&5);
+		assert_eq!(list.as_slice(), &[2, 3]);
+
+		list.remove(&7);
+		assert_eq!(list.as_slice(), &[2, 3]);
+	}
+
+	#[test]
+	fn test_list_empty_size_contains() {
+		let mut list = list_of([1, 2]);
+		assert_eq!(list.size(), 2);
+		assert!(list.contains(&2));
+		assert!(!list.contains(&3));
+		assert!(!list.is_empty());
+
+		list.empty();
+		assert_eq!(list.size(), 0);
+		assert!(list.is_empty());
+		assert_eq!(list, List::default());
+	}
+
+	#[test]
+	fn test_list_clone_iterate() {
+		let mut list = list_of([1, 2, 3]);
+		let cloned = list.clone();
+		for item in &mut list {
+			*item *= 10;
+		}
+
+		assert_eq!(cloned.iter().copied().sum::<i32>(), 6);
+		assert_eq!(list.into_vec(), alloc::vec![10, 20, 30]);
+	}
+
+	#[test]
+	fn test_list_sort_stable() {
+		let mut list = List::new();
+		list.extend([(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')]);
+
+		list.sort_descending(|a, b| a.0 < b.0);
+		assert_eq!(list.as_slice(), &[(1, 'a'), (1, 'c'), (0, 'b'), (0, 'd')]);
+
+		list.sort_ascending(|a, b| a.0 < b.0);
+		assert_eq!(list.as_slice(), &[(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+	}
+
+	#[test]
+	fn test_list_remove_if_retain() {
+		let mut list = list_of([1, 2, 3, 4, 5]);
+		let removed = list.remove_if(|&n| n > 3);
+		assert_eq!(list.as_slice(), &[1, 2, 3]);
+		assert_eq!(removed.as_slice(), &[4, 5]);
+
+		assert!(list.remove_if(|&n| n > 3).is_empty());
+		assert_eq!(list.as_slice(), &[1, 2, 3]);
+
+		list.retain(|&n| n != 2);
+		assert_eq!(list.as_slice(), &[1, 3]);
+	}
+
+	#[test]
+	fn test_list_cursor() {
+		let mut list = list_of([1, 2, 3]);
+		let mut cursor = list.cursor_mut();
+		cursor.insert_before(0);
+		assert_eq!(cursor.index(), 1);
+		assert_eq!(cursor.current(), Some(&1));
+
+		cursor.move_next();
+		assert_eq!(cursor.remove_current(), Some(2));
+		*cursor.current_mut().unwrap() = 30;
+		cursor.move_next();
+		cursor.move_next();
+		assert_eq!(cursor.current(), None);
+		assert_eq!(cursor.remove_current(), None);
+		cursor.insert_after(4);
+		assert_eq!(cursor.current(), Some(&4));
+
+		assert_eq!(list.as_slice(), &[0, 1, 30, 4]);
+	}
+
+	#[test]
+	fn test_list_conversions() {
+		let list: List<i32> = (1..=3).collect();
+		assert_eq!(list, List::from([1, 2, 3]));
+		assert_eq!(list, List::from(alloc::vec![1, 2, 3]));
+
+		let mut list = list;
+		Extend::extend(&mut list, [4]);
+		assert_eq!(Vec::from(list), alloc::vec![1, 2, 3, 4]);
+	}
+}
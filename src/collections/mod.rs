@@ -0,0 +1,49 @@
+use core::cmp::Ordering;
+
+/// The [`AsciiCaseInsensitive`] string wrapper
+pub mod ascii_case_insensitive;
+pub use self::ascii_case_insensitive::AsciiCaseInsensitive;
+
+/// The [`DeepClone`] trait
+pub mod deep_clone;
+pub use self::deep_clone::DeepClone;
+
+/// The [`List`] type
+pub mod list;
+pub use self::list::List;
+
+/// The [`OrderedMap`] type, and its entry, cursor, and iterator types
+pub mod ordered_map;
+pub use self::ordered_map::OrderedMap;
+
+/// The [`OrderedMultiMap`] type and its iterator
+pub mod ordered_multimap;
+pub use self::ordered_multimap::OrderedMultiMap;
+
+/// The [`OrderedSet`] type
+pub mod ordered_set;
+pub use self::ordered_set::OrderedSet;
+
+/// The [`Queue`] type
+pub mod queue;
+pub use self::queue::Queue;
+
+/// The [`Stack`] type
+pub mod stack;
+pub use self::stack::Stack;
+
+/// Compares two items with a "less than" algorithm, treating items that
+/// aren't less than each other as equal, so that a stable sort keeps them
+/// in their original order.
+fn less_than_ordering<T, F>(less_than: &mut F, a: &T, b: &T) -> Ordering
+where
+	F: FnMut(&T, &T) -> bool,
+{
+	if less_than(a, b) {
+		Ordering::Less
+	} else if less_than(b, a) {
+		Ordering::Greater
+	} else {
+		Ordering::Equal
+	}
+}
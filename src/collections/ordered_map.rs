@@ -0,0 +1,1026 @@
+extern crate alloc;
+use alloc::collections::BTreeMap;
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+/// A finite ordered sequence of key-value pairs with no duplicate keys,
+/// as described by the [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Entries keep the order they were added in. Setting the value of a key
+/// that's already in the map keeps the entry at its original position.
+///
+/// Entries are stored in a vector, so looking up a key takes linear time.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ordered-map
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::OrderedMap;
+///
+/// let mut map = OrderedMap::new();
+/// map.set("b", 1);
+/// map.set("a", 2);
+/// map.set("b", 3);
+///
+/// assert_eq!(map.get("b"), Some(&3));
+/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"b", &"a"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedMap<K, V> {
+	entries: Vec<(K, V)>,
+}
+
+impl<K, V> OrderedMap<K, V> {
+	/// Creates a new, empty ordered map
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			entries: Vec::new(),
+		}
+	}
+
+	/// Creates a new, empty ordered map with space for at least `capacity` entries
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			entries: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Creates an ordered map from entries that are known to have unique keys
+	pub(crate) fn from_unique_entries(entries: Vec<(K, V)>) -> Self {
+		Self { entries }
+	}
+
+	/// Returns the number of entries in the map.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-size
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Checks if the map has a size of zero.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-is-empty
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Removes all entries from the map.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-clear
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	/// Sorts the map in ascending order, using `less_than` to decide if
+	/// one entry is less than another. Sorting is stable, so entries that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-sort-in-ascending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two entries are each less than the other, since the sort can
+	/// detect the inconsistency.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedMap;
+	///
+	/// let mut map = OrderedMap::new();
+	/// map.set("b", 1);
+	/// map.set("c", 3);
+	/// map.set("a", 2);
+	///
+	/// map.sort_ascending(|(a, _), (b, _)| a < b);
+	/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"a", &"b", &"c"]);
+	///
+	/// map.sort_descending(|(_, a), (_, b)| a < b);
+	/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"c", &"a", &"b"]);
+	/// ```
+	pub fn sort_ascending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut((&K, &V), (&K, &V)) -> bool,
+	{
+		let mut less_than = |a: &(K, V), b: &(K, V)| less_than((&a.0, &a.1), (&b.0, &b.1));
+		self.entries
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, a, b));
+	}
+
+	/// Sorts the map in descending order, using `less_than` to decide if
+	/// one entry is less than another. Sorting is stable, so entries that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-sort-in-descending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two entries are each less than the other, since the sort can
+	/// detect the inconsistency.
+	pub fn sort_descending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut((&K, &V), (&K, &V)) -> bool,
+	{
+		let mut less_than = |a: &(K, V), b: &(K, V)| less_than((&a.0, &a.1), (&b.0, &b.1));
+		self.entries
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, b, a));
+	}
+
+	/// Returns a cursor that starts at the first entry of the map, and can
+	/// remove and insert entries as it moves through the map
+	pub fn cursor_mut(&mut self) -> CursorMut<'_, K, V> {
+		CursorMut {
+			map: self,
+			index: 0,
+		}
+	}
+
+	/// Returns a new map with the same entries, in the same order.
+	///
+	/// This is the same as [`Clone::clone()`]: entries are cloned with their
+	/// own `Clone` impls, so entries that are shared pointers like `Rc` point
+	/// at the same values as the originals. See [`DeepClone`] for a clone
+	/// that copies those values too.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-clone
+	///
+	/// [`DeepClone`]: crate::collections::DeepClone
+	#[must_use]
+	pub fn clone_shallow(&self) -> Self
+	where
+		Self: Clone,
+	{
+		self.clone()
+	}
+
+	/// Keeps only the entries that match the given condition, keeping their
+	/// order.
+	///
+	/// Unlike removing entries one at a time, which shifts the rest of the
+	/// map each time, this removes every matching entry in a single pass,
+	/// so it's the fastest way to remove many entries at once.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-remove
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedMap;
+	///
+	/// let mut map = OrderedMap::new();
+	/// map.set("a", 1);
+	/// map.set("b", 2);
+	/// map.set("c", 3);
+	///
+	/// map.retain(|_, value| *value != 2);
+	/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"a", &"c"]);
+	/// ```
+	pub fn retain<F>(&mut self, mut condition: F)
+	where
+		F: FnMut(&K, &mut V) -> bool,
+	{
+		self.entries.retain_mut(|(k, v)| condition(k, v));
+	}
+
+	/// Returns the key and value of the entry at the given index, if it exists
+	#[must_use]
+	pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+		self.entries.get(index).map(|(k, v)| (k, v))
+	}
+
+	/// Returns an iterator over the keys of the map, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-getting-the-keys
+	pub fn keys(&self) -> Keys<'_, K, V> {
+		Keys {
+			inner: self.entries.iter(),
+		}
+	}
+
+	/// Returns an iterator over the values of the map, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-getting-the-values
+	pub fn values(&self) -> Values<'_, K, V> {
+		Values {
+			inner: self.entries.iter(),
+		}
+	}
+
+	/// Returns an iterator that allows modifying each value of the map, in order
+	pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+		ValuesMut {
+			inner: self.entries.iter_mut(),
+		}
+	}
+
+	/// Returns an iterator over the entries of the map, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-iterate
+	pub fn iter(&self) -> Iter<'_, K, V> {
+		Iter {
+			inner: self.entries.iter(),
+		}
+	}
+
+	/// Returns an iterator over the entries of the map, in order, that
+	/// allows modifying each value
+	pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+		IterMut {
+			inner: self.entries.iter_mut(),
+		}
+	}
+}
+
+impl<K: PartialEq, V> OrderedMap<K, V> {
+	fn position<Q>(&self, key: &Q) -> Option<usize>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.entries.iter().position(|(k, _)| k.borrow() == key)
+	}
+
+	/// Returns the value of the entry with the given key, if it exists.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-get
+	#[must_use]
+	pub fn get<Q>(&self, key: &Q) -> Option<&V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.position(key).map(|i| &self.entries[i].1)
+	}
+
+	/// Returns a mutable reference to the value of the entry with the given
+	/// key, if it exists
+	#[must_use]
+	pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.position(key).map(move |i| &mut self.entries[i].1)
+	}
+
+	/// Sets the value of the entry with the given key, keeping its position
+	/// if it already exists, or adding a new entry to the end of the map
+	/// otherwise. Returns the previous value, if there was one.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-set
+	pub fn set(&mut self, key: K, value: V) -> Option<V> {
+		match self.position(&key) {
+			Some(i) => Some(core::mem::replace(&mut self.entries[i].1, value)),
+			None => {
+				self.entries.push((key, value));
+				None
+			}
+		}
+	}
+
+	/// Returns the entry with the given key, which can be used to get the
+	/// value or insert one with a single lookup.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedMap;
+	///
+	/// let mut counts = OrderedMap::new();
+	/// for word in ["a", "b", "a"] {
+	///     *counts.entry(word).or_insert(0) += 1;
+	/// }
+	///
+	/// assert_eq!(counts.get("a"), Some(&2));
+	/// assert_eq!(counts.get("b"), Some(&1));
+	/// ```
+	pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+		match self.position(&key) {
+			Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+			None => Entry::Vacant(VacantEntry { map: self, key }),
+		}
+	}
+
+	/// Removes the entry with the given key, keeping the order of the other
+	/// entries. Returns the value of the removed entry, if it existed.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-remove
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedMap;
+	///
+	/// let mut map = OrderedMap::new();
+	/// map.set("a", 1);
+	/// map.set("b", 2);
+	/// map.set("c", 3);
+	///
+	/// assert_eq!(map.remove("b"), Some(2));
+	/// assert_eq!(map.remove("b"), None);
+	/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"a", &"c"]);
+	/// ```
+	pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.position(key).map(|i| self.entries.remove(i).1)
+	}
+
+	/// Removes the entry with the given key by moving the last entry into
+	/// its place. Returns the value of the removed entry, if it existed.
+	///
+	/// This doesn't shift the entries after the removed one, so it's
+	/// faster than [`OrderedMap::remove()`] on large maps, but it **doesn't
+	/// keep the order of the entries** like the WHATWG Infra Standard's
+	/// removal does. Only use it when the order of the map doesn't matter.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedMap;
+	///
+	/// let mut map = OrderedMap::new();
+	/// map.set("a", 1);
+	/// map.set("b", 2);
+	/// map.set("c", 3);
+	///
+	/// assert_eq!(map.swap_remove("a"), Some(1));
+	/// assert_eq!(map.keys().collect::<Vec<_>>(), vec![&"c", &"b"]);
+	/// ```
+	pub fn swap_remove<Q>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.position(key).map(|i| self.entries.swap_remove(i).1)
+	}
+
+	/// Checks if the map has an entry with the given key.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#map-exists
+	#[must_use]
+	pub fn contains_key<Q>(&self, key: &Q) -> bool
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.position(key).is_some()
+	}
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<K: PartialEq, V> From<Vec<(K, V)>> for OrderedMap<K, V> {
+	/// Creates an ordered map from the entries of a vector, as if each
+	/// entry was set in order, so a later entry with the same key as an
+	/// earlier one replaces its value but keeps its position
+	fn from(entries: Vec<(K, V)>) -> Self {
+		entries.into_iter().collect()
+	}
+}
+
+impl<K: PartialEq, V, const N: usize> From<[(K, V); N]> for OrderedMap<K, V> {
+	fn from(entries: [(K, V); N]) -> Self {
+		entries.into_iter().collect()
+	}
+}
+
+impl<K, V> From<OrderedMap<K, V>> for Vec<(K, V)> {
+	fn from(value: OrderedMap<K, V>) -> Self {
+		value.entries
+	}
+}
+
+impl<K, V> From<BTreeMap<K, V>> for OrderedMap<K, V> {
+	/// Creates an ordered map with the entries of a `BTreeMap`, in
+	/// ascending order of their keys
+	fn from(map: BTreeMap<K, V>) -> Self {
+		Self::from_unique_entries(map.into_iter().collect())
+	}
+}
+
+impl<K: Ord, V> From<OrderedMap<K, V>> for BTreeMap<K, V> {
+	fn from(map: OrderedMap<K, V>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> From<std::collections::HashMap<K, V, S>> for OrderedMap<K, V> {
+	/// Creates an ordered map with the entries of a `HashMap`, in the
+	/// `HashMap`'s iteration order
+	fn from(map: std::collections::HashMap<K, V, S>) -> Self {
+		Self::from_unique_entries(map.into_iter().collect())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> From<OrderedMap<K, V>> for std::collections::HashMap<K, V, S>
+where
+	K: Eq + core::hash::Hash,
+	S: core::hash::BuildHasher + Default,
+{
+	fn from(map: OrderedMap<K, V>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<K: PartialEq, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+	/// Creates an ordered map from the entries of an iterator, as if each
+	/// entry was set in order
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		let mut map = Self::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<K: PartialEq, V> Extend<(K, V)> for OrderedMap<K, V> {
+	/// Sets each entry of an iterator, in order
+	fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+		for (key, value) in iter {
+			self.set(key, value);
+		}
+	}
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+	type Item = (K, V);
+	type IntoIter = IntoIter<K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter {
+			inner: self.entries.into_iter(),
+		}
+	}
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
+	type Item = (&'a K, &'a V);
+	type IntoIter = Iter<'a, K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, K, V> IntoIterator for &'a mut OrderedMap<K, V> {
+	type Item = (&'a K, &'a mut V);
+	type IntoIter = IterMut<'a, K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+/// An entry of an [`OrderedMap`], created by [`OrderedMap::entry()`]
+#[derive(Debug)]
+pub enum Entry<'a, K, V> {
+	/// An entry whose key is in the map
+	Occupied(OccupiedEntry<'a, K, V>),
+	/// An entry whose key isn't in the map
+	Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+	/// Returns the key of the entry
+	#[must_use]
+	pub fn key(&self) -> &K {
+		match self {
+			Self::Occupied(entry) => entry.key(),
+			Self::Vacant(entry) => entry.key(),
+		}
+	}
+
+	/// Returns the value of the entry, first adding it to the end of
+	/// the map with the given value if it's vacant
+	pub fn or_insert(self, value: V) -> &'a mut V {
+		match self {
+			Self::Occupied(entry) => entry.into_mut(),
+			Self::Vacant(entry) => entry.insert(value),
+		}
+	}
+
+	/// Returns the value of the entry, first adding it to the end of
+	/// the map with the result of `default` if it's vacant
+	pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+	where
+		F: FnOnce() -> V,
+	{
+		match self {
+			Self::Occupied(entry) => entry.into_mut(),
+			Self::Vacant(entry) => entry.insert(default()),
+		}
+	}
+
+	/// Returns the value of the entry, first adding it to the end of
+	/// the map with the result of calling `default` with the key if it's
+	/// vacant
+	pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+	where
+		F: FnOnce(&K) -> V,
+	{
+		match self {
+			Self::Occupied(entry) => entry.into_mut(),
+			Self::Vacant(entry) => {
+				let value = default(entry.key());
+				entry.insert(value)
+			}
+		}
+	}
+
+	/// Returns the value of the entry, first adding it to the end of
+	/// the map with the default value if it's vacant
+	pub fn or_default(self) -> &'a mut V
+	where
+		V: Default,
+	{
+		self.or_insert_with(V::default)
+	}
+
+	/// Modifies the value of the entry if it's occupied, and returns the
+	/// entry so that it can be chained with `or_insert()` and friends
+	#[must_use]
+	pub fn and_modify<F>(mut self, f: F) -> Self
+	where
+		F: FnOnce(&mut V),
+	{
+		if let Self::Occupied(entry) = &mut self {
+			f(entry.get_mut());
+		}
+		self
+	}
+}
+
+/// An entry of an [`OrderedMap`] whose key is in the map
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, K, V> {
+	map: &'a mut OrderedMap<K, V>,
+	index: usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+	/// Returns the index of the entry in the map
+	#[must_use]
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the key of the entry
+	#[must_use]
+	pub fn key(&self) -> &K {
+		&self.map.entries[self.index].0
+	}
+
+	/// Returns the value of the entry
+	#[must_use]
+	pub fn get(&self) -> &V {
+		&self.map.entries[self.index].1
+	}
+
+	/// Returns a mutable reference to the value of the entry
+	#[must_use]
+	pub fn get_mut(&mut self) -> &mut V {
+		&mut self.map.entries[self.index].1
+	}
+
+	/// Converts the entry into a mutable reference to its value, which
+	/// lives as long as the map
+	#[must_use]
+	pub fn into_mut(self) -> &'a mut V {
+		&mut self.map.entries[self.index].1
+	}
+
+	/// Sets the value of the entry, keeping its position, and returns the
+	/// previous value
+	pub fn insert(&mut self, value: V) -> V {
+		core::mem::replace(self.get_mut(), value)
+	}
+
+	/// Removes the entry from the map, keeping the order of the other
+	/// entries, and returns its value
+	pub fn remove(self) -> V {
+		self.remove_entry().1
+	}
+
+	/// Removes the entry from the map, keeping the order of the other
+	/// entries, and returns its key and value
+	pub fn remove_entry(self) -> (K, V) {
+		self.map.entries.remove(self.index)
+	}
+
+	/// Removes the entry from the map by moving the last entry into its
+	/// place, and returns its key and value. This **doesn't keep the order
+	/// of the entries**; see [`OrderedMap::swap_remove()`].
+	pub fn swap_remove_entry(self) -> (K, V) {
+		self.map.entries.swap_remove(self.index)
+	}
+}
+
+/// An entry of an [`OrderedMap`] whose key isn't in the map
+#[derive(Debug)]
+pub struct VacantEntry<'a, K, V> {
+	map: &'a mut OrderedMap<K, V>,
+	key: K,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+	/// Returns the key of the entry
+	#[must_use]
+	pub fn key(&self) -> &K {
+		&self.key
+	}
+
+	/// Takes ownership of the key of the entry
+	#[must_use]
+	pub fn into_key(self) -> K {
+		self.key
+	}
+
+	/// Adds the entry to the end of the map with the given value, and
+	/// returns a mutable reference to the value
+	pub fn insert(self, value: V) -> &'a mut V {
+		let entries = &mut self.map.entries;
+		entries.push((self.key, value));
+		&mut entries.last_mut().unwrap().1
+	}
+}
+
+/// A cursor over an [`OrderedMap`] that can remove and insert entries while
+/// traversing it, created by [`OrderedMap::cursor_mut()`].
+///
+/// The cursor points at one entry at a time, starting at the first one.
+/// Removing the current entry moves the cursor on to the entry after it.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::OrderedMap;
+///
+/// let mut headers = OrderedMap::new();
+/// headers.set("accept", "*/*");
+/// headers.set("cookie", "a=1");
+/// headers.set("host", "example.com");
+///
+/// let mut cursor = headers.cursor_mut();
+/// while let Some((&name, _)) = cursor.current() {
+///     if name == "cookie" {
+///         cursor.remove_current();
+///     } else {
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(headers.keys().collect::<Vec<_>>(), vec![&"accept", &"host"]);
+/// ```
+#[derive(Debug)]
+pub struct CursorMut<'a, K, V> {
+	map: &'a mut OrderedMap<K, V>,
+	index: usize,
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+	/// Returns the index of the current entry, which is the size of the
+	/// map if the cursor is past the end
+	#[must_use]
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the key and value of the current entry, or `None` if the
+	/// cursor is past the end
+	#[must_use]
+	pub fn current(&self) -> Option<(&K, &V)> {
+		self.map.get_index(self.index)
+	}
+
+	/// Returns a mutable reference to the value of the current entry, or
+	/// `None` if the cursor is past the end
+	#[must_use]
+	pub fn current_value_mut(&mut self) -> Option<&mut V> {
+		self.map.entries.get_mut(self.index).map(|(_, v)| v)
+	}
+
+	/// Moves the cursor to the next entry, or does nothing if the cursor is
+	/// past the end
+	pub fn move_next(&mut self) {
+		if self.index < self.map.entries.len() {
+			self.index += 1;
+		}
+	}
+
+	/// Removes the current entry and returns its key and value, moving the
+	/// cursor to the entry after it. Returns `None` if the cursor is past
+	/// the end.
+	pub fn remove_current(&mut self) -> Option<(K, V)> {
+		if self.index < self.map.entries.len() {
+			Some(self.map.entries.remove(self.index))
+		} else {
+			None
+		}
+	}
+}
+
+impl<'a, K: PartialEq, V> CursorMut<'a, K, V> {
+	/// Inserts an entry before the current one, unless the map already
+	/// contains the key. The cursor keeps pointing at the same entry, so
+	/// the inserted entry isn't visited. Returns `true` if the entry was
+	/// added.
+	pub fn insert_before(&mut self, key: K, value: V) -> bool {
+		if self.map.contains_key(&key) {
+			return false;
+		}
+		self.map.entries.insert(self.index, (key, value));
+		self.index += 1;
+		true
+	}
+
+	/// Inserts an entry after the current one, unless the map already
+	/// contains the key, so that it's visited next. If the cursor is past
+	/// the end, the entry is appended and becomes the current entry.
+	/// Returns `true` if the entry was added.
+	pub fn insert_after(&mut self, key: K, value: V) -> bool {
+		if self.map.contains_key(&key) {
+			return false;
+		}
+		let index = (self.index + 1).min(self.map.entries.len());
+		self.map.entries.insert(index, (key, value));
+		true
+	}
+}
+
+macro_rules! entry_iterator {
+	($(#[$doc:meta])* $name:ident<$($lt:lifetime,)? K, V>, $inner:ty, $item:ty, |$entry:ident| $map:expr) => {
+		$(#[$doc])*
+		#[derive(Debug)]
+		pub struct $name<$($lt,)? K, V> {
+			inner: $inner,
+		}
+
+		impl<$($lt,)? K, V> Iterator for $name<$($lt,)? K, V> {
+			type Item = $item;
+
+			fn next(&mut self) -> Option<Self::Item> {
+				self.inner.next().map(|$entry| $map)
+			}
+
+			fn size_hint(&self) -> (usize, Option<usize>) {
+				self.inner.size_hint()
+			}
+		}
+
+		impl<$($lt,)? K, V> DoubleEndedIterator for $name<$($lt,)? K, V> {
+			fn next_back(&mut self) -> Option<Self::Item> {
+				self.inner.next_back().map(|$entry| $map)
+			}
+		}
+
+		impl<$($lt,)? K, V> ExactSizeIterator for $name<$($lt,)? K, V> {}
+		impl<$($lt,)? K, V> FusedIterator for $name<$($lt,)? K, V> {}
+	};
+}
+
+entry_iterator!(
+	/// An iterator over the entries of an [`OrderedMap`]
+	Iter<'a, K, V>, slice::Iter<'a, (K, V)>, (&'a K, &'a V), |entry| (&entry.0, &entry.1)
+);
+entry_iterator!(
+	/// A mutable iterator over the entries of an [`OrderedMap`]
+	IterMut<'a, K, V>, slice::IterMut<'a, (K, V)>, (&'a K, &'a mut V), |entry| (&entry.0, &mut entry.1)
+);
+entry_iterator!(
+	/// An owning iterator over the entries of an [`OrderedMap`]
+	IntoIter<K, V>, vec::IntoIter<(K, V)>, (K, V), |entry| entry
+);
+entry_iterator!(
+	/// An iterator over the keys of an [`OrderedMap`]
+	Keys<'a, K, V>, slice::Iter<'a, (K, V)>, &'a K, |entry| &entry.0
+);
+entry_iterator!(
+	/// An iterator over the values of an [`OrderedMap`]
+	Values<'a, K, V>, slice::Iter<'a, (K, V)>, &'a V, |entry| &entry.1
+);
+entry_iterator!(
+	/// A mutable iterator over the values of an [`OrderedMap`]
+	ValuesMut<'a, K, V>, slice::IterMut<'a, (K, V)>, &'a mut V, |entry| &mut entry.1
+);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::vec;
+
+	#[test]
+	fn test_ordered_map_set_get() {
+		let mut map = OrderedMap::new();
+		assert_eq!(map.set("a", 1), None);
+		assert_eq!(map.set("b", 2), None);
+		assert_eq!(map.set("a", 3), Some(1));
+
+		assert_eq!(map.get("a"), Some(&3));
+		assert_eq!(map.get("c"), None);
+		assert_eq!(map.get_index(1), Some((&"b", &2)));
+		assert_eq!(map.size(), 2);
+
+		if let Some(value) = map.get_mut("b") {
+			*value += 10;
+		}
+		assert_eq!(
+			map.iter().collect::<Vec<_>>(),
+			vec![(&"a", &3), (&"b", &12)]
+		);
+	}
+
+	#[test]
+	fn test_ordered_map_borrowed_keys() {
+		let mut map = OrderedMap::new();
+		map.set(alloc::string::String::from("key"), 1);
+
+		assert!(map.contains_key("key"));
+		assert_eq!(map.get("key"), Some(&1));
+		assert_eq!(map.remove("key"), Some(1));
+		assert!(!map.contains_key("key"));
+	}
+
+	#[test]
+	fn test_ordered_map_remove_keeps_order() {
+		let mut map = OrderedMap::new();
+		for (i, key) in ["a", "b", "c", "d"].into_iter().enumerate() {
+			map.set(key, i);
+		}
+
+		assert_eq!(map.remove("b"), Some(1));
+		assert_eq!(map.remove("b"), None);
+		assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec!["a", "c", "d"]);
+		assert_eq!(map.values().copied().collect::<Vec<_>>(), vec![0, 2, 3]);
+
+		map.clear();
+		assert!(map.is_empty());
+	}
+
+	#[test]
+	fn test_ordered_map_iterators() {
+		let mut map = OrderedMap::new();
+		map.set(1, "one");
+		map.set(2, "two");
+
+		for value in map.values_mut() {
+			*value = "many";
+		}
+		for (key, value) in &mut map {
+			if *key == 1 {
+				*value = "one";
+			}
+		}
+
+		assert_eq!(map.iter().rev().len(), 2);
+		assert_eq!(
+			map.into_iter().collect::<Vec<_>>(),
+			vec![(1, "one"), (2, "many")]
+		);
+	}
+
+	#[test]
+	fn test_ordered_map_cursor() {
+		let mut map = OrderedMap::new();
+		map.set("a", 1);
+		map.set("b", 2);
+		map.set("c", 3);
+
+		let mut cursor = map.cursor_mut();
+		assert!(!cursor.insert_before("c", 0));
+		assert!(cursor.insert_before("z", 0));
+		assert_eq!(cursor.current(), Some((&"a", &1)));
+		assert!(cursor.insert_after("y", 0));
+		cursor.move_next();
+		assert_eq!(cursor.remove_current(), Some(("y", 0)));
+		*cursor.current_value_mut().unwrap() = 20;
+		cursor.move_next();
+		cursor.move_next();
+		cursor.move_next();
+		assert_eq!(cursor.index(), 4);
+		assert_eq!(cursor.remove_current(), None);
+		assert!(cursor.insert_after("d", 4));
+		assert_eq!(cursor.current(), Some((&"d", &4)));
+
+		let entries: Vec<_> = map.into_iter().collect();
+		assert_eq!(
+			entries,
+			vec![("z", 0), ("a", 1), ("b", 20), ("c", 3), ("d", 4)]
+		);
+	}
+
+	#[test]
+	fn test_ordered_map_entry() {
+		let mut map = OrderedMap::new();
+		map.set("a", 1);
+
+		assert_eq!(*map.entry("a").or_insert(10), 1);
+		assert_eq!(*map.entry("b").or_insert_with(|| 2), 2);
+		assert_eq!(*map.entry("c").or_default(), 0);
+		map.entry("a").and_modify(|v| *v += 1).or_insert(0);
+		map.entry("d")
+			.and_modify(|v| *v += 1)
+			.or_insert_with_key(|k| k.len());
+
+		match map.entry("b") {
+			Entry::Occupied(mut entry) => {
+				assert_eq!(entry.index(), 1);
+				assert_eq!(entry.insert(20), 2);
+				assert_eq!(entry.remove_entry(), ("b", 20));
+			}
+			Entry::Vacant(_) => unreachable!(),
+		}
+		match map.entry("e") {
+			Entry::Occupied(_) => unreachable!(),
+			Entry::Vacant(entry) => assert_eq!(entry.into_key(), "e"),
+		}
+
+		let entries: Vec<_> = map.into_iter().collect();
+		assert_eq!(entries, vec![("a", 2), ("c", 0), ("d", 1)]);
+	}
+
+	#[test]
+	fn test_ordered_map_conversions() {
+		let map = OrderedMap::from([("b", 1), ("a", 2), ("b", 3)]);
+		assert_eq!(Vec::from(map.clone()), vec![("b", 3), ("a", 2)]);
+
+		let tree = BTreeMap::from(map);
+		let map = OrderedMap::from(tree);
+		assert_eq!(Vec::from(map), vec![("a", 2), ("b", 3)]);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_ordered_map_hash_map_conversions() {
+		let map: OrderedMap<_, _> = [("a", 1), ("b", 2)].into_iter().collect();
+		let hash_map = std::collections::HashMap::<_, _>::from(map);
+		assert_eq!(hash_map.get("b"), Some(&2));
+
+		let map = OrderedMap::from(hash_map);
+		assert_eq!(map.size(), 2);
+		assert_eq!(map.get("a"), Some(&1));
+	}
+
+	#[test]
+	fn test_ordered_map_swap_remove_retain() {
+		let mut map = OrderedMap::from([("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+		assert_eq!(map.swap_remove("b"), Some(2));
+		assert_eq!(map.swap_remove("b"), None);
+		assert_eq!(map.swap_remove("d"), Some(4));
+		assert_eq!(Vec::from(map.clone()), vec![("a", 1), ("c", 3)]);
+
+		match map.entry("a") {
+			Entry::Occupied(entry) => assert_eq!(entry.swap_remove_entry(), ("a", 1)),
+			Entry::Vacant(_) => unreachable!(),
+		}
+		assert_eq!(Vec::from(map.clone()), vec![("c", 3)]);
+
+		let mut map = OrderedMap::from([("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+		map.retain(|key, value| {
+			*value *= 10;
+			*key != "c"
+		});
+		assert_eq!(Vec::from(map), vec![("a", 10), ("b", 20), ("d", 40)]);
+	}
+}
@@ -0,0 +1,361 @@
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::collections::List;
+
+/// A list of key-value pairs that may contain the same key more than once,
+/// like the header list of the [Fetch Standard][fetch-header-list].
+///
+/// Entries keep the order they were appended in, including entries that
+/// share a key. Keys are compared as-is, so case-insensitive keys need a
+/// key type that compares that way.
+///
+/// [fetch-header-list]: https://fetch.spec.whatwg.org/#concept-header-list
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::OrderedMultiMap;
+///
+/// let mut headers = OrderedMultiMap::new();
+/// headers.append("accept", "text/html");
+/// headers.append("cookie", "a=1");
+/// headers.append("accept", "*/*");
+///
+/// assert_eq!(headers.get("accept"), Some(&"text/html"));
+/// assert_eq!(headers.get_all("accept").collect::<Vec<_>>(), vec![&"text/html", &"*/*"]);
+/// assert_eq!(headers.combine("accept", ", "), Some(String::from("text/html, */*")));
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedMultiMap<K, V> {
+	entries: List<(K, V)>,
+}
+
+impl<K, V> OrderedMultiMap<K, V> {
+	/// Creates a new, empty multimap
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			entries: List::new(),
+		}
+	}
+
+	/// Creates a new, empty multimap with space for at least `capacity` entries
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			entries: List::with_capacity(capacity),
+		}
+	}
+
+	/// Adds an entry to the end of the multimap, even if its key is
+	/// already in the multimap.
+	///
+	/// See also: [Fetch Standard definition][fetch-dfn]
+	///
+	/// [fetch-dfn]: https://fetch.spec.whatwg.org/#concept-header-list-append
+	pub fn append(&mut self, key: K, value: V) {
+		self.entries.append((key, value));
+	}
+
+	/// Returns the number of entries in the multimap, counting each entry
+	/// with a repeated key
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.entries.size()
+	}
+
+	/// Checks if the multimap has a size of zero
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Removes all entries from the multimap
+	pub fn clear(&mut self) {
+		self.entries.empty();
+	}
+
+	/// Returns an iterator over the entries of the multimap, in order
+	pub fn iter(&self) -> slice::Iter<'_, (K, V)> {
+		self.entries.iter()
+	}
+
+	/// Converts the multimap into a list of its entries
+	#[must_use]
+	pub fn into_list(self) -> List<(K, V)> {
+		self.entries
+	}
+}
+
+impl<K: PartialEq, V> OrderedMultiMap<K, V> {
+	/// Returns the value of the first entry with the given key, if there is one
+	#[must_use]
+	pub fn get<Q>(&self, key: &Q) -> Option<&V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.entries
+			.iter()
+			.find(|(existing, _)| existing.borrow() == key)
+			.map(|(_, value)| value)
+	}
+
+	/// Returns an iterator over the values of every entry with the given
+	/// key, in order
+	pub fn get_all<'a, Q>(&'a self, key: &'a Q) -> GetAll<'a, K, V, Q>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		GetAll {
+			inner: self.entries.iter(),
+			key,
+		}
+	}
+
+	/// Checks if the multimap has at least one entry with the given key.
+	///
+	/// See also: [Fetch Standard definition][fetch-dfn]
+	///
+	/// [fetch-dfn]: https://fetch.spec.whatwg.org/#header-list-contains
+	#[must_use]
+	pub fn contains_key<Q>(&self, key: &Q) -> bool
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		self.get(key).is_some()
+	}
+
+	/// Sets the value of the first entry with the given key and removes
+	/// the other entries with that key, or adds a new entry to the end of
+	/// the multimap if there are none.
+	///
+	/// See also: [Fetch Standard definition][fetch-dfn]
+	///
+	/// [fetch-dfn]: https://fetch.spec.whatwg.org/#concept-header-list-set
+	pub fn set(&mut self, key: K, value: V) {
+		let index = match self
+			.entries
+			.iter()
+			.position(|(existing, _)| *existing == key)
+		{
+			Some(index) => index,
+			None => {
+				self.entries.append((key, value));
+				return;
+			}
+		};
+
+		self.entries[index].1 = value;
+		let mut i = 0;
+		self.entries.retain(|(existing, _)| {
+			let keep = i <= index || *existing != key;
+			i += 1;
+			keep
+		});
+	}
+
+	/// Removes every entry with the given key, and returns their values
+	/// in order.
+	///
+	/// See also: [Fetch Standard definition][fetch-dfn]
+	///
+	/// [fetch-dfn]: https://fetch.spec.whatwg.org/#concept-header-list-delete
+	pub fn remove_all<Q>(&mut self, key: &Q) -> List<V>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+	{
+		let mut values = List::new();
+		values.extend(
+			self.entries
+				.remove_if(|(existing, _)| existing.borrow() == key)
+				.into_iter()
+				.map(|(_, value)| value),
+		);
+		values
+	}
+
+	/// Returns the values of every entry with the given key joined by the
+	/// separator, in order, or `None` if there are no entries with that
+	/// key. Header lists combine values with `", "`.
+	///
+	/// See also: [Fetch Standard definition][fetch-dfn]
+	///
+	/// [fetch-dfn]: https://fetch.spec.whatwg.org/#concept-header-list-get
+	#[must_use]
+	pub fn combine<Q>(&self, key: &Q, separator: &str) -> Option<String>
+	where
+		K: Borrow<Q>,
+		Q: PartialEq + ?Sized,
+		V: AsRef<str>,
+	{
+		let mut values = self.get_all(key);
+		let mut combined = String::from(values.next()?.as_ref());
+		for value in values {
+			combined.push_str(separator);
+			combined.push_str(value.as_ref());
+		}
+		Some(combined)
+	}
+}
+
+impl<K, V> Default for OrderedMultiMap<K, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedMultiMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_map()
+			.entries(self.entries.iter().map(|(k, v)| (k, v)))
+			.finish()
+	}
+}
+
+impl<K, V> From<Vec<(K, V)>> for OrderedMultiMap<K, V> {
+	fn from(entries: Vec<(K, V)>) -> Self {
+		Self {
+			entries: entries.into(),
+		}
+	}
+}
+
+impl<K, V> FromIterator<(K, V)> for OrderedMultiMap<K, V> {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		Self {
+			entries: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<K, V> Extend<(K, V)> for OrderedMultiMap<K, V> {
+	/// Appends each entry of an iterator, in order
+	fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+		self.entries.extend(iter);
+	}
+}
+
+impl<K, V> IntoIterator for OrderedMultiMap<K, V> {
+	type Item = (K, V);
+	type IntoIter = vec::IntoIter<(K, V)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.entries.into_iter()
+	}
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedMultiMap<K, V> {
+	type Item = &'a (K, V);
+	type IntoIter = slice::Iter<'a, (K, V)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.entries.iter()
+	}
+}
+
+/// An iterator over the values of an [`OrderedMultiMap`] with a given
+/// key, created by [`OrderedMultiMap::get_all()`]
+#[derive(Debug)]
+pub struct GetAll<'a, K, V, Q: ?Sized> {
+	inner: slice::Iter<'a, (K, V)>,
+	key: &'a Q,
+}
+
+impl<'a, K, V, Q> Iterator for GetAll<'a, K, V, Q>
+where
+	K: Borrow<Q>,
+	Q: PartialEq + ?Sized,
+{
+	type Item = &'a V;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let key = self.key;
+		self.inner
+			.find(|(existing, _)| existing.borrow() == key)
+			.map(|(_, value)| value)
+	}
+}
+
+impl<'a, K, V, Q> DoubleEndedIterator for GetAll<'a, K, V, Q>
+where
+	K: Borrow<Q>,
+	Q: PartialEq + ?Sized,
+{
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let key = self.key;
+		self.inner
+			.rfind(|(existing, _)| existing.borrow() == key)
+			.map(|(_, value)| value)
+	}
+}
+
+impl<'a, K, V, Q> FusedIterator for GetAll<'a, K, V, Q>
+where
+	K: Borrow<Q>,
+	Q: PartialEq + ?Sized,
+{
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::vec::Vec;
+
+	fn headers() -> OrderedMultiMap<&'static str, &'static str> {
+		let mut headers = OrderedMultiMap::new();
+		headers.append("accept", "a");
+		headers.append("cookie", "b");
+		headers.append("accept", "c");
+		headers.append("host", "d");
+		headers.append("accept", "e");
+		headers
+	}
+
+	#[test]
+	fn test_ordered_multimap_get() {
+		let headers = headers();
+		assert_eq!(headers.size(), 5);
+		assert_eq!(headers.get("accept"), Some(&"a"));
+		assert_eq!(headers.get("missing"), None);
+		assert!(headers.contains_key("host"));
+		assert_eq!(
+			headers.get_all("accept").rev().collect::<Vec<_>>(),
+			alloc::vec![&"e", &"c", &"a"]
+		);
+		assert_eq!(
+			headers.combine("accept", ", "),
+			Some(String::from("a, c, e"))
+		);
+		assert_eq!(headers.combine("missing", ", "), None);
+	}
+
+	#[test]
+	fn test_ordered_multimap_set_remove_all() {
+		let mut headers = headers();
+		headers.set("accept", "f");
+		headers.set("range", "g");
+		assert_eq!(
+			headers.iter().copied().collect::<Vec<_>>(),
+			alloc::vec![
+				("accept", "f"),
+				("cookie", "b"),
+				("host", "d"),
+				("range", "g")
+			]
+		);
+
+		headers.append("cookie", "h");
+		assert_eq!(headers.remove_all("cookie").as_slice(), &["b", "h"]);
+		assert!(headers.remove_all("cookie").is_empty());
+		assert_eq!(headers.size(), 3);
+	}
+}
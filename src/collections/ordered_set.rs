@@ -0,0 +1,698 @@
+extern crate alloc;
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::ops::Index;
+use core::slice;
+
+/// A list with no duplicate items, as described by the
+/// [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Items keep the order they were added in. Appending or prepending an
+/// item that's already in the set does nothing.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ordered-set
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::OrderedSet;
+///
+/// let mut set = OrderedSet::new();
+/// set.append("b");
+/// set.append("c");
+/// set.append("b");
+/// set.prepend("a");
+///
+/// assert_eq!(set.size(), 3);
+/// assert_eq!(set.as_slice(), &["a", "b", "c"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedSet<T> {
+	items: Vec<T>,
+}
+
+impl<T> OrderedSet<T> {
+	/// Creates a new, empty ordered set
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { items: Vec::new() }
+	}
+
+	/// Creates a new, empty ordered set with space for at least `capacity` items
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			items: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Creates an ordered set from items that are known to be unique
+	pub(crate) fn from_unique_items(items: Vec<T>) -> Self {
+		Self { items }
+	}
+
+	/// Returns the number of items in the set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-size
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if the set has a size of zero.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-is-empty
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Removes all items from the set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-empty
+	pub fn empty(&mut self) {
+		self.items.clear();
+	}
+
+	/// Sorts the set in ascending order, using `less_than` to decide if
+	/// one item is less than another. Sorting is stable, so items that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-sort-in-ascending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two items are each less than the other, since the sort can
+	/// detect the inconsistency.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedSet;
+	///
+	/// let mut set = OrderedSet::new();
+	/// set.extend(["banana", "fig", "apple"]);
+	/// set.sort_ascending(|a, b| a.len() < b.len());
+	/// assert_eq!(set.as_slice(), &["fig", "apple", "banana"]);
+	/// ```
+	pub fn sort_ascending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut(&T, &T) -> bool,
+	{
+		self.items
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, a, b));
+	}
+
+	/// Sorts the set in descending order, using `less_than` to decide if
+	/// one item is less than another. Sorting is stable, so items that
+	/// aren't less than each other keep their relative order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-sort-in-descending-order
+	///
+	/// # Panics
+	/// May panic if `less_than` isn't a strict weak order, like one that
+	/// says two items are each less than the other, since the sort can
+	/// detect the inconsistency.
+	pub fn sort_descending<F>(&mut self, mut less_than: F)
+	where
+		F: FnMut(&T, &T) -> bool,
+	{
+		self.items
+			.sort_by(|a, b| super::less_than_ordering(&mut less_than, b, a));
+	}
+
+	/// Returns a cursor that starts at the first item of the set, and can
+	/// remove and insert items as it moves through the set
+	pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+		CursorMut {
+			set: self,
+			index: 0,
+		}
+	}
+
+	/// Returns a new set with the same items, in the same order.
+	///
+	/// This is the same as [`Clone::clone()`]: items are cloned with their
+	/// own `Clone` impls, so items that are shared pointers like `Rc` point
+	/// at the same values as the originals. See [`DeepClone`] for a clone
+	/// that copies those values too.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-clone
+	///
+	/// [`DeepClone`]: crate::collections::DeepClone
+	#[must_use]
+	pub fn clone_shallow(&self) -> Self
+	where
+		Self: Clone,
+	{
+		self.clone()
+	}
+
+	/// Returns a reference to the item at the given index, if it exists
+	#[must_use]
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.items.get(index)
+	}
+
+	/// Returns an iterator over the items of the set, in order.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-iterate
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.items.iter()
+	}
+
+	/// Returns the items of the set as a slice
+	#[must_use]
+	pub fn as_slice(&self) -> &[T] {
+		&self.items
+	}
+
+	/// Converts the set into a vector of its items
+	#[must_use]
+	pub fn into_vec(self) -> Vec<T> {
+		self.items
+	}
+}
+
+impl<T: PartialEq> OrderedSet<T> {
+	/// Adds an item to the end of the set, unless the set already contains
+	/// it. Returns `true` if the item was added.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-append
+	pub fn append(&mut self, item: T) -> bool {
+		if self.contains(&item) {
+			return false;
+		}
+		self.items.push(item);
+		true
+	}
+
+	/// Appends each item of another set or iterator, in order, skipping
+	/// items that are already in the set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-append
+	pub fn extend<I>(&mut self, items: I)
+	where
+		I: IntoIterator<Item = T>,
+	{
+		for item in items {
+			self.append(item);
+		}
+	}
+
+	/// Adds an item to the beginning of the set, unless the set already
+	/// contains it. Returns `true` if the item was added.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-prepend
+	pub fn prepend(&mut self, item: T) -> bool {
+		self.insert(0, item)
+	}
+
+	/// Inserts an item before the given index, unless the set already
+	/// contains it. Returns `true` if the item was added.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-insert
+	///
+	/// # Panics
+	/// Panics if `index` is greater than the size of the set.
+	pub fn insert(&mut self, index: usize, item: T) -> bool {
+		assert!(index <= self.items.len(), "index out of bounds");
+		if self.contains(&item) {
+			return false;
+		}
+		self.items.insert(index, item);
+		true
+	}
+
+	/// Inserts an item before the given index like [`OrderedSet::insert()`],
+	/// but returns the item back instead of panicking if `index` is greater
+	/// than the size of the set.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedSet;
+	///
+	/// let mut set = OrderedSet::from(["a", "c"]);
+	/// assert_eq!(set.try_insert(1, "b"), Ok(true));
+	/// assert_eq!(set.try_insert(0, "c"), Ok(false));
+	/// assert_eq!(set.try_insert(4, "d"), Err("d"));
+	/// ```
+	pub fn try_insert(&mut self, index: usize, item: T) -> Result<bool, T> {
+		if index > self.items.len() {
+			return Err(item);
+		}
+		Ok(self.insert(index, item))
+	}
+
+	/// Replaces the first occurrence of either `item` or `replacement` with
+	/// `replacement`, and removes the other one. Does nothing if the set
+	/// contains neither.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-replace
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedSet;
+	///
+	/// let mut set = OrderedSet::new();
+	/// set.extend(["a", "b", "c"]);
+	///
+	/// set.replace(&"c", "a");
+	/// assert_eq!(set.as_slice(), &["a", "b"]);
+	///
+	/// set.replace(&"b", "z");
+	/// assert_eq!(set.as_slice(), &["a", "z"]);
+	/// ```
+	pub fn replace(&mut self, item: &T, replacement: T) {
+		let first = self
+			.items
+			.iter()
+			.position(|existing| existing == item || *existing == replacement);
+
+		if let Some(index) = first {
+			let mut i = 0;
+			self.items.retain(|existing| {
+				let keep = i <= index || (existing != item && *existing != replacement);
+				i += 1;
+				keep
+			});
+			self.items[index] = replacement;
+		}
+	}
+
+	/// Removes the item from the set, if it's present. Returns `true` if
+	/// the item was removed.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-remove
+	pub fn remove(&mut self, item: &T) -> bool {
+		match self.items.iter().position(|existing| existing == item) {
+			Some(index) => {
+				self.items.remove(index);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Checks if the set contains the given item.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-contain
+	#[must_use]
+	pub fn contains(&self, item: &T) -> bool {
+		self.items.contains(item)
+	}
+
+	/// Checks if every item of this set is also in `other`.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-subset
+	#[must_use]
+	pub fn is_subset(&self, other: &Self) -> bool {
+		self.items.iter().all(|item| other.contains(item))
+	}
+
+	/// Checks if every item of `other` is also in this set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-superset
+	#[must_use]
+	pub fn is_superset(&self, other: &Self) -> bool {
+		other.is_subset(self)
+	}
+
+	/// Checks if both sets contain the same items, regardless of their order.
+	///
+	/// Unlike `==`, which also compares the order of the items, this
+	/// follows the WHATWG Infra Standard's definition of set equality.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-equal
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedSet;
+	///
+	/// let mut a = OrderedSet::new();
+	/// a.extend([1, 2]);
+	/// let mut b = OrderedSet::new();
+	/// b.extend([2, 1]);
+	///
+	/// assert!(a.is_equal(&b));
+	/// assert_ne!(a, b);
+	/// ```
+	#[must_use]
+	pub fn is_equal(&self, other: &Self) -> bool {
+		self.size() == other.size() && self.is_subset(other)
+	}
+
+	/// Returns a new set with the items of this set, followed by the items
+	/// of `other` that aren't in this set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-union
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::OrderedSet;
+	///
+	/// let mut a = OrderedSet::new();
+	/// a.extend(["self", "https:"]);
+	/// let mut b = OrderedSet::new();
+	/// b.extend(["data:", "self"]);
+	///
+	/// assert_eq!(a.union(&b).as_slice(), &["self", "https:", "data:"]);
+	/// assert_eq!(a.intersection(&b).as_slice(), &["self"]);
+	/// assert_eq!(a.difference(&b).as_slice(), &["https:"]);
+	/// ```
+	#[must_use]
+	pub fn union(&self, other: &Self) -> Self
+	where
+		T: Clone,
+	{
+		let mut union = self.clone();
+		union.extend(other.items.iter().cloned());
+		union
+	}
+
+	/// Returns a new set with the items of this set that are also in
+	/// `other`, in the order of this set.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#set-intersection
+	#[must_use]
+	pub fn intersection(&self, other: &Self) -> Self
+	where
+		T: Clone,
+	{
+		self.filtered(|item| other.contains(item))
+	}
+
+	/// Returns a new set with the items of this set that aren't in `other`,
+	/// in the order of this set
+	#[must_use]
+	pub fn difference(&self, other: &Self) -> Self
+	where
+		T: Clone,
+	{
+		self.filtered(|item| !other.contains(item))
+	}
+
+	fn filtered<F>(&self, mut condition: F) -> Self
+	where
+		T: Clone,
+		F: FnMut(&T) -> bool,
+	{
+		Self {
+			items: self
+				.items
+				.iter()
+				.filter(|item| condition(item))
+				.cloned()
+				.collect(),
+		}
+	}
+}
+
+impl<T> Default for OrderedSet<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for OrderedSet<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_set().entries(self.items.iter()).finish()
+	}
+}
+
+impl<T> Index<usize> for OrderedSet<T> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		&self.items[index]
+	}
+}
+
+impl<T: PartialEq> From<Vec<T>> for OrderedSet<T> {
+	/// Creates an ordered set from the items of a vector, keeping only the
+	/// first of any equal items
+	fn from(items: Vec<T>) -> Self {
+		items.into_iter().collect()
+	}
+}
+
+impl<T: PartialEq, const N: usize> From<[T; N]> for OrderedSet<T> {
+	fn from(items: [T; N]) -> Self {
+		items.into_iter().collect()
+	}
+}
+
+impl<T> From<OrderedSet<T>> for Vec<T> {
+	fn from(value: OrderedSet<T>) -> Self {
+		value.into_vec()
+	}
+}
+
+impl<T: PartialEq> FromIterator<T> for OrderedSet<T> {
+	/// Creates an ordered set from the items of an iterator, keeping only
+	/// the first of any equal items
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let mut set = Self::new();
+		OrderedSet::extend(&mut set, iter);
+		set
+	}
+}
+
+impl<T: PartialEq> Extend<T> for OrderedSet<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		OrderedSet::extend(self, iter);
+	}
+}
+
+impl<T> IntoIterator for OrderedSet<T> {
+	type Item = T;
+	type IntoIter = vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a OrderedSet<T> {
+	type Item = &'a T;
+	type IntoIter = slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+/// A cursor over an [`OrderedSet`] that can remove and insert items while
+/// traversing it, created by [`OrderedSet::cursor_mut()`].
+///
+/// The cursor points at one item at a time, starting at the first one.
+/// Removing the current item moves the cursor on to the item after it.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::OrderedSet;
+///
+/// let mut set = OrderedSet::new();
+/// set.extend(["a", "b", "c"]);
+///
+/// let mut cursor = set.cursor_mut();
+/// while let Some(&item) = cursor.current() {
+///     if item == "b" {
+///         cursor.remove_current();
+///         assert!(!cursor.insert_before("a"));
+///     } else {
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(set.as_slice(), &["a", "c"]);
+/// ```
+#[derive(Debug)]
+pub struct CursorMut<'a, T> {
+	set: &'a mut OrderedSet<T>,
+	index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+	/// Returns the index of the current item, which is the size of the
+	/// set if the cursor is past the end
+	#[must_use]
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the current item, or `None` if the cursor is past the end
+	#[must_use]
+	pub fn current(&self) -> Option<&T> {
+		self.set.items.get(self.index)
+	}
+
+	/// Moves the cursor to the next item, or does nothing if the cursor is
+	/// past the end
+	pub fn move_next(&mut self) {
+		if self.index < self.set.items.len() {
+			self.index += 1;
+		}
+	}
+
+	/// Removes the current item and returns it, moving the cursor to the
+	/// item after it. Returns `None` if the cursor is past the end.
+	pub fn remove_current(&mut self) -> Option<T> {
+		if self.index < self.set.items.len() {
+			Some(self.set.items.remove(self.index))
+		} else {
+			None
+		}
+	}
+}
+
+impl<'a, T: PartialEq> CursorMut<'a, T> {
+	/// Inserts an item before the current one, unless the set already
+	/// contains it. The cursor keeps pointing at the same item, so the
+	/// inserted item isn't visited. Returns `true` if the item was added.
+	pub fn insert_before(&mut self, item: T) -> bool {
+		if self.set.contains(&item) {
+			return false;
+		}
+		self.set.items.insert(self.index, item);
+		self.index += 1;
+		true
+	}
+
+	/// Inserts an item after the current one, unless the set already
+	/// contains it, so that it's visited next. If the cursor is past the
+	/// end, the item is appended and becomes the current item. Returns
+	/// `true` if the item was added.
+	pub fn insert_after(&mut self, item: T) -> bool {
+		if self.set.contains(&item) {
+			return false;
+		}
+		let index = (self.index + 1).min(self.set.items.len());
+		self.set.items.insert(index, item);
+		true
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn set_of<const N: usize>(items: [i32; N]) -> OrderedSet<i32> {
+		let mut set = OrderedSet::new();
+		set.extend(items);
+		set
+	}
+
+	#[test]
+	fn test_ordered_set_append_prepend() {
+		let mut set = OrderedSet::new();
+		assert!(set.append(2));
+		assert!(!set.append(2));
+		assert!(set.prepend(1));
+		assert!(!set.prepend(2));
+		assert!(set.insert(2, 3));
+		assert!(!set.insert(0, 3));
+
+		assert_eq!(set.as_slice(), &[1, 2, 3]);
+		assert_eq!(set_of([3, 1, 3, 2, 1]).as_slice(), &[3, 1, 2]);
+	}
+
+	#[test]
+	fn test_ordered_set_replace() {
+		let mut set = set_of([1, 2, 3, 4]);
+		set.replace(&3, 1);
+		assert_eq!(set.as_slice(), &[1, 2, 4]);
+
+		set.replace(&4, 0);
+		assert_eq!(set.as_slice(), &[1, 2, 0]);
+
+		set.replace(&2, 0);
+		assert_eq!(set.as_slice(), &[1, 0]);
+
+		set.replace(&9, 8);
+		assert_eq!(set.as_slice(), &[1, 0]);
+	}
+
+	#[test]
+	fn test_ordered_set_remove_contains() {
+		let mut set = set_of([1, 2, 3]);
+		assert!(set.remove(&2));
+		assert!(!set.remove(&2));
+		assert!(!set.contains(&2));
+		assert!(set.contains(&3));
+		assert_eq!(set.size(), 2);
+
+		set.empty();
+		assert!(set.is_empty());
+	}
+
+	#[test]
+	fn test_ordered_set_subset_superset_equal() {
+		let a = set_of([1, 2, 3]);
+		let b = set_of([3, 1]);
+
+		assert!(b.is_subset(&a));
+		assert!(!a.is_subset(&b));
+		assert!(a.is_superset(&b));
+		assert!(OrderedSet::new().is_subset(&b));
+		assert!(a.is_equal(&set_of([2, 3, 1])));
+		assert!(!a.is_equal(&b));
+	}
+
+	#[test]
+	fn test_ordered_set_union_intersection_difference() {
+		let a = set_of([3, 1, 2]);
+		let b = set_of([4, 2, 3]);
+
+		assert_eq!(a.union(&b).as_slice(), &[3, 1, 2, 4]);
+		assert_eq!(b.union(&a).as_slice(), &[4, 2, 3, 1]);
+		assert_eq!(a.intersection(&b).as_slice(), &[3, 2]);
+		assert_eq!(b.intersection(&a).as_slice(), &[2, 3]);
+		assert_eq!(a.difference(&b).as_slice(), &[1]);
+		assert!(a.difference(&a).is_empty());
+		assert!(a.intersection(&OrderedSet::new()).is_empty());
+	}
+
+	#[test]
+	fn test_ordered_set_conversions() {
+		let set: OrderedSet<i32> = [3, 1, 3, 2, 1].into_iter().collect();
+		assert_eq!(set.as_slice(), &[3, 1, 2]);
+		assert_eq!(set, OrderedSet::from([3, 1, 2, 1]));
+		assert_eq!(Vec::from(set), alloc::vec![3, 1, 2]);
+	}
+}
@@ -0,0 +1,232 @@
+extern crate alloc;
+use alloc::collections::vec_deque::{self, VecDeque};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A list that items are added to at one end and removed from at the
+/// other, as described by the [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Items are enqueued at the back of the queue and dequeued from the
+/// front. Iterating goes from the front of the queue to the back.
+///
+/// Items are stored in a ring buffer, so both enqueuing and dequeuing
+/// take constant time.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#queue
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::Queue;
+///
+/// let mut tasks = Queue::new();
+/// tasks.enqueue("fetch");
+/// tasks.enqueue("parse");
+///
+/// assert_eq!(tasks.dequeue(), Some("fetch"));
+/// assert_eq!(tasks.dequeue(), Some("parse"));
+/// assert_eq!(tasks.dequeue(), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Queue<T> {
+	items: VecDeque<T>,
+}
+
+impl<T> Queue<T> {
+	/// Creates a new, empty queue
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			items: VecDeque::new(),
+		}
+	}
+
+	/// Creates a new, empty queue with space for at least `capacity` items
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			items: VecDeque::with_capacity(capacity),
+		}
+	}
+
+	/// Adds an item to the back of the queue.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#queue-enqueue
+	pub fn enqueue(&mut self, item: T) {
+		self.items.push_back(item);
+	}
+
+	/// Removes the item at the front of the queue and returns it, or
+	/// returns `None` if the queue is empty.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#queue-dequeue
+	pub fn dequeue(&mut self) -> Option<T> {
+		self.items.pop_front()
+	}
+
+	/// Returns the item at the front of the queue without removing it, or
+	/// returns `None` if the queue is empty
+	#[must_use]
+	pub fn front(&self) -> Option<&T> {
+		self.items.front()
+	}
+
+	/// Removes all items from the queue.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-empty
+	pub fn empty(&mut self) {
+		self.items.clear();
+	}
+
+	/// Checks if the queue contains an item that's equal to the given item.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-contain
+	#[must_use]
+	pub fn contains(&self, item: &T) -> bool
+	where
+		T: PartialEq,
+	{
+		self.items.contains(item)
+	}
+
+	/// Returns the number of items in the queue.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-size
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if the queue has a size of zero.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-is-empty
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Returns an iterator over the items of the queue, from the front to
+	/// the back.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-iterate
+	pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+		self.items.iter()
+	}
+
+	/// Converts the queue into a vector of its items, from the front to the back
+	#[must_use]
+	pub fn into_vec(self) -> Vec<T> {
+		self.items.into()
+	}
+}
+
+impl<T> Default for Queue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.items.iter()).finish()
+	}
+}
+
+impl<T> From<Vec<T>> for Queue<T> {
+	fn from(items: Vec<T>) -> Self {
+		Self {
+			items: items.into(),
+		}
+	}
+}
+
+impl<T, const N: usize> From<[T; N]> for Queue<T> {
+	fn from(items: [T; N]) -> Self {
+		Self::from(Vec::from(items))
+	}
+}
+
+impl<T> From<Queue<T>> for Vec<T> {
+	fn from(value: Queue<T>) -> Self {
+		value.into_vec()
+	}
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		Self {
+			items: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<T> Extend<T> for Queue<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		self.items.extend(iter);
+	}
+}
+
+impl<T> IntoIterator for Queue<T> {
+	type Item = T;
+	type IntoIter = vec_deque::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+	type Item = &'a T;
+	type IntoIter = vec_deque::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_queue_enqueue_dequeue() {
+		let mut queue = Queue::new();
+		assert_eq!(queue.dequeue(), None);
+
+		queue.enqueue(1);
+		queue.enqueue(2);
+		assert_eq!(queue.front(), Some(&1));
+		assert_eq!(queue.size(), 2);
+
+		assert_eq!(queue.dequeue(), Some(1));
+		queue.enqueue(3);
+		assert_eq!(queue.dequeue(), Some(2));
+		assert_eq!(queue.dequeue(), Some(3));
+		assert!(queue.is_empty());
+	}
+
+	#[test]
+	fn test_queue_list_operations() {
+		let mut queue = Queue::new();
+		queue.enqueue("a");
+		queue.enqueue("b");
+		assert!(queue.contains(&"b"));
+		assert_eq!(queue.clone().into_vec(), alloc::vec!["a", "b"]);
+
+		queue.empty();
+		assert_eq!(queue, Queue::default());
+	}
+}
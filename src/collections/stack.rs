@@ -0,0 +1,247 @@
+extern crate alloc;
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::slice;
+
+/// A list that's operated on from one end, as described by the
+/// [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Items are pushed onto and popped from the top of the stack, which is
+/// the end of the underlying list. Iterating goes from the bottom of the
+/// stack to the top.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#stack
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::Stack;
+///
+/// let mut open_elements = Stack::new();
+/// open_elements.push("html");
+/// open_elements.push("body");
+/// open_elements.push("p");
+///
+/// assert_eq!(open_elements.peek(), Some(&"p"));
+/// assert_eq!(open_elements.pop(), Some("p"));
+/// assert_eq!(open_elements.size(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stack<T> {
+	items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+	/// Creates a new, empty stack
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { items: Vec::new() }
+	}
+
+	/// Creates a new, empty stack with space for at least `capacity` items
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			items: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Adds an item to the top of the stack.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#stack-push
+	pub fn push(&mut self, item: T) {
+		self.items.push(item);
+	}
+
+	/// Removes the item at the top of the stack and returns it, or returns
+	/// `None` if the stack is empty.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#stack-pop
+	pub fn pop(&mut self) -> Option<T> {
+		self.items.pop()
+	}
+
+	/// Returns the item at the top of the stack without removing it, or
+	/// returns `None` if the stack is empty.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#stack-peek
+	#[must_use]
+	pub fn peek(&self) -> Option<&T> {
+		self.items.last()
+	}
+
+	/// Returns a mutable reference to the item at the top of the stack, or
+	/// returns `None` if the stack is empty
+	#[must_use]
+	pub fn peek_mut(&mut self) -> Option<&mut T> {
+		self.items.last_mut()
+	}
+
+	/// Removes all items from the stack.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-empty
+	pub fn empty(&mut self) {
+		self.items.clear();
+	}
+
+	/// Checks if the stack contains an item that's equal to the given item.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-contain
+	#[must_use]
+	pub fn contains(&self, item: &T) -> bool
+	where
+		T: PartialEq,
+	{
+		self.items.contains(item)
+	}
+
+	/// Returns the number of items in the stack.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-size
+	#[must_use]
+	pub fn size(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if the stack has a size of zero.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-is-empty
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Returns an iterator over the items of the stack, from the bottom to
+	/// the top.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#list-iterate
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.items.iter()
+	}
+
+	/// Returns the items of the stack as a slice, from the bottom to the top
+	#[must_use]
+	pub fn as_slice(&self) -> &[T] {
+		&self.items
+	}
+
+	/// Converts the stack into a vector of its items, from the bottom to the top
+	#[must_use]
+	pub fn into_vec(self) -> Vec<T> {
+		self.items
+	}
+}
+
+impl<T> Default for Stack<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.items.iter()).finish()
+	}
+}
+
+impl<T> From<Vec<T>> for Stack<T> {
+	fn from(items: Vec<T>) -> Self {
+		Self { items }
+	}
+}
+
+impl<T, const N: usize> From<[T; N]> for Stack<T> {
+	fn from(items: [T; N]) -> Self {
+		Self::from(Vec::from(items))
+	}
+}
+
+impl<T> From<Stack<T>> for Vec<T> {
+	fn from(value: Stack<T>) -> Self {
+		value.into_vec()
+	}
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		Self {
+			items: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<T> Extend<T> for Stack<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		self.items.extend(iter);
+	}
+}
+
+impl<T> IntoIterator for Stack<T> {
+	type Item = T;
+	type IntoIter = vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+	type Item = &'a T;
+	type IntoIter = slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_stack_push_pop_peek() {
+		let mut stack = Stack::new();
+		assert_eq!(stack.peek(), None);
+		assert_eq!(stack.pop(), None);
+
+		stack.push(1);
+		stack.push(2);
+		assert_eq!(stack.peek(), Some(&2));
+		assert_eq!(stack.size(), 2);
+
+		*stack.peek_mut().unwrap() = 3;
+		assert_eq!(stack.pop(), Some(3));
+		assert_eq!(stack.pop(), Some(1));
+		assert!(stack.is_empty());
+	}
+
+	#[test]
+	fn test_stack_list_operations() {
+		let mut stack = Stack::new();
+		stack.push("a");
+		stack.push("b");
+		assert!(stack.contains(&"a"));
+		assert_eq!(
+			stack.iter().copied().collect::<Vec<_>>(),
+			alloc::vec!["a", "b"]
+		);
+
+		stack.empty();
+		assert_eq!(stack, Stack::default());
+	}
+}
@@ -0,0 +1,1139 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::codepoints::surrogates::combine_surrogate_pair;
+use crate::error::{Found, InfraParseError};
+#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+use crate::instrument::{emit, TraceEvent};
+use crate::scan::{find_either_byte, find_non_ascii_whitespace};
+
+/// A cursor over a string, which keeps the position variable of the
+/// WHATWG Infra Standard's [string parsing][whatwg-infra-dfn] algorithms
+/// together with the string it points into.
+///
+/// The position is a byte offset that's always on a codepoint boundary, so
+/// unlike passing a `&mut usize` to
+/// [`collect_codepoints()`](crate::collect_codepoints), the cursor can't be
+/// moved into the middle of a codepoint. Advancing the cursor with
+/// [`Iterator::next()`] returns the codepoint at the position, and moves
+/// the position past it.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strings
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::CodePointCursor;
+///
+/// let mut cursor = CodePointCursor::new("max-age=60");
+/// let name = cursor.collect_while(|c| c != '=');
+/// assert_eq!(name, "max-age");
+/// assert_eq!(cursor.next(), Some('='));
+/// assert_eq!(cursor.peek(), Some('6'));
+/// assert_eq!(cursor.remaining(), "60");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodePointCursor<'a> {
+	input: &'a str,
+	position: usize,
+	index: usize,
+}
+
+impl<'a> CodePointCursor<'a> {
+	/// Creates a cursor at the start of a string
+	#[must_use]
+	pub const fn new(input: &'a str) -> Self {
+		Self {
+			input,
+			position: 0,
+			index: 0,
+		}
+	}
+
+	/// Returns the whole string that the cursor is over
+	#[must_use]
+	pub const fn input(&self) -> &'a str {
+		self.input
+	}
+
+	/// Returns the position of the cursor, as a byte offset into the string.
+	///
+	/// Byte offsets can slice the string without scanning it, unlike
+	/// [`CodePointCursor::code_point_index()`].
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let input = "\u{E9}t\u{E9}!";
+	/// let mut cursor = CodePointCursor::new(input);
+	/// cursor.advance_by(3);
+	/// assert_eq!(cursor.byte_offset(), 5);
+	/// assert_eq!(cursor.code_point_index(), 3);
+	/// assert_eq!(&input[cursor.byte_offset()..], "!");
+	/// ```
+	#[must_use]
+	pub const fn byte_offset(&self) -> usize {
+		self.position
+	}
+
+	/// Returns the position of the cursor, as the number of codepoints
+	/// before it, which is how the WHATWG standards count positions. The
+	/// cursor keeps track of it as it advances, so this doesn't scan the
+	/// string.
+	#[must_use]
+	pub const fn code_point_index(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the line and column of the position, for reporting where in
+	/// the string a parse error happened.
+	///
+	/// Newlines are counted like [`normalize_newlines()`] would leave them:
+	/// a CR, an LF, or a CR followed by an LF each end a line. The line and
+	/// column are computed from the start of the string when this is called,
+	/// so cursors that never report positions don't pay for tracking them.
+	///
+	/// [`normalize_newlines()`]: crate::normalize_newlines
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{CodePointCursor, LineColumn};
+	///
+	/// let mut cursor = CodePointCursor::new("a\r\nb\rc\u{E9}d");
+	/// cursor.advance_by(6);
+	/// assert_eq!(cursor.line_column(), LineColumn { line: 3, column: 2 });
+	/// assert_eq!(cursor.line_column().to_string(), "3:2");
+	/// ```
+	#[must_use]
+	pub fn line_column(&self) -> LineColumn {
+		let before = &self.input[..self.position];
+		let bytes = before.as_bytes();
+		let mut line = 1;
+		let mut line_start = 0;
+
+		while let Some(i) = find_either_byte(&bytes[line_start..], b'\n', b'\r') {
+			let newline = line_start + i;
+			// The LF of a CRLF pair doesn't start another line
+			if !(bytes[newline] == b'\n' && newline > 0 && bytes[newline - 1] == b'\r') {
+				line += 1;
+			}
+			line_start = newline + 1;
+		}
+
+		LineColumn {
+			line,
+			column: before[line_start..].chars().count() + 1,
+		}
+	}
+
+	/// Returns the rest of the string, from the position to the end
+	#[must_use]
+	pub fn remaining(&self) -> &'a str {
+		&self.input[self.position..]
+	}
+
+	/// Checks if the position is past the end of the string, so there are
+	/// no codepoints left
+	#[must_use]
+	pub fn is_past_end(&self) -> bool {
+		self.position >= self.input.len()
+	}
+
+	/// Returns the codepoint at the position without advancing, or `None`
+	/// if the position is past the end of the string
+	#[must_use]
+	pub fn peek(&self) -> Option<char> {
+		self.remaining().chars().next()
+	}
+
+	/// Saves the position of the cursor, to go back to it later with
+	/// [`CodePointCursor::restore()`] if parsing ahead doesn't work out.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("mailto:a@b");
+	/// let start = cursor.checkpoint();
+	/// let scheme = cursor.collect_while(|c| c.is_ascii_alphanumeric());
+	/// if cursor.peek() != Some(':') {
+	///     cursor.restore(start);
+	/// }
+	/// assert_eq!(scheme, "mailto");
+	/// assert_eq!(cursor.next(), Some(':'));
+	/// ```
+	#[must_use]
+	pub fn checkpoint(&self) -> Checkpoint {
+		Checkpoint {
+			position: self.position,
+			index: self.index,
+		}
+	}
+
+	/// Moves the cursor back (or forward) to a saved position.
+	///
+	/// # Panics
+	/// Panics if the checkpoint's position isn't on a codepoint boundary of
+	/// the cursor's string, which can only happen if the checkpoint came
+	/// from a cursor over a different string.
+	pub fn restore(&mut self, checkpoint: Checkpoint) {
+		assert!(
+			self.input.is_char_boundary(checkpoint.position),
+			"checkpoint is not from a cursor over this string"
+		);
+		self.position = checkpoint.position;
+		self.index = checkpoint.index;
+	}
+
+	/// Collects the codepoints from the position that match a predicate,
+	/// and advances the position past them.
+	/// [`CodePointCursor::collect_while()`] returns the same codepoints
+	/// without copying them.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#collect-a-sequence-of-code-points
+	#[cfg(feature = "alloc")]
+	pub fn collect_codepoints<P>(&mut self, predicate: P) -> String
+	where
+		P: FnMut(char) -> bool,
+	{
+		String::from(self.collect_while(predicate))
+	}
+
+	/// Collects the codepoints from the position that match a predicate
+	/// like [`CodePointCursor::collect_codepoints()`], but returns them as a
+	/// slice of the string instead of allocating a new one.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let input = "width=100";
+	/// let mut cursor = CodePointCursor::new(input);
+	/// let name: &'static str = cursor.collect_while(|c| c != '=');
+	/// assert_eq!(name, "width");
+	/// assert!(std::ptr::eq(name.as_ptr(), input.as_ptr()));
+	/// ```
+	pub fn collect_while<P>(&mut self, predicate: P) -> &'a str
+	where
+		P: FnMut(char) -> bool,
+	{
+		self.consume_while_max(predicate, usize::MAX)
+	}
+
+	/// Advances the position by up to `n` codepoints, stopping at the end of
+	/// the string, and returns the number of codepoints it advanced by.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("//\u{E9}");
+	/// assert_eq!(cursor.advance_by(2), 2);
+	/// assert_eq!(cursor.remaining(), "\u{E9}");
+	/// assert_eq!(cursor.advance_by(2), 1);
+	/// assert!(cursor.is_past_end());
+	/// ```
+	pub fn advance_by(&mut self, n: usize) -> usize {
+		let start = self.index;
+		self.take_codepoints(n);
+		self.index - start
+	}
+
+	/// Returns the next `n` codepoints from the position, or fewer if the
+	/// string ends first, and advances the position past them.
+	///
+	/// Unlike [`Iterator::take()`], this takes the cursor by reference and
+	/// returns a slice of the string.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("\u{1F431}abc");
+	/// assert_eq!(cursor.take_codepoints(2), "\u{1F431}a");
+	/// assert_eq!(cursor.take_codepoints(5), "bc");
+	/// assert_eq!(cursor.take_codepoints(1), "");
+	/// ```
+	pub fn take_codepoints(&mut self, n: usize) -> &'a str {
+		self.consume_while_max(|_| true, n)
+	}
+
+	/// Collects up to `max` codepoints from the position that match a
+	/// predicate, and advances the position past them. This bounds how much
+	/// of the input a single step can consume.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("12345x");
+	/// assert_eq!(cursor.consume_while_max(|c| c.is_ascii_digit(), 3), "123");
+	/// assert_eq!(cursor.consume_while_max(|c| c.is_ascii_digit(), 3), "45");
+	/// assert_eq!(cursor.peek(), Some('x'));
+	/// ```
+	pub fn consume_while_max<P>(&mut self, mut predicate: P, max: usize) -> &'a str
+	where
+		P: FnMut(char) -> bool,
+	{
+		let rest = self.remaining();
+		let mut len = rest.len();
+		let mut count = 0;
+		for (i, c) in rest.char_indices() {
+			if count == max || !predicate(c) {
+				len = i;
+				break;
+			}
+			count += 1;
+		}
+		self.position += len;
+		self.index += count;
+		&rest[..len]
+	}
+
+	/// Advances the position past a string if the codepoints at the
+	/// position are that string, and returns whether they were.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("//host");
+	/// assert!(!cursor.expect_str("///"));
+	/// assert_eq!(cursor.byte_offset(), 0);
+	/// assert!(cursor.expect_str("//"));
+	/// assert_eq!(cursor.remaining(), "host");
+	/// ```
+	pub fn expect_str(&mut self, expected: &str) -> bool {
+		if !self.remaining().starts_with(expected) {
+			return false;
+		}
+		self.advance_past(expected);
+		true
+	}
+
+	/// Advances the position past a string if the codepoints at the
+	/// position are an [ASCII case-insensitive][whatwg-infra-dfn] match for
+	/// that string, and returns whether they were.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-case-insensitive
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("Charset=utf-8");
+	/// assert!(cursor.expect_str_ignore_ascii_case("charset"));
+	/// assert_eq!(cursor.remaining(), "=utf-8");
+	/// ```
+	pub fn expect_str_ignore_ascii_case(&mut self, expected: &str) -> bool {
+		let rest = self.remaining().as_bytes();
+		if rest.len() < expected.len()
+			|| !rest[..expected.len()].eq_ignore_ascii_case(expected.as_bytes())
+		{
+			return false;
+		}
+		// Only ASCII bytes compare equal to bytes with a different value, so
+		// the matched bytes end at the same char boundary that `expected` does
+		self.advance_past(expected);
+		true
+	}
+
+	/// Advances the position past the codepoint at the position if it
+	/// matches a predicate, and returns it.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodePointCursor;
+	///
+	/// let mut cursor = CodePointCursor::new("+1");
+	/// assert_eq!(cursor.consume_char_if(|c| c == '-'), None);
+	/// assert_eq!(cursor.consume_char_if(|c| c == '+' || c == '-'), Some('+'));
+	/// assert_eq!(cursor.peek(), Some('1'));
+	/// ```
+	pub fn consume_char_if<P>(&mut self, predicate: P) -> Option<char>
+	where
+		P: FnOnce(char) -> bool,
+	{
+		let c = self.peek().filter(|&c| predicate(c))?;
+		self.position += c.len_utf8();
+		self.index += 1;
+		Some(c)
+	}
+
+	/// Advances the position past a string like
+	/// [`CodePointCursor::expect_str()`], but returns an error at the
+	/// position if the codepoints there aren't that string.
+	///
+	/// # Errors
+	/// Returns an [`InfraParseError`] that found the codepoint at the
+	/// position, or the end of the input.
+	pub fn require_str(&mut self, expected: &str) -> Result<(), InfraParseError> {
+		if self.expect_str(expected) {
+			Ok(())
+		} else {
+			Err(self.error("match a string", "the given string"))
+		}
+	}
+
+	/// Creates an error for an operation that expected something at the
+	/// position, but found the codepoint there (or the end of the input),
+	/// for parsers built on the cursor.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{CodePointCursor, Found};
+	///
+	/// let mut cursor = CodePointCursor::new("1.5");
+	/// cursor.collect_while(|c| c.is_ascii_digit());
+	/// let error = cursor.error("parse an integer", "an ASCII digit");
+	/// assert_eq!(error.offset(), 1);
+	/// assert_eq!(error.found(), Found::CodePoint('.'));
+	/// ```
+	#[must_use]
+	pub fn error(&self, operation: &'static str, expected: &'static str) -> InfraParseError {
+		let found = self.peek().map_or(Found::EndOfInput, Found::CodePoint);
+		let error = InfraParseError::new(operation, self.position, expected, found)
+			.with_code_point_index(self.index);
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		emit(TraceEvent::ParseError(&error));
+		error
+	}
+
+	/// Advances the position past the given string, which must be at the
+	/// position, byte for byte or ASCII case-insensitively
+	fn advance_past(&mut self, matched: &str) {
+		self.position += matched.len();
+		self.index += matched.chars().count();
+	}
+
+	/// Advances the position past any ASCII whitespace.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#skip-ascii-whitespace
+	pub fn skip_ascii_whitespace(&mut self) {
+		let rest = self.remaining();
+		// ASCII whitespace is never part of a multi-byte sequence, and each
+		// byte of it is one codepoint
+		let len = find_non_ascii_whitespace(rest.as_bytes()).unwrap_or(rest.len());
+		self.position += len;
+		self.index += len;
+	}
+}
+
+/// A cursor over a byte sequence, with the same operations as
+/// [`CodePointCursor`], but a byte at a time, for parsing byte-level
+/// formats and protocols.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::ByteCursor;
+///
+/// let mut cursor = ByteCursor::new(b"HTTP/1.1 200");
+/// assert!(cursor.expect_bytes(b"HTTP/"));
+/// let version = cursor.collect_while(|b| b != b' ');
+/// assert_eq!(version, b"1.1");
+/// cursor.skip_ascii_whitespace();
+/// assert_eq!(cursor.remaining(), b"200");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ByteCursor<'a> {
+	input: &'a [u8],
+	position: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+	/// Creates a cursor at the start of a byte sequence
+	#[must_use]
+	pub const fn new(input: &'a [u8]) -> Self {
+		Self { input, position: 0 }
+	}
+
+	/// Returns the whole byte sequence that the cursor is over
+	#[must_use]
+	pub const fn input(&self) -> &'a [u8] {
+		self.input
+	}
+
+	/// Returns the position of the cursor, as an offset into the byte sequence
+	#[must_use]
+	pub const fn byte_offset(&self) -> usize {
+		self.position
+	}
+
+	/// Returns the rest of the byte sequence, from the position to the end
+	#[must_use]
+	pub fn remaining(&self) -> &'a [u8] {
+		&self.input[self.position..]
+	}
+
+	/// Checks if the position is past the end of the byte sequence, so
+	/// there are no bytes left
+	#[must_use]
+	pub fn is_past_end(&self) -> bool {
+		self.position >= self.input.len()
+	}
+
+	/// Returns the byte at the position without advancing, or `None` if the
+	/// position is past the end of the byte sequence
+	#[must_use]
+	pub fn peek(&self) -> Option<u8> {
+		self.input.get(self.position).copied()
+	}
+
+	/// Saves the position of the cursor, to go back to it later with
+	/// [`ByteCursor::restore()`]
+	#[must_use]
+	pub fn checkpoint(&self) -> Checkpoint {
+		Checkpoint {
+			position: self.position,
+			index: self.position,
+		}
+	}
+
+	/// Moves the cursor back (or forward) to a saved position.
+	///
+	/// # Panics
+	/// Panics if the checkpoint's position is past the end of the cursor's
+	/// byte sequence, which can only happen if the checkpoint came from a
+	/// cursor over a different one.
+	pub fn restore(&mut self, checkpoint: Checkpoint) {
+		assert!(
+			checkpoint.position <= self.input.len(),
+			"checkpoint is not from a cursor over this byte sequence"
+		);
+		self.position = checkpoint.position;
+	}
+
+	/// Collects the bytes from the position that match a predicate as a
+	/// slice of the byte sequence, and advances the position past them
+	pub fn collect_while<P>(&mut self, predicate: P) -> &'a [u8]
+	where
+		P: FnMut(u8) -> bool,
+	{
+		self.consume_while_max(predicate, usize::MAX)
+	}
+
+	/// Advances the position by up to `n` bytes, stopping at the end of the
+	/// byte sequence, and returns the number of bytes it advanced by
+	pub fn advance_by(&mut self, n: usize) -> usize {
+		self.take_bytes(n).len()
+	}
+
+	/// Returns the next `n` bytes from the position, or fewer if the byte
+	/// sequence ends first, and advances the position past them
+	pub fn take_bytes(&mut self, n: usize) -> &'a [u8] {
+		let rest = self.remaining();
+		let len = n.min(rest.len());
+		self.position += len;
+		&rest[..len]
+	}
+
+	/// Collects up to `max` bytes from the position that match a predicate,
+	/// and advances the position past them
+	pub fn consume_while_max<P>(&mut self, mut predicate: P, max: usize) -> &'a [u8]
+	where
+		P: FnMut(u8) -> bool,
+	{
+		let rest = self.remaining();
+		let bounded = &rest[..max.min(rest.len())];
+		let len = bounded
+			.iter()
+			.position(|&b| !predicate(b))
+			.unwrap_or(bounded.len());
+		self.position += len;
+		&rest[..len]
+	}
+
+	/// Advances the position past a byte sequence if the bytes at the
+	/// position are that sequence, and returns whether they were
+	pub fn expect_bytes(&mut self, expected: &[u8]) -> bool {
+		if !self.remaining().starts_with(expected) {
+			return false;
+		}
+		self.position += expected.len();
+		true
+	}
+
+	/// Advances the position past a byte sequence if the bytes at the
+	/// position are a [byte-case-insensitive][whatwg-infra-dfn] match for
+	/// that sequence, and returns whether they were.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#byte-case-insensitive
+	pub fn expect_bytes_ignore_ascii_case(&mut self, expected: &[u8]) -> bool {
+		let rest = self.remaining();
+		if rest.len() < expected.len() || !rest[..expected.len()].eq_ignore_ascii_case(expected) {
+			return false;
+		}
+		self.position += expected.len();
+		true
+	}
+
+	/// Advances the position past the byte at the position if it matches a
+	/// predicate, and returns it
+	pub fn consume_byte_if<P>(&mut self, predicate: P) -> Option<u8>
+	where
+		P: FnOnce(u8) -> bool,
+	{
+		let b = self.peek().filter(|&b| predicate(b))?;
+		self.position += 1;
+		Some(b)
+	}
+
+	/// Advances the position past a byte sequence like
+	/// [`ByteCursor::expect_bytes()`], but returns an error at the position
+	/// if the bytes there aren't that sequence.
+	///
+	/// # Errors
+	/// Returns an [`InfraParseError`] that found the byte at the position,
+	/// or the end of the input.
+	pub fn require_bytes(&mut self, expected: &[u8]) -> Result<(), InfraParseError> {
+		if self.expect_bytes(expected) {
+			Ok(())
+		} else {
+			Err(self.error("match a byte sequence", "the given byte sequence"))
+		}
+	}
+
+	/// Creates an error for an operation that expected something at the
+	/// position, but found the byte there (or the end of the input)
+	#[must_use]
+	pub fn error(&self, operation: &'static str, expected: &'static str) -> InfraParseError {
+		let found = self.peek().map_or(Found::EndOfInput, Found::Byte);
+		let error = InfraParseError::new(operation, self.position, expected, found);
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		emit(TraceEvent::ParseError(&error));
+		error
+	}
+
+	/// Advances the position past any ASCII whitespace bytes
+	pub fn skip_ascii_whitespace(&mut self) {
+		let rest = self.remaining();
+		self.position += find_non_ascii_whitespace(rest).unwrap_or(rest.len());
+	}
+}
+
+impl Iterator for ByteCursor<'_> {
+	type Item = u8;
+
+	fn next(&mut self) -> Option<u8> {
+		let b = self.peek()?;
+		self.position += 1;
+		Some(b)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.input.len() - self.position;
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for ByteCursor<'_> {}
+
+impl FusedIterator for ByteCursor<'_> {}
+
+/// A cursor over UTF-16 code units, which may be ill-formed, for the
+/// specifications and JavaScript interfaces that count positions in code
+/// units instead of codepoints.
+///
+/// Advancing with [`Iterator::next()`] moves by one code unit, and
+/// [`CodeUnitCursor::next_code_point()`] moves by one codepoint, which is
+/// two code units for a surrogate pair. Lone surrogates are returned as
+/// codepoints of their own, like the WHATWG Infra Standard's
+/// [code points][whatwg-infra-dfn] of a string.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string-code-point
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::CodeUnitCursor;
+///
+/// let input: Vec<u16> = "a\u{1F431}".encode_utf16().chain([0xD800]).collect();
+/// let mut cursor = CodeUnitCursor::new(&input);
+/// assert_eq!(cursor.next_code_point(), Some(0x61));
+/// assert_eq!(cursor.next_code_point(), Some(0x1F431));
+/// assert_eq!(cursor.code_unit_offset(), 3);
+/// assert_eq!(cursor.next_code_point(), Some(0xD800));
+/// assert!(cursor.is_past_end());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeUnitCursor<'a> {
+	input: &'a [u16],
+	position: usize,
+}
+
+impl<'a> CodeUnitCursor<'a> {
+	/// Creates a cursor at the start of a sequence of code units
+	#[must_use]
+	pub const fn new(input: &'a [u16]) -> Self {
+		Self { input, position: 0 }
+	}
+
+	/// Returns all the code units that the cursor is over
+	#[must_use]
+	pub const fn input(&self) -> &'a [u16] {
+		self.input
+	}
+
+	/// Returns the position of the cursor, as an offset in code units
+	#[must_use]
+	pub const fn code_unit_offset(&self) -> usize {
+		self.position
+	}
+
+	/// Returns the rest of the code units, from the position to the end
+	#[must_use]
+	pub fn remaining(&self) -> &'a [u16] {
+		&self.input[self.position..]
+	}
+
+	/// Checks if the position is past the end of the code units
+	#[must_use]
+	pub fn is_past_end(&self) -> bool {
+		self.position >= self.input.len()
+	}
+
+	/// Returns the code unit at the position without advancing, or `None`
+	/// if the position is past the end
+	#[must_use]
+	pub fn peek(&self) -> Option<u16> {
+		self.input.get(self.position).copied()
+	}
+
+	/// Returns the codepoint at the position without advancing, combining
+	/// a surrogate pair into one codepoint
+	#[must_use]
+	pub fn peek_code_point(&self) -> Option<u32> {
+		self.code_point_at_position().map(|(cp, _)| cp)
+	}
+
+	/// Returns the codepoint at the position, combining a surrogate pair
+	/// into one codepoint, and advances the position past it
+	pub fn next_code_point(&mut self) -> Option<u32> {
+		let (cp, len) = self.code_point_at_position()?;
+		self.position += len;
+		Some(cp)
+	}
+
+	/// Saves the position of the cursor, to go back to it later with
+	/// [`CodeUnitCursor::restore()`]
+	#[must_use]
+	pub fn checkpoint(&self) -> Checkpoint {
+		Checkpoint {
+			position: self.position,
+			index: self.position,
+		}
+	}
+
+	/// Moves the cursor back (or forward) to a saved position.
+	///
+	/// # Panics
+	/// Panics if the checkpoint's position is past the end of the cursor's
+	/// code units, which can only happen if the checkpoint came from a
+	/// cursor over different ones.
+	pub fn restore(&mut self, checkpoint: Checkpoint) {
+		assert!(
+			checkpoint.position <= self.input.len(),
+			"checkpoint is not from a cursor over these code units"
+		);
+		self.position = checkpoint.position;
+	}
+
+	/// Collects the code units from the position that match a predicate as
+	/// a slice, and advances the position past them
+	pub fn collect_while<P>(&mut self, mut predicate: P) -> &'a [u16]
+	where
+		P: FnMut(u16) -> bool,
+	{
+		let rest = self.remaining();
+		let len = rest
+			.iter()
+			.position(|&unit| !predicate(unit))
+			.unwrap_or(rest.len());
+		self.position += len;
+		&rest[..len]
+	}
+
+	/// Advances the position by up to `n` code units, stopping at the end,
+	/// and returns the number of code units it advanced by
+	pub fn advance_by(&mut self, n: usize) -> usize {
+		let len = n.min(self.input.len() - self.position);
+		self.position += len;
+		len
+	}
+
+	/// Advances the position past a string if the code units at the
+	/// position are its UTF-16 encoding, and returns whether they were
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::CodeUnitCursor;
+	///
+	/// let input: Vec<u16> = "\u{E9}t\u{E9}".encode_utf16().collect();
+	/// let mut cursor = CodeUnitCursor::new(&input);
+	/// assert!(cursor.expect_str("\u{E9}t"));
+	/// assert_eq!(cursor.code_unit_offset(), 2);
+	/// ```
+	pub fn expect_str(&mut self, expected: &str) -> bool {
+		let rest = self.remaining();
+		let mut len = 0;
+		for unit in expected.encode_utf16() {
+			if rest.get(len) != Some(&unit) {
+				return false;
+			}
+			len += 1;
+		}
+		self.position += len;
+		true
+	}
+
+	/// Advances the position past a string like
+	/// [`CodeUnitCursor::expect_str()`], but returns an error at the
+	/// position if the code units there aren't its UTF-16 encoding.
+	///
+	/// # Errors
+	/// Returns an [`InfraParseError`] that found the code unit at the
+	/// position, or the end of the input.
+	pub fn require_str(&mut self, expected: &str) -> Result<(), InfraParseError> {
+		if self.expect_str(expected) {
+			Ok(())
+		} else {
+			Err(self.error("match a string", "the given string"))
+		}
+	}
+
+	/// Creates an error for an operation that expected something at the
+	/// position, but found the code unit there (or the end of the input).
+	/// The error's offset is counted in code units.
+	#[must_use]
+	pub fn error(&self, operation: &'static str, expected: &'static str) -> InfraParseError {
+		let found = self.peek().map_or(Found::EndOfInput, Found::CodeUnit);
+		let error = InfraParseError::new(operation, self.position, expected, found);
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		emit(TraceEvent::ParseError(&error));
+		error
+	}
+
+	/// Returns the codepoint at the position and the number of code units
+	/// it's made of
+	fn code_point_at_position(&self) -> Option<(u32, usize)> {
+		let first = self.peek()?;
+		let second = self.input.get(self.position + 1);
+		match second.and_then(|&second| combine_surrogate_pair(first, second)) {
+			Some(cp) => Some((cp, 2)),
+			None => Some((u32::from(first), 1)),
+		}
+	}
+}
+
+impl Iterator for CodeUnitCursor<'_> {
+	type Item = u16;
+
+	fn next(&mut self) -> Option<u16> {
+		let unit = self.peek()?;
+		self.position += 1;
+		Some(unit)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.input.len() - self.position;
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for CodeUnitCursor<'_> {}
+
+impl FusedIterator for CodeUnitCursor<'_> {}
+
+/// A line and column in a string, both starting at 1, created by
+/// [`CodePointCursor::line_column()`]. Columns count codepoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+	/// The line, starting at 1
+	pub line: usize,
+	/// The codepoint in the line, starting at 1
+	pub column: usize,
+}
+
+impl fmt::Display for LineColumn {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.line, self.column)
+	}
+}
+
+/// A saved position of a [`CodePointCursor`], [`ByteCursor`], or
+/// [`CodeUnitCursor`], created by their `checkpoint()` methods
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint {
+	position: usize,
+	index: usize,
+}
+
+impl Iterator for CodePointCursor<'_> {
+	type Item = char;
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.position += c.len_utf8();
+		self.index += 1;
+		Some(c)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.remaining().chars().size_hint()
+	}
+}
+
+impl FusedIterator for CodePointCursor<'_> {}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+
+	#[test]
+	fn test_code_point_cursor_next_peek() {
+		let mut cursor = CodePointCursor::new("a\u{E9}\u{1F431}");
+		assert_eq!(cursor.peek(), Some('a'));
+		assert_eq!(cursor.next(), Some('a'));
+		assert_eq!(cursor.next(), Some('\u{E9}'));
+		assert_eq!(cursor.byte_offset(), 3);
+		assert_eq!(cursor.remaining(), "\u{1F431}");
+		assert!(!cursor.is_past_end());
+		assert_eq!(cursor.next(), Some('\u{1F431}'));
+		assert!(cursor.is_past_end());
+		assert_eq!(cursor.peek(), None);
+		assert_eq!(cursor.next(), None);
+		assert_eq!(cursor.input(), "a\u{E9}\u{1F431}");
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn test_code_point_cursor_collect_skip() {
+		let mut cursor = CodePointCursor::new("  \u{E9}t\u{E9} \t ok");
+		cursor.skip_ascii_whitespace();
+		assert_eq!(cursor.collect_codepoints(|c| c != ' '), "\u{E9}t\u{E9}");
+		assert_eq!(cursor.collect_codepoints(|c| c != ' '), "");
+		cursor.skip_ascii_whitespace();
+		assert_eq!(cursor.collect_codepoints(|_| true), "ok");
+		assert!(cursor.is_past_end());
+		cursor.skip_ascii_whitespace();
+		assert_eq!(cursor.collect_codepoints(|_| true), "");
+	}
+
+	#[test]
+	fn test_code_point_cursor_bounded() {
+		let mut cursor = CodePointCursor::new("\u{E9}\u{E9}\u{E9}abc");
+		assert_eq!(cursor.consume_while_max(|_| true, 0), "");
+		assert_eq!(cursor.consume_while_max(|c| c != 'a', 2), "\u{E9}\u{E9}");
+		assert_eq!(cursor.consume_while_max(|c| c != 'a', 2), "\u{E9}");
+		assert_eq!(cursor.advance_by(0), 0);
+		assert_eq!(cursor.advance_by(1), 1);
+		assert_eq!(cursor.take_codepoints(usize::MAX), "bc");
+		assert_eq!(cursor.advance_by(1), 0);
+		assert_eq!(cursor.byte_offset(), 9);
+	}
+
+	#[test]
+	fn test_code_point_cursor_checkpoint() {
+		let mut cursor = CodePointCursor::new("\u{E9}ab");
+		let start = cursor.checkpoint();
+		cursor.next();
+		let middle = cursor.checkpoint();
+		assert!(start < middle);
+		assert_eq!(cursor.take_codepoints(2), "ab");
+
+		cursor.restore(middle);
+		assert_eq!(cursor.remaining(), "ab");
+		cursor.restore(start);
+		assert_eq!(cursor.remaining(), "\u{E9}ab");
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_code_point_cursor_restore_other_string() {
+		let mut other = CodePointCursor::new("ab");
+		other.next();
+		let checkpoint = other.checkpoint();
+		CodePointCursor::new("\u{E9}").restore(checkpoint);
+	}
+
+	#[test]
+	fn test_code_point_cursor_line_column() {
+		let input = "ab\n\r\n\u{1F431}\r\rc";
+		let mut cursor = CodePointCursor::new(input);
+		let mut positions = alloc::vec::Vec::new();
+		loop {
+			let LineColumn { line, column } = cursor.line_column();
+			positions.push((line, column));
+			if cursor.next().is_none() {
+				break;
+			}
+		}
+		assert_eq!(
+			positions,
+			[
+				(1, 1),
+				(1, 2),
+				(1, 3),
+				(2, 1),
+				(3, 1),
+				(3, 1),
+				(3, 2),
+				(4, 1),
+				(5, 1),
+				(5, 2)
+			]
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn test_code_point_cursor_index() {
+		let input = " \u{E9}\u{1F431}a bc";
+		let mut cursor = CodePointCursor::new(input);
+		let check = |cursor: &CodePointCursor<'_>| {
+			let before = &input[..cursor.byte_offset()];
+			assert_eq!(
+				cursor.code_point_index(),
+				before.chars().count(),
+				"{before:?}"
+			);
+		};
+
+		cursor.skip_ascii_whitespace();
+		check(&cursor);
+		let checkpoint = cursor.checkpoint();
+		cursor.next();
+		check(&cursor);
+		assert_eq!(cursor.advance_by(2), 2);
+		check(&cursor);
+		cursor.restore(checkpoint);
+		check(&cursor);
+		cursor.collect_codepoints(|c| c != ' ');
+		check(&cursor);
+		cursor.consume_while_max(|_| true, 2);
+		check(&cursor);
+		cursor.take_codepoints(10);
+		check(&cursor);
+		assert_eq!(cursor.code_point_index(), 7);
+	}
+
+	#[test]
+	fn test_code_point_cursor_collect_while() {
+		let input = "\u{E9}\u{E9}-x";
+		let mut cursor = CodePointCursor::new(input);
+		let collected = cursor.collect_while(|c| c != '-');
+		assert_eq!(collected, "\u{E9}\u{E9}");
+		assert_eq!(collected.as_ptr(), input.as_ptr());
+		assert_eq!(cursor.collect_while(char::is_alphabetic), "");
+		assert_eq!(cursor.next(), Some('-'));
+		assert_eq!(cursor.collect_while(|_| true), "x");
+		assert_eq!(cursor.collect_while(|_| true), "");
+	}
+
+	#[test]
+	fn test_code_point_cursor_expect() {
+		let mut cursor = CodePointCursor::new("\u{E9}T\u{E9}x");
+		assert!(cursor.expect_str(""));
+		assert!(!cursor.expect_str("\u{E9}t"));
+		assert!(!cursor.expect_str_ignore_ascii_case("\u{C9}t"));
+		assert!(!cursor.expect_str_ignore_ascii_case("\u{E9}t\u{E9}xy"));
+		assert!(cursor.expect_str_ignore_ascii_case("\u{E9}t\u{E9}"));
+		assert_eq!(cursor.code_point_index(), 3);
+		assert_eq!(cursor.consume_char_if(|c| c == 'y'), None);
+		assert!(cursor.expect_str("x"));
+		assert_eq!(cursor.consume_char_if(|_| true), None);
+		assert!(cursor.expect_str(""));
+		assert!(!cursor.expect_str_ignore_ascii_case("x"));
+	}
+
+	#[test]
+	fn test_byte_cursor() {
+		let mut cursor = ByteCursor::new(b"\xFFGET  /a\r\n");
+		assert_eq!(cursor.len(), 10);
+		assert_eq!(cursor.next(), Some(0xFF));
+		let checkpoint = cursor.checkpoint();
+		assert!(!cursor.expect_bytes(b"POST"));
+		assert!(cursor.expect_bytes_ignore_ascii_case(b"get"));
+		cursor.skip_ascii_whitespace();
+		assert_eq!(cursor.consume_byte_if(|b| b == b'/'), Some(b'/'));
+		assert_eq!(cursor.collect_while(|b| !b.is_ascii_whitespace()), b"a");
+		assert_eq!(cursor.consume_while_max(|_| true, 1), b"\r");
+		assert_eq!(cursor.take_bytes(2), b"\n");
+		assert!(cursor.is_past_end());
+		assert_eq!(cursor.advance_by(1), 0);
+		assert_eq!(cursor.peek(), None);
+
+		cursor.restore(checkpoint);
+		assert_eq!(cursor.byte_offset(), 1);
+		assert_eq!(cursor.advance_by(3), 3);
+		assert_eq!(cursor.remaining(), b"  /a\r\n");
+		assert!(!cursor.expect_bytes_ignore_ascii_case(b"  /a\r\n\n"));
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_byte_cursor_restore_other_bytes() {
+		let mut other = ByteCursor::new(b"ab");
+		other.advance_by(2);
+		let checkpoint = other.checkpoint();
+		ByteCursor::new(b"a").restore(checkpoint);
+	}
+
+	#[test]
+	fn test_code_unit_cursor() {
+		// A trailing surrogate before a leading one doesn't form a pair
+		let input = [0xDC00, 0xD800, 0x0061, 0xD83D, 0xDC31, 0xD800];
+		let mut cursor = CodeUnitCursor::new(&input);
+		assert_eq!(cursor.len(), 6);
+		assert_eq!(cursor.next_code_point(), Some(0xDC00));
+		assert_eq!(cursor.peek_code_point(), Some(0xD800));
+		assert_eq!(cursor.next(), Some(0xD800));
+		let checkpoint = cursor.checkpoint();
+		assert!(cursor.expect_str("a\u{1F431}"));
+		assert_eq!(cursor.next_code_point(), Some(0xD800));
+		assert_eq!(cursor.next_code_point(), None);
+		assert_eq!(cursor.advance_by(1), 0);
+
+		cursor.restore(checkpoint);
+		assert!(!cursor.expect_str("a\u{1F432}"));
+		assert_eq!(cursor.code_unit_offset(), 2);
+		assert_eq!(cursor.collect_while(|unit| unit < 0xD800), [0x61]);
+		assert_eq!(cursor.advance_by(1), 1);
+		// Splitting a pair leaves its trailing surrogate on its own
+		assert_eq!(cursor.next_code_point(), Some(0xDC31));
+		assert!(cursor.expect_str(""));
+		assert_eq!(cursor.remaining(), [0xD800]);
+	}
+
+	#[test]
+	fn test_cursor_errors() {
+		let mut cursor = CodePointCursor::new("\u{E9}=");
+		assert_eq!(cursor.require_str("\u{E9}"), Ok(()));
+		let error = cursor.require_str("==").unwrap_err();
+		assert_eq!(error.operation(), "match a string");
+		assert_eq!((error.offset(), error.code_point_index()), (2, Some(1)));
+		assert_eq!(error.found(), Found::CodePoint('='));
+		assert_eq!(cursor.byte_offset(), 2);
+		cursor.next();
+		assert_eq!(cursor.error("parse", "a value").found(), Found::EndOfInput);
+
+		let mut cursor = ByteCursor::new(b"ab");
+		assert_eq!(cursor.require_bytes(b"a"), Ok(()));
+		let error = cursor.require_bytes(b"c").unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (1, None));
+		assert_eq!(error.found(), Found::Byte(b'b'));
+
+		let input = [0xD800];
+		let mut cursor = CodeUnitCursor::new(&input);
+		let error = cursor.require_str("a").unwrap_err();
+		assert_eq!(error.found(), Found::CodeUnit(0xD800));
+	}
+}
@@ -0,0 +1,285 @@
+use core::fmt::{self, Write};
+
+use crate::error::CapacityError;
+use crate::scan::{find_ascii_whitespace, find_byte, find_either_byte, find_non_ascii_whitespace};
+
+/// Formats a value into a fixed-capacity buffer, like a stack array, and
+/// returns the formatted string, for targets that can't allocate.
+///
+/// With the wrappers of this module, it runs the string algorithms without
+/// an allocator, like normalizing the newlines of a string into a buffer.
+///
+/// # Errors
+/// Returns a [`CapacityError`] if the formatted string doesn't fit in the
+/// buffer, with the length that the buffer would need.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{format_into, NormalizedNewlines};
+///
+/// let mut buffer = [0u8; 8];
+/// assert_eq!(format_into(&mut buffer, NormalizedNewlines("a\r\nb")), Ok("a\nb"));
+///
+/// let error = format_into(&mut buffer, NormalizedNewlines("one\r\ntwo\r\nthree")).unwrap_err();
+/// assert_eq!((error.capacity(), error.required()), (8, 13));
+/// ```
+pub fn format_into(buffer: &mut [u8], value: impl fmt::Display) -> Result<&str, CapacityError> {
+	let mut writer = BufferWriter {
+		buffer,
+		len: 0,
+		required: 0,
+	};
+	// Only the writer can fail, and it never does
+	let _ = write!(writer, "{}", value);
+	if writer.required > writer.buffer.len() {
+		return Err(CapacityError::new(writer.buffer.len(), writer.required));
+	}
+
+	// Each string was copied whole, so the buffer holds valid UTF-8
+	let written = &writer.buffer[..writer.len];
+	Ok(core::str::from_utf8(written).unwrap_or_default())
+}
+
+/// A writer into a fixed-capacity buffer, which keeps counting the length
+/// of the formatted string after the buffer is full
+struct BufferWriter<'b> {
+	buffer: &'b mut [u8],
+	len: usize,
+	required: usize,
+}
+
+impl Write for BufferWriter<'_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.required = self.required.saturating_add(s.len());
+		if self.required <= self.buffer.len() {
+			self.buffer[self.len..self.required].copy_from_slice(s.as_bytes());
+			self.len = self.required;
+		}
+		Ok(())
+	}
+}
+
+/// Formats a string with its newlines normalized, like
+/// [`normalize_newlines()`](crate::normalize_newlines), while it's written,
+/// without allocating a new string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#normalize-newlines
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::NormalizedNewlines;
+///
+/// let text = format!("<p>{}</p>", NormalizedNewlines("a\r\nb\rc"));
+/// assert_eq!(text, "<p>a\nb\nc</p>");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NormalizedNewlines<'a>(pub &'a str);
+
+impl fmt::Display for NormalizedNewlines<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut rest = self.0;
+		while let Some(cr) = find_byte(rest.as_bytes(), b'\r') {
+			f.write_str(&rest[..cr])?;
+			f.write_str("\n")?;
+			rest = &rest[cr + 1..];
+			if let Some(after_lf) = rest.strip_prefix('\n') {
+				rest = after_lf;
+			}
+		}
+		f.write_str(rest)
+	}
+}
+
+/// Formats a string with its newlines removed, like
+/// [`strip_newlines()`](crate::strip_newlines), while it's written, without
+/// allocating a new string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strip-newlines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StrippedNewlines<'a>(pub &'a str);
+
+impl fmt::Display for StrippedNewlines<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut rest = self.0;
+		while let Some(newline) = find_either_byte(rest.as_bytes(), b'\n', b'\r') {
+			f.write_str(&rest[..newline])?;
+			rest = &rest[newline + 1..];
+		}
+		f.write_str(rest)
+	}
+}
+
+/// Formats a string with ASCII whitespace removed from before and after
+/// it, and its runs of ASCII whitespace collapsed into single spaces, like
+/// [`trim_collapse_ascii_whitespace()`](crate::trim_collapse_ascii_whitespace),
+/// while it's written, without allocating a new string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::CollapsedAsciiWhitespace;
+///
+/// let title = CollapsedAsciiWhitespace("\n  Cats  and\tdogs ");
+/// assert_eq!(title.to_string(), "Cats and dogs");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollapsedAsciiWhitespace<'a>(pub &'a str);
+
+impl fmt::Display for CollapsedAsciiWhitespace<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut rest = self.0;
+		let mut first = true;
+		while let Some(start) = find_non_ascii_whitespace(rest.as_bytes()) {
+			if !first {
+				f.write_str(" ")?;
+			}
+			first = false;
+			rest = &rest[start..];
+
+			let end = find_ascii_whitespace(rest.as_bytes()).unwrap_or(rest.len());
+			f.write_str(&rest[..end])?;
+			rest = &rest[end..];
+		}
+		Ok(())
+	}
+}
+
+/// Formats a string with its ASCII upper alphas lowercased, like
+/// [`ascii_lowercase()`](crate::ascii_lowercase), while it's written,
+/// without allocating a new string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-lowercase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsciiLowercase<'a>(pub &'a str);
+
+impl fmt::Display for AsciiLowercase<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_ascii_case_mapped(
+			f,
+			self.0,
+			|b| b.is_ascii_uppercase(),
+			|b| b.to_ascii_lowercase(),
+		)
+	}
+}
+
+/// Formats a string with its ASCII lower alphas uppercased, like
+/// [`ascii_uppercase()`](crate::ascii_uppercase), while it's written,
+/// without allocating a new string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-uppercase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsciiUppercase<'a>(pub &'a str);
+
+impl fmt::Display for AsciiUppercase<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_ascii_case_mapped(
+			f,
+			self.0,
+			|b| b.is_ascii_lowercase(),
+			|b| b.to_ascii_uppercase(),
+		)
+	}
+}
+
+/// Writes a string with each ASCII byte that matches a predicate mapped to
+/// another ASCII byte, copying the runs of bytes between them in bulk
+fn write_ascii_case_mapped(
+	f: &mut fmt::Formatter<'_>,
+	s: &str,
+	matches: impl Fn(u8) -> bool,
+	map: impl Fn(u8) -> u8,
+) -> fmt::Result {
+	let mut rest = s;
+	// The matched bytes are ASCII, so every index found is at a char boundary
+	while let Some(i) = rest.bytes().position(&matches) {
+		f.write_str(&rest[..i])?;
+		f.write_char(char::from(map(rest.as_bytes()[i])))?;
+		rest = &rest[i + 1..];
+	}
+	f.write_str(rest)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+	use alloc::string::ToString;
+
+	const INPUTS: [&str; 7] = [
+		"",
+		"\r\n\r\n",
+		"a\r\rb\n\r\nc\r",
+		" \t\x0C\r\n ",
+		"  Cats  and\tDOGS ",
+		"\u{E9}T\u{C9}\r\n\u{1F431} z",
+		"no-op",
+	];
+
+	#[test]
+	#[cfg(feature = "strings")]
+	fn test_display_wrappers_match_functions() {
+		use crate::strings::*;
+
+		for s in INPUTS {
+			assert_eq!(NormalizedNewlines(s).to_string(), normalize_newlines(s));
+			assert_eq!(StrippedNewlines(s).to_string(), strip_newlines(s));
+			assert_eq!(
+				CollapsedAsciiWhitespace(s).to_string(),
+				trim_collapse_ascii_whitespace(s)
+			);
+			assert_eq!(AsciiLowercase(s).to_string(), ascii_lowercase(s));
+			assert_eq!(AsciiUppercase(s).to_string(), ascii_uppercase(s));
+		}
+	}
+
+	#[test]
+	fn test_format_into() {
+		let mut buffer = [0u8; 13];
+		assert_eq!(format_into(&mut buffer, ""), Ok(""));
+		assert_eq!(
+			format_into(&mut buffer, NormalizedNewlines(INPUTS[2])),
+			Ok("a\n\nb\n\nc\n")
+		);
+		assert_eq!(
+			format_into(&mut buffer, AsciiLowercase(INPUTS[5])),
+			Ok("\u{E9}t\u{C9}\r\n\u{1F431} z")
+		);
+
+		assert_eq!(
+			format_into(&mut buffer, CollapsedAsciiWhitespace(INPUTS[4])),
+			Ok("Cats and DOGS")
+		);
+		let error =
+			format_into(&mut buffer[..12], CollapsedAsciiWhitespace(INPUTS[4])).unwrap_err();
+		assert_eq!((error.capacity(), error.required()), (12, 13));
+		let error = format_into(&mut [], StrippedNewlines(INPUTS[2])).unwrap_err();
+		assert_eq!((error.capacity(), error.required()), (0, 3));
+	}
+
+	#[test]
+	fn test_display_wrappers() {
+		assert_eq!(NormalizedNewlines(INPUTS[2]).to_string(), "a\n\nb\n\nc\n");
+		assert_eq!(StrippedNewlines(INPUTS[2]).to_string(), "abc");
+		assert_eq!(CollapsedAsciiWhitespace(INPUTS[3]).to_string(), "");
+		assert_eq!(
+			AsciiLowercase(INPUTS[5]).to_string(),
+			"\u{E9}t\u{C9}\r\n\u{1F431} z"
+		);
+		assert_eq!(
+			AsciiUppercase(INPUTS[5]).to_string(),
+			"\u{E9}T\u{C9}\r\n\u{1F431} Z"
+		);
+	}
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+
+use crate::error::{Found, InfraParseError};
+
+/// An encoding that a byte order mark can select, found by [`sniff_bom()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+	/// UTF-8, with the byte order mark `EF BB BF`
+	Utf8,
+	/// UTF-16 in little-endian byte order, with the byte order mark `FF FE`
+	Utf16Le,
+	/// UTF-16 in big-endian byte order, with the byte order mark `FE FF`
+	Utf16Be,
+}
+
+impl Encoding {
+	/// Returns the name of the encoding in the Encoding Standard, like
+	/// `"UTF-16LE"`
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Utf8 => "UTF-8",
+			Self::Utf16Le => "UTF-16LE",
+			Self::Utf16Be => "UTF-16BE",
+		}
+	}
+}
+
+impl fmt::Display for Encoding {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Checks if a byte sequence starts with the byte order mark of UTF-8,
+/// UTF-16LE, or UTF-16BE, and returns that encoding with the length of the
+/// byte order mark, in bytes.
+///
+/// The decode algorithms of the Encoding Standard sniff the byte order mark
+/// first, and use its encoding instead of the one they were given.
+///
+/// See also: [WHATWG Encoding Standard definition][whatwg-encoding-dfn]
+///
+/// [whatwg-encoding-dfn]: https://encoding.spec.whatwg.org/#bom-sniff
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{sniff_bom, Encoding};
+///
+/// let bytes = b"\xFF\xFEa\0";
+/// assert_eq!(sniff_bom(bytes), Some((Encoding::Utf16Le, 2)));
+/// assert_eq!(sniff_bom(b"\xEF\xBB\xBFa"), Some((Encoding::Utf8, 3)));
+/// assert_eq!(sniff_bom(b"a"), None);
+/// ```
+#[must_use]
+pub const fn sniff_bom(bytes: &[u8]) -> Option<(Encoding, usize)> {
+	match bytes {
+		[0xEF, 0xBB, 0xBF, ..] => Some((Encoding::Utf8, 3)),
+		[0xFE, 0xFF, ..] => Some((Encoding::Utf16Be, 2)),
+		[0xFF, 0xFE, ..] => Some((Encoding::Utf16Le, 2)),
+		_ => None,
+	}
+}
+
+/// Decodes a byte sequence as UTF-8 into a string, after removing a UTF-8
+/// byte order mark from its start if it has one.
+///
+/// Each invalid sequence of bytes is replaced with U+FFFD REPLACEMENT
+/// CHARACTER, the way the Encoding Standard's UTF-8 decoder does. A byte
+/// order mark of another encoding isn't removed, and decodes to
+/// replacement characters.
+///
+/// See also: [WHATWG Encoding Standard definition][whatwg-encoding-dfn]
+///
+/// [whatwg-encoding-dfn]: https://encoding.spec.whatwg.org/#utf-8-decode
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::utf8_decode;
+///
+/// assert_eq!(utf8_decode(b"\xEF\xBB\xBFcaf\xC3\xA9"), "caf\u{E9}");
+/// assert_eq!(utf8_decode(b"caf\xC3"), "caf\u{FFFD}");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn utf8_decode(bytes: &[u8]) -> Cow<'_, str> {
+	let bytes = match sniff_bom(bytes) {
+		Some((Encoding::Utf8, len)) => &bytes[len..],
+		_ => bytes,
+	};
+	utf8_decode_without_bom(bytes)
+}
+
+/// Decodes a byte sequence as UTF-8 into a string, keeping a byte order
+/// mark at its start as U+FEFF.
+///
+/// Each invalid sequence of bytes is replaced with U+FFFD REPLACEMENT
+/// CHARACTER, like [`utf8_decode()`]. Valid UTF-8 is borrowed without
+/// copying it.
+///
+/// See also: [WHATWG Encoding Standard definition][whatwg-encoding-dfn]
+///
+/// [whatwg-encoding-dfn]: https://encoding.spec.whatwg.org/#utf-8-decode-without-bom
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::utf8_decode_without_bom;
+///
+/// assert_eq!(utf8_decode_without_bom(b"\xEF\xBB\xBFa"), "\u{FEFF}a");
+/// assert_eq!(utf8_decode_without_bom(b"\xF0\x9F\x90a"), "\u{FFFD}a");
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn utf8_decode_without_bom(bytes: &[u8]) -> Cow<'_, str> {
+	// The standard library replaces the maximal subparts of invalid
+	// sequences, which is what the Encoding Standard's decoder does too
+	String::from_utf8_lossy(bytes)
+}
+
+/// Decodes a byte sequence as UTF-8 into a string, keeping a byte order
+/// mark at its start as U+FEFF, or fails if it isn't valid UTF-8.
+///
+/// See also: [WHATWG Encoding Standard definition][whatwg-encoding-dfn]
+///
+/// [whatwg-encoding-dfn]: https://encoding.spec.whatwg.org/#utf-8-decode-without-bom-or-fail
+///
+/// # Errors
+/// Returns an [`InfraParseError`] at the first byte that starts an invalid
+/// sequence, or that found the end of the input if the byte sequence ends
+/// in the middle of a codepoint.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{utf8_decode_without_bom_or_fail, Found};
+///
+/// assert_eq!(utf8_decode_without_bom_or_fail(b"caf\xC3\xA9"), Ok("caf\u{E9}"));
+///
+/// let error = utf8_decode_without_bom_or_fail(b"ab\xFF").unwrap_err();
+/// assert_eq!((error.offset(), error.found()), (2, Found::Byte(0xFF)));
+/// ```
+pub fn utf8_decode_without_bom_or_fail(bytes: &[u8]) -> Result<&str, InfraParseError> {
+	core::str::from_utf8(bytes).map_err(|e| {
+		let offset = e.valid_up_to();
+		let found = match e.error_len() {
+			Some(_) => Found::Byte(bytes[offset]),
+			None => Found::EndOfInput,
+		};
+		InfraParseError::new("UTF-8 decode", offset, "valid UTF-8", found)
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+	#[test]
+	fn test_sniff_bom() {
+		assert_eq!(sniff_bom(UTF8_BOM), Some((Encoding::Utf8, 3)));
+		assert_eq!(sniff_bom(b"\xFE\xFF"), Some((Encoding::Utf16Be, 2)));
+		assert_eq!(sniff_bom(b"\xFF\xFE\x00\x00"), Some((Encoding::Utf16Le, 2)));
+		assert_eq!(sniff_bom(b"\xEF\xBB"), None);
+		assert_eq!(sniff_bom(b"\xFE"), None);
+		assert_eq!(sniff_bom(b""), None);
+		assert_eq!(Encoding::Utf16Be.name(), "UTF-16BE");
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn test_utf8_decode_bom() {
+		assert_eq!(utf8_decode(b""), "");
+		assert_eq!(utf8_decode(UTF8_BOM), "");
+		assert_eq!(utf8_decode(b"\xEF\xBB\xBF\xEF\xBB\xBF"), "\u{FEFF}");
+		assert_eq!(utf8_decode(b"\xEF\xBB"), "\u{FFFD}");
+		assert_eq!(utf8_decode(b"\xFF\xFEa"), "\u{FFFD}\u{FFFD}a");
+		assert!(matches!(
+			utf8_decode(b"\xEF\xBB\xBFok"),
+			Cow::Borrowed("ok")
+		));
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn test_utf8_decode_replacement() {
+		// Each maximal subpart of an invalid sequence is one replacement
+		assert_eq!(utf8_decode_without_bom(b"\xE2\x82a"), "\u{FFFD}a");
+		assert_eq!(utf8_decode_without_bom(b"\xC0\x80"), "\u{FFFD}\u{FFFD}");
+		assert_eq!(
+			utf8_decode_without_bom(b"\xED\xA0\x80"),
+			"\u{FFFD}\u{FFFD}\u{FFFD}"
+		);
+		assert_eq!(
+			utf8_decode_without_bom(b"\xF4\x90\x80\x80"),
+			"\u{FFFD}".repeat(4)
+		);
+	}
+
+	#[test]
+	fn test_utf8_decode_without_bom_or_fail() {
+		assert_eq!(utf8_decode_without_bom_or_fail(UTF8_BOM), Ok("\u{FEFF}"));
+		let error = utf8_decode_without_bom_or_fail(b"a\xF0\x9F\x90").unwrap_err();
+		assert_eq!((error.offset(), error.found()), (1, Found::EndOfInput));
+		let error = utf8_decode_without_bom_or_fail(b"a\xF0\x9Fa").unwrap_err();
+		assert_eq!((error.offset(), error.found()), (1, Found::Byte(0xF0)));
+	}
+}
@@ -0,0 +1,370 @@
+use core::fmt;
+
+#[cfg(feature = "json")]
+use crate::json::{JsonBytesParseError, JsonParseError};
+
+/// Any error returned by this crate, for applications that pass errors of
+/// different operations up through one type.
+///
+/// Each operation-specific error converts into it with [`From`], so `?`
+/// works on them in functions returning `Result<_, whatwg_infra::Error>`.
+///
+/// # Examples
+#[cfg_attr(feature = "json", doc = "```")]
+#[cfg_attr(not(feature = "json"), doc = "```ignore")]
+/// use whatwg_infra::{parse_json_string_to_infra_value, CodePointCursor, Error};
+///
+/// fn parse_assignment(input: &str) -> Result<(String, String), Error> {
+///     let mut cursor = CodePointCursor::new(input);
+///     let name = cursor.collect_codepoints(|c| c != '=');
+///     cursor.require_str("=")?;
+///     let value = parse_json_string_to_infra_value(cursor.remaining())?;
+///     Ok((name, value.to_string()))
+/// }
+///
+/// assert!(parse_assignment("a=[1, 2]").is_ok());
+/// assert!(matches!(parse_assignment("a"), Err(Error::Parse(_))));
+/// assert!(matches!(parse_assignment("a=["), Err(Error::Json(_))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+	/// Parsing with a cursor or a fallible string operation failed
+	Parse(InfraParseError),
+	/// A formatted string doesn't fit in a fixed-capacity buffer
+	Capacity(CapacityError),
+	/// A string isn't valid JSON
+	#[cfg(feature = "json")]
+	Json(JsonParseError),
+	/// A byte sequence isn't valid UTF-8 or valid JSON
+	#[cfg(feature = "json")]
+	JsonBytes(JsonBytesParseError),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Parse(e) => e.fmt(f),
+			Self::Capacity(e) => e.fmt(f),
+			#[cfg(feature = "json")]
+			Self::Json(e) => e.fmt(f),
+			#[cfg(feature = "json")]
+			Self::JsonBytes(e) => e.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Parse(e) => Some(e),
+			Self::Capacity(e) => Some(e),
+			#[cfg(feature = "json")]
+			Self::Json(e) => Some(e),
+			#[cfg(feature = "json")]
+			Self::JsonBytes(e) => Some(e),
+		}
+	}
+}
+
+impl From<InfraParseError> for Error {
+	fn from(e: InfraParseError) -> Self {
+		Self::Parse(e)
+	}
+}
+
+impl From<CapacityError> for Error {
+	fn from(e: CapacityError) -> Self {
+		Self::Capacity(e)
+	}
+}
+
+#[cfg(feature = "json")]
+impl From<JsonParseError> for Error {
+	fn from(e: JsonParseError) -> Self {
+		Self::Json(e)
+	}
+}
+
+#[cfg(feature = "json")]
+impl From<JsonBytesParseError> for Error {
+	fn from(e: JsonBytesParseError) -> Self {
+		Self::JsonBytes(e)
+	}
+}
+
+/// An error returned when parsing input with one of the crate's cursors or
+/// fallible string operations fails.
+///
+/// It records which operation failed, the position it failed at, what the
+/// operation expected there, and what it found instead. Parsers built on a
+/// cursor can create their own errors at the cursor's position with
+/// [`CodePointCursor::error()`], [`ByteCursor::error()`], or
+/// [`CodeUnitCursor::error()`].
+///
+/// [`CodePointCursor::error()`]: crate::cursor::CodePointCursor::error
+/// [`ByteCursor::error()`]: crate::cursor::ByteCursor::error
+/// [`CodeUnitCursor::error()`]: crate::cursor::CodeUnitCursor::error
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{CodePointCursor, Found};
+///
+/// let mut cursor = CodePointCursor::new("\u{E9}t\u{E9}");
+/// cursor.next();
+/// let error = cursor.require_str("=").unwrap_err();
+/// assert_eq!(error.offset(), 2);
+/// assert_eq!(error.code_point_index(), Some(1));
+/// assert_eq!(error.found(), Found::CodePoint('t'));
+/// assert_eq!(
+///     error.to_string(),
+///     "match a string failed at offset 2 (codepoint 1): expected the given string, found 't'"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InfraParseError {
+	operation: &'static str,
+	offset: usize,
+	code_point_index: Option<usize>,
+	expected: &'static str,
+	found: Found,
+}
+
+impl InfraParseError {
+	/// Creates an error for an operation that expected something at an
+	/// offset of its input, but found something else
+	#[must_use]
+	pub const fn new(
+		operation: &'static str,
+		offset: usize,
+		expected: &'static str,
+		found: Found,
+	) -> Self {
+		Self {
+			operation,
+			offset,
+			code_point_index: None,
+			expected,
+			found,
+		}
+	}
+
+	/// Returns the error with the index of the codepoint it happened at,
+	/// for input that's counted in codepoints as well as bytes
+	#[must_use]
+	pub const fn with_code_point_index(mut self, index: usize) -> Self {
+		self.code_point_index = Some(index);
+		self
+	}
+
+	/// Returns a description of the operation that failed, like
+	/// `"collect a sequence of codepoints"`
+	#[must_use]
+	pub const fn operation(&self) -> &'static str {
+		self.operation
+	}
+
+	/// Returns the offset in the input that the operation failed at. It's
+	/// counted in bytes, or in code units for input made of UTF-16 code units.
+	#[must_use]
+	pub const fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the index of the codepoint that the operation failed at, if
+	/// the input is counted in codepoints
+	#[must_use]
+	pub const fn code_point_index(&self) -> Option<usize> {
+		self.code_point_index
+	}
+
+	/// Returns a description of what the operation expected, like
+	/// `"a codepoint boundary"`
+	#[must_use]
+	pub const fn expected(&self) -> &'static str {
+		self.expected
+	}
+
+	/// Returns what the operation found instead
+	#[must_use]
+	pub const fn found(&self) -> Found {
+		self.found
+	}
+}
+
+impl fmt::Display for InfraParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} failed at offset {}", self.operation, self.offset)?;
+		if let Some(index) = self.code_point_index {
+			write!(f, " (codepoint {})", index)?;
+		}
+		write!(f, ": expected {}, found {}", self.expected, self.found)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InfraParseError {}
+
+/// An error returned when a formatted string doesn't fit in a fixed-capacity
+/// buffer, by [`format_into()`](crate::display::format_into)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CapacityError {
+	capacity: usize,
+	required: usize,
+}
+
+impl CapacityError {
+	/// Creates an error for a buffer of a capacity, in bytes, that needed to
+	/// hold a string of another length
+	#[must_use]
+	pub const fn new(capacity: usize, required: usize) -> Self {
+		Self { capacity, required }
+	}
+
+	/// Returns the capacity of the buffer, in bytes
+	#[must_use]
+	pub const fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Returns the length of the formatted string, in bytes, which is the
+	/// capacity that the buffer would need
+	#[must_use]
+	pub const fn required(&self) -> usize {
+		self.required
+	}
+}
+
+impl fmt::Display for CapacityError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"a buffer of {} bytes can't hold a string of {} bytes",
+			self.capacity, self.required
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// How an operation handles input that it can't convert, like a codepoint
+/// above U+00FF when isomorphic encoding.
+///
+/// Operations that accept a policy take it in a `_with_policy` variant, so
+/// one policy can be chosen for an application and passed to each of them.
+///
+/// # Examples
+#[cfg_attr(feature = "strings", doc = "```")]
+#[cfg_attr(not(feature = "strings"), doc = "```ignore")]
+/// use whatwg_infra::{isomorphic_encode_with_policy, ViolationPolicy};
+///
+/// let value = "caf\u{E9} \u{2615}";
+/// assert!(isomorphic_encode_with_policy(value, ViolationPolicy::Error).is_err());
+/// assert_eq!(
+///     isomorphic_encode_with_policy(value, ViolationPolicy::Replace),
+///     Ok(b"caf\xE9 ?".to_vec())
+/// );
+/// assert_eq!(
+///     isomorphic_encode_with_policy(value, ViolationPolicy::Ignore),
+///     Ok(b"caf\xE9 ".to_vec())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ViolationPolicy {
+	/// The operation returns an error at the first input it can't convert
+	#[default]
+	Error,
+	/// The operation replaces each input it can't convert, with U+FFFD
+	/// REPLACEMENT CHARACTER in strings, or with `?` (0x3F) in byte
+	/// sequences and ASCII strings, which can't hold U+FFFD
+	Replace,
+	/// The operation leaves out each input it can't convert
+	Ignore,
+}
+
+/// What an operation found where it expected something else, as part of an
+/// [`InfraParseError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Found {
+	/// The input ended
+	EndOfInput,
+	/// A codepoint of a string
+	CodePoint(char),
+	/// A byte of a byte sequence, or of a string that isn't on a codepoint
+	/// boundary
+	Byte(u8),
+	/// A UTF-16 code unit
+	CodeUnit(u16),
+}
+
+impl fmt::Display for Found {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::EndOfInput => f.write_str("the end of the input"),
+			Self::CodePoint(c) => write!(f, "{:?}", c),
+			Self::Byte(byte) => write!(f, "byte 0x{:02X}", byte),
+			Self::CodeUnit(unit) => write!(f, "code unit 0x{:04X}", unit),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+	use alloc::string::ToString;
+
+	#[test]
+	fn test_infra_parse_error_display() {
+		let error =
+			InfraParseError::new("parse a token", 3, "a token codepoint", Found::Byte(0x80));
+		assert_eq!(error.code_point_index(), None);
+		assert_eq!(
+			error.to_string(),
+			"parse a token failed at offset 3: expected a token codepoint, found byte 0x80"
+		);
+
+		let error = InfraParseError::new("parse a token", 3, "\"=\"", Found::EndOfInput)
+			.with_code_point_index(2);
+		assert_eq!(
+			error.to_string(),
+			"parse a token failed at offset 3 (codepoint 2): expected \"=\", found the end of the input"
+		);
+		assert_eq!(Found::CodeUnit(0xD800).to_string(), "code unit 0xD800");
+	}
+
+	#[test]
+	fn test_error_from() {
+		let parse = InfraParseError::new("parse", 0, "a value", Found::EndOfInput);
+		let error = Error::from(parse);
+		assert_eq!(error, Error::Parse(parse));
+		assert_eq!(error.to_string(), parse.to_string());
+		let capacity = CapacityError::new(4, 6);
+		assert_eq!(Error::from(capacity), Error::Capacity(capacity));
+		assert_eq!(
+			capacity.to_string(),
+			"a buffer of 4 bytes can't hold a string of 6 bytes"
+		);
+
+		#[cfg(feature = "json")]
+		{
+			let json = JsonParseError::UnexpectedEnd;
+			assert_eq!(Error::from(json), Error::Json(json));
+			let bytes = JsonBytesParseError::Syntax(json);
+			assert_eq!(Error::from(bytes).to_string(), json.to_string());
+		}
+	}
+
+	#[test]
+	#[cfg(all(feature = "std", feature = "json"))]
+	fn test_error_source() {
+		use std::error::Error as _;
+
+		let error = Error::from(JsonBytesParseError::Syntax(JsonParseError::UnexpectedEnd));
+		let source = error.source().unwrap();
+		assert!(source.is::<JsonBytesParseError>());
+		assert!(source.source().unwrap().is::<JsonParseError>());
+	}
+}
@@ -0,0 +1,384 @@
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
+
+use crate::codepoints::scalar::InfraScalarValue;
+use crate::strings::{
+	ascii_lowercase, ascii_uppercase, contains_noncharacter, isomorphic_decode, isomorphic_encode,
+	normalize_newlines, strip_newlines, trim_ascii_whitespace, trim_collapse_ascii_whitespace,
+};
+
+/// The result of a function of the C interface
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WhatwgInfraStatus {
+	/// The function succeeded, and wrote its output
+	Ok = 0,
+	/// A pointer argument is null, other than the input of a zero-length
+	/// string
+	NullPointer = 1,
+	/// A string argument isn't valid UTF-8
+	InvalidUtf8 = 2,
+	/// A string can't be isomorphic encoded, because it contains a
+	/// codepoint above U+00FF
+	NotIsomorphic = 3,
+}
+
+/// A string or byte sequence allocated by this crate, which the caller owns
+/// and frees with [`whatwg_infra_buffer_free()`].
+///
+/// The strings are UTF-8, and like all of the buffers, aren't terminated by
+/// a NUL byte.
+#[repr(C)]
+#[derive(Debug)]
+pub struct WhatwgInfraBuffer {
+	/// A pointer to the first byte, which is dangling for an empty buffer
+	pub data: *mut u8,
+	/// The length of the buffer, in bytes
+	pub len: usize,
+	/// The capacity of the allocation, which only
+	/// [`whatwg_infra_buffer_free()`] uses
+	pub capacity: usize,
+}
+
+impl WhatwgInfraBuffer {
+	fn from_vec(bytes: Vec<u8>) -> Self {
+		let mut bytes = ManuallyDrop::new(bytes);
+		Self {
+			data: bytes.as_mut_ptr(),
+			len: bytes.len(),
+			capacity: bytes.capacity(),
+		}
+	}
+
+	fn from_string(s: String) -> Self {
+		Self::from_vec(s.into_bytes())
+	}
+}
+
+/// Frees a buffer that a function of the C interface wrote. Freeing a
+/// buffer whose `data` is null does nothing.
+///
+/// # Safety
+/// The buffer must have been written by this crate, and must not be freed
+/// twice.
+#[no_mangle]
+pub unsafe extern "C" fn whatwg_infra_buffer_free(buffer: WhatwgInfraBuffer) {
+	if buffer.data.is_null() {
+		return;
+	}
+	drop(Vec::from_raw_parts(
+		buffer.data,
+		buffer.len,
+		buffer.capacity,
+	));
+}
+
+/// Borrows a byte sequence from a pointer and a length, accepting a null
+/// pointer for an empty sequence
+unsafe fn input_bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], WhatwgInfraStatus> {
+	if data.is_null() {
+		return match len {
+			0 => Ok(&[]),
+			_ => Err(WhatwgInfraStatus::NullPointer),
+		};
+	}
+	Ok(core::slice::from_raw_parts(data, len))
+}
+
+/// Borrows a UTF-8 string from a pointer and a length
+unsafe fn input_str<'a>(data: *const u8, len: usize) -> Result<&'a str, WhatwgInfraStatus> {
+	let bytes = input_bytes(data, len)?;
+	core::str::from_utf8(bytes).map_err(|_| WhatwgInfraStatus::InvalidUtf8)
+}
+
+/// Runs an operation on a UTF-8 input string, and writes its output through
+/// a pointer
+unsafe fn run<T>(
+	data: *const u8,
+	len: usize,
+	output: *mut T,
+	operation: impl FnOnce(&str) -> Result<T, WhatwgInfraStatus>,
+) -> WhatwgInfraStatus {
+	if output.is_null() {
+		return WhatwgInfraStatus::NullPointer;
+	}
+	match input_str(data, len).and_then(operation) {
+		Ok(value) => {
+			output.write(value);
+			WhatwgInfraStatus::Ok
+		}
+		Err(status) => status,
+	}
+}
+
+/// Declares C functions that run a string operation returning a `String`
+macro_rules! string_operations {
+	($($(#[$meta:meta])* $name:ident => $operation:path;)+) => {
+		$(
+			$(#[$meta])*
+			///
+			/// Writes the result to `output`, which the caller frees with
+			/// [`whatwg_infra_buffer_free()`].
+			///
+			/// # Safety
+			/// `data` must point to `len` readable bytes, unless `len` is 0,
+			/// and `output` must point to writable memory for a
+			/// [`WhatwgInfraBuffer`].
+			#[no_mangle]
+			pub unsafe extern "C" fn $name(
+				data: *const u8,
+				len: usize,
+				output: *mut WhatwgInfraBuffer,
+			) -> WhatwgInfraStatus {
+				run(data, len, output, |s| Ok(WhatwgInfraBuffer::from_string($operation(s))))
+			}
+		)+
+	};
+}
+
+string_operations! {
+	/// Calls [`normalize_newlines()`] on a UTF-8 string.
+	whatwg_infra_normalize_newlines => normalize_newlines;
+	/// Calls [`strip_newlines()`] on a UTF-8 string.
+	whatwg_infra_strip_newlines => strip_newlines;
+	/// Calls [`trim_collapse_ascii_whitespace()`] on a UTF-8 string.
+	whatwg_infra_strip_and_collapse_ascii_whitespace => trim_collapse_ascii_whitespace;
+	/// Calls [`ascii_lowercase()`] on a UTF-8 string.
+	whatwg_infra_ascii_lowercase => ascii_lowercase;
+	/// Calls [`ascii_uppercase()`] on a UTF-8 string.
+	whatwg_infra_ascii_uppercase => ascii_uppercase;
+}
+
+/// Finds the part of a UTF-8 string without ASCII whitespace before and
+/// after it, like [`trim_ascii_whitespace()`], without copying the string.
+///
+/// Writes the byte offset of the part to `start`, and its length to
+/// `output_len`.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes, unless `len` is 0, and
+/// `start` and `output_len` must point to writable memory for a `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn whatwg_infra_strip_leading_and_trailing_ascii_whitespace(
+	data: *const u8,
+	len: usize,
+	start: *mut usize,
+	output_len: *mut usize,
+) -> WhatwgInfraStatus {
+	if output_len.is_null() {
+		return WhatwgInfraStatus::NullPointer;
+	}
+	run(data, len, start, |s| {
+		let trimmed = trim_ascii_whitespace(s);
+		output_len.write(trimmed.len());
+		Ok(trimmed.as_ptr() as usize - s.as_ptr() as usize)
+	})
+}
+
+/// Calls [`isomorphic_encode()`] on a UTF-8 string.
+///
+/// Writes the byte sequence to `output`, which the caller frees with
+/// [`whatwg_infra_buffer_free()`].
+///
+/// # Safety
+/// `data` must point to `len` readable bytes, unless `len` is 0, and
+/// `output` must point to writable memory for a [`WhatwgInfraBuffer`].
+#[no_mangle]
+pub unsafe extern "C" fn whatwg_infra_isomorphic_encode(
+	data: *const u8,
+	len: usize,
+	output: *mut WhatwgInfraBuffer,
+) -> WhatwgInfraStatus {
+	run(data, len, output, |s| {
+		isomorphic_encode(s)
+			.map(WhatwgInfraBuffer::from_vec)
+			.ok_or(WhatwgInfraStatus::NotIsomorphic)
+	})
+}
+
+/// Calls [`isomorphic_decode()`] on a byte sequence, which can't fail for
+/// valid pointers.
+///
+/// Writes the UTF-8 string to `output`, which the caller frees with
+/// [`whatwg_infra_buffer_free()`].
+///
+/// # Safety
+/// `data` must point to `len` readable bytes, unless `len` is 0, and
+/// `output` must point to writable memory for a [`WhatwgInfraBuffer`].
+#[no_mangle]
+pub unsafe extern "C" fn whatwg_infra_isomorphic_decode(
+	data: *const u8,
+	len: usize,
+	output: *mut WhatwgInfraBuffer,
+) -> WhatwgInfraStatus {
+	if output.is_null() {
+		return WhatwgInfraStatus::NullPointer;
+	}
+	match input_bytes(data, len) {
+		Ok(bytes) => {
+			output.write(WhatwgInfraBuffer::from_string(isomorphic_decode(bytes)));
+			WhatwgInfraStatus::Ok
+		}
+		Err(status) => status,
+	}
+}
+
+/// Calls [`contains_noncharacter()`] on a UTF-8 string, and writes the
+/// result to `output`.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes, unless `len` is 0, and
+/// `output` must point to writable memory for a `bool`.
+#[no_mangle]
+pub unsafe extern "C" fn whatwg_infra_contains_noncharacter(
+	data: *const u8,
+	len: usize,
+	output: *mut bool,
+) -> WhatwgInfraStatus {
+	run(data, len, output, |s| Ok(contains_noncharacter(s)))
+}
+
+/// Checks if a codepoint is a [noncharacter](crate::is_noncharacter). Values
+/// above U+10FFFF aren't.
+#[no_mangle]
+pub extern "C" fn whatwg_infra_is_noncharacter(code_point: u32) -> bool {
+	code_point.is_noncharacter()
+}
+
+/// Checks if a codepoint is a [C0 control](crate::is_c0_control)
+#[no_mangle]
+pub extern "C" fn whatwg_infra_is_c0_control(code_point: u32) -> bool {
+	code_point.is_c0_control()
+}
+
+/// Checks if a codepoint is a [C0 control or space](crate::is_c0_control_space)
+#[no_mangle]
+pub extern "C" fn whatwg_infra_is_c0_control_or_space(code_point: u32) -> bool {
+	code_point.is_c0_control_space()
+}
+
+/// Checks if a codepoint is an [ASCII tab or newline](crate::is_ascii_tab_newline)
+#[no_mangle]
+pub extern "C" fn whatwg_infra_is_ascii_tab_or_newline(code_point: u32) -> bool {
+	code_point.is_ascii_tab_newline()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use core::mem::MaybeUninit;
+	use core::ptr;
+
+	unsafe fn call_string(
+		function: unsafe extern "C" fn(
+			*const u8,
+			usize,
+			*mut WhatwgInfraBuffer,
+		) -> WhatwgInfraStatus,
+		input: &[u8],
+	) -> Result<Vec<u8>, WhatwgInfraStatus> {
+		let mut output = MaybeUninit::uninit();
+		match function(input.as_ptr(), input.len(), output.as_mut_ptr()) {
+			WhatwgInfraStatus::Ok => {
+				let output = output.assume_init();
+				let bytes = core::slice::from_raw_parts(output.data, output.len).to_vec();
+				whatwg_infra_buffer_free(output);
+				Ok(bytes)
+			}
+			status => Err(status),
+		}
+	}
+
+	#[test]
+	fn test_ffi_string_operations() {
+		unsafe {
+			let call = call_string;
+			assert_eq!(
+				call(whatwg_infra_normalize_newlines, b"a\r\nb\r"),
+				Ok(b"a\nb\n".to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_strip_newlines, b"a\r\nb"),
+				Ok(b"ab".to_vec())
+			);
+			assert_eq!(
+				call(
+					whatwg_infra_strip_and_collapse_ascii_whitespace,
+					b" a \t b "
+				),
+				Ok(b"a b".to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_ascii_lowercase, b"AbC"),
+				Ok(b"abc".to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_ascii_uppercase, b"AbC"),
+				Ok(b"ABC".to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_isomorphic_encode, "\u{E9}".as_bytes()),
+				Ok(b"\xE9".to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_isomorphic_encode, "\u{2615}".as_bytes()),
+				Err(WhatwgInfraStatus::NotIsomorphic)
+			);
+			assert_eq!(
+				call(whatwg_infra_isomorphic_decode, b"\xE9"),
+				Ok("\u{E9}".as_bytes().to_vec())
+			);
+			assert_eq!(
+				call(whatwg_infra_ascii_lowercase, b"\xFF"),
+				Err(WhatwgInfraStatus::InvalidUtf8)
+			);
+		}
+	}
+
+	#[test]
+	fn test_ffi_pointers() {
+		unsafe {
+			let mut output = MaybeUninit::uninit();
+			let status = whatwg_infra_normalize_newlines(ptr::null(), 0, output.as_mut_ptr());
+			assert_eq!(status, WhatwgInfraStatus::Ok);
+			let output = output.assume_init();
+			assert_eq!(output.len, 0);
+			whatwg_infra_buffer_free(output);
+
+			let mut output = MaybeUninit::uninit();
+			let status = whatwg_infra_normalize_newlines(ptr::null(), 1, output.as_mut_ptr());
+			assert_eq!(status, WhatwgInfraStatus::NullPointer);
+			let status = whatwg_infra_normalize_newlines(b"a".as_ptr(), 1, ptr::null_mut());
+			assert_eq!(status, WhatwgInfraStatus::NullPointer);
+
+			let input = b" \tcat\n";
+			let (mut start, mut len) = (0, 0);
+			let status = whatwg_infra_strip_leading_and_trailing_ascii_whitespace(
+				input.as_ptr(),
+				input.len(),
+				&mut start,
+				&mut len,
+			);
+			assert_eq!((status, start, len), (WhatwgInfraStatus::Ok, 2, 3));
+
+			let mut found = false;
+			let input = "a\u{FDD0}".as_bytes();
+			let status =
+				whatwg_infra_contains_noncharacter(input.as_ptr(), input.len(), &mut found);
+			assert_eq!((status, found), (WhatwgInfraStatus::Ok, true));
+		}
+	}
+
+	#[test]
+	fn test_ffi_predicates() {
+		assert!(whatwg_infra_is_noncharacter(0xFFFE) && !whatwg_infra_is_noncharacter(0x11FFFE));
+		assert!(whatwg_infra_is_c0_control(0x1F) && !whatwg_infra_is_c0_control(0x20));
+		assert!(whatwg_infra_is_c0_control_or_space(0x20));
+		assert!(
+			whatwg_infra_is_ascii_tab_or_newline(0x0A)
+				&& !whatwg_infra_is_ascii_tab_or_newline(0x0C)
+		);
+	}
+}
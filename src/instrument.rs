@@ -0,0 +1,195 @@
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::error::InfraParseError;
+#[cfg(feature = "json")]
+use crate::json::JsonBytesParseError;
+
+/// Something that happened while one of the crate's operations ran, which
+/// is passed to the hook set with [`set_trace_hook()`].
+///
+/// The JSON parsing and serialization functions are spans, reported with
+/// an [`TraceEvent::Enter`] and an [`TraceEvent::Exit`] event around the
+/// events inside them. Errors are reported when they're created, so a
+/// cursor error that's created while trying one alternative is reported
+/// even if another alternative then succeeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceEvent<'a> {
+	/// An operation started, like `"parse a JSON string to an Infra value"`
+	Enter(&'static str),
+	/// An operation finished, whether it succeeded or failed
+	Exit(&'static str),
+	/// A cursor created an error for what it expected at its position
+	ParseError(&'a InfraParseError),
+	/// Parsing JSON failed, either in one of the parsing functions or in a
+	/// [`JsonStreamParser`](crate::JsonStreamParser)
+	#[cfg(feature = "json")]
+	JsonError(&'a JsonBytesParseError),
+}
+
+/// A function that's called with each [`TraceEvent`]
+pub type TraceHook = fn(&TraceEvent<'_>);
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the function that's called with the events of every thread, or
+/// removes it with `None`, and returns the previous one.
+///
+/// Without a hook, each event costs a single atomic load.
+///
+/// # Examples
+#[cfg_attr(feature = "json", doc = "```")]
+#[cfg_attr(not(feature = "json"), doc = "```ignore")]
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use whatwg_infra::{parse_json_string_to_infra_value, set_trace_hook, TraceEvent};
+///
+/// static ERRORS: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn count_errors(event: &TraceEvent<'_>) {
+///     if let TraceEvent::JsonError(error) = event {
+///         ERRORS.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// set_trace_hook(Some(count_errors));
+/// assert!(parse_json_string_to_infra_value("[1, 2").is_err());
+/// set_trace_hook(None);
+/// assert_eq!(ERRORS.load(Ordering::Relaxed), 1);
+/// ```
+pub fn set_trace_hook(hook: Option<TraceHook>) -> Option<TraceHook> {
+	let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut ());
+	// SAFETY: Only null and `TraceHook` pointers are stored
+	unsafe { from_ptr(HOOK.swap(hook, Ordering::AcqRel)) }
+}
+
+/// Calls the trace hook with an event, if there is one
+#[inline]
+pub(crate) fn emit(event: TraceEvent<'_>) {
+	// SAFETY: Only null and `TraceHook` pointers are stored
+	if let Some(hook) = unsafe { from_ptr(HOOK.load(Ordering::Acquire)) } {
+		hook(&event);
+	}
+}
+
+/// Converts a stored pointer back into the hook it came from.
+///
+/// # Safety
+/// The pointer must be null or have been converted from a [`TraceHook`].
+unsafe fn from_ptr(hook: *mut ()) -> Option<TraceHook> {
+	if hook.is_null() {
+		None
+	} else {
+		Some(mem::transmute::<*mut (), TraceHook>(hook))
+	}
+}
+
+/// Reports the start of an operation when it's created, and its end when
+/// it's dropped
+#[cfg(feature = "json")]
+pub(crate) struct Span(&'static str);
+
+#[cfg(feature = "json")]
+impl Span {
+	pub(crate) fn enter(operation: &'static str) -> Self {
+		emit(TraceEvent::Enter(operation));
+		Self(operation)
+	}
+}
+
+#[cfg(feature = "json")]
+impl Drop for Span {
+	fn drop(&mut self) {
+		emit(TraceEvent::Exit(self.0));
+	}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+	use super::*;
+	use crate::cursor::CodePointCursor;
+	use std::cell::RefCell;
+	use std::format;
+	use std::string::String;
+	use std::vec::Vec;
+
+	std::thread_local! {
+		static EVENTS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+	}
+
+	// The hook is shared by every thread, so it only records the events
+	// of the thread that's running this test
+	fn record(event: &TraceEvent<'_>) {
+		EVENTS.with(|events| {
+			if let Some(events) = events.borrow_mut().as_mut() {
+				events.push(format!("{:?}", event));
+			}
+		});
+	}
+
+	fn recorded<F: FnOnce()>(f: F) -> Vec<String> {
+		EVENTS.with(|events| *events.borrow_mut() = Some(Vec::new()));
+		f();
+		EVENTS.with(|events| events.borrow_mut().take().unwrap())
+	}
+
+	#[test]
+	fn test_trace_hook() {
+		set_trace_hook(Some(record));
+
+		let events = recorded(|| {
+			let mut cursor = CodePointCursor::new("a");
+			assert!(cursor.require_str("b").is_err());
+		});
+		assert_eq!(events.len(), 1);
+		assert!(events[0].starts_with("ParseError("), "{:?}", events);
+
+		#[cfg(feature = "json")]
+		{
+			use crate::json::{
+				parse_json_bytes_to_infra_value, serialize_infra_value_to_json_string, InfraValue,
+				JsonStreamParser,
+			};
+
+			let events = recorded(|| {
+				assert!(parse_json_bytes_to_infra_value(b"[1, x]").is_err());
+			});
+			assert_eq!(
+				events,
+				[
+					"Enter(\"parse JSON bytes to an Infra value\")",
+					"Enter(\"parse a JSON string to an Infra value\")",
+					"JsonError(Syntax(UnexpectedCharacter { character: 'x', position: 4 }))",
+					"Exit(\"parse a JSON string to an Infra value\")",
+					"Exit(\"parse JSON bytes to an Infra value\")",
+				]
+			);
+
+			let events = recorded(|| {
+				let mut parser = JsonStreamParser::new();
+				parser.feed(b"[1").unwrap();
+				assert!(parser.finish().is_err());
+			});
+			assert_eq!(events, ["JsonError(Syntax(UnexpectedEnd))"]);
+
+			let events = recorded(|| {
+				let _ = serialize_infra_value_to_json_string(&InfraValue::Null);
+			});
+			assert_eq!(
+				events,
+				[
+					"Enter(\"serialize an Infra value to a JSON string\")",
+					"Exit(\"serialize an Infra value to a JSON string\")",
+				]
+			);
+		}
+
+		set_trace_hook(None);
+		let events = recorded(|| {
+			let cursor = CodePointCursor::new("");
+			let _ = cursor.error("parse", "a value");
+		});
+		assert!(events.is_empty());
+	}
+}
@@ -0,0 +1,149 @@
+extern crate alloc;
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+
+use crate::collections::{List, OrderedSet};
+use crate::strings::{split_on_ascii_whitespace, split_on_commas, strictly_split};
+
+/// A set of shared strings, which gives every equal string the same
+/// [`Arc<str>`] handle.
+///
+/// Splitting many strings made of the same few tokens, like the values of
+/// `class` attributes, allocates each distinct token once with the
+/// interner's split methods, instead of once per occurrence. Equal handles
+/// can be compared by pointer with [`Arc::ptr_eq()`].
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use whatwg_infra::Interner;
+///
+/// let mut interner = Interner::new();
+/// let a = interner.split_on_ascii_whitespace_to_set("card featured card");
+/// let b = interner.split_on_ascii_whitespace_to_set("featured");
+///
+/// assert_eq!(a.size(), 2);
+/// assert!(Arc::ptr_eq(&a[1], &b[0]));
+/// assert_eq!(interner.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+	strings: BTreeSet<Arc<str>>,
+}
+
+impl Interner {
+	/// Creates a new, empty interner
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			strings: BTreeSet::new(),
+		}
+	}
+
+	/// Returns the shared handle of a string, adding the string to the
+	/// interner if it isn't in it yet
+	pub fn intern(&mut self, s: &str) -> Arc<str> {
+		if let Some(existing) = self.strings.get(s) {
+			return Arc::clone(existing);
+		}
+		let interned: Arc<str> = Arc::from(s);
+		self.strings.insert(Arc::clone(&interned));
+		interned
+	}
+
+	/// Returns the shared handle of a string if it's in the interner,
+	/// without adding it
+	#[must_use]
+	pub fn get(&self, s: &str) -> Option<Arc<str>> {
+		self.strings.get(s).cloned()
+	}
+
+	/// Returns the number of distinct strings in the interner
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.strings.len()
+	}
+
+	/// Checks if the interner has no strings
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.strings.is_empty()
+	}
+
+	/// Removes every string from the interner. Handles that were already
+	/// returned stay valid, but aren't shared with strings interned later.
+	pub fn clear(&mut self) {
+		self.strings.clear();
+	}
+
+	/// Like [`split_on_ascii_whitespace()`], but returns interned tokens
+	pub fn split_on_ascii_whitespace(&mut self, s: &str) -> List<Arc<str>> {
+		split_on_ascii_whitespace(s)
+			.map(|token| self.intern(token))
+			.collect()
+	}
+
+	/// Like [`split_on_ascii_whitespace()`], but returns the distinct
+	/// interned tokens, in the order they first appear
+	pub fn split_on_ascii_whitespace_to_set(&mut self, s: &str) -> OrderedSet<Arc<str>> {
+		split_on_ascii_whitespace(s)
+			.map(|token| self.intern(token))
+			.collect()
+	}
+
+	/// Like [`strictly_split()`], but returns interned tokens
+	pub fn strictly_split(&mut self, s: &str, delimiter: char) -> List<Arc<str>> {
+		strictly_split(s, delimiter)
+			.map(|token| self.intern(token))
+			.collect()
+	}
+
+	/// Like [`split_on_commas()`], but returns interned tokens
+	pub fn split_on_commas(&mut self, s: &str) -> List<Arc<str>> {
+		split_on_commas(s).map(|token| self.intern(token)).collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_interner_intern() {
+		let mut interner = Interner::new();
+		assert!(interner.is_empty());
+		assert_eq!(interner.get("a"), None);
+
+		let a = interner.intern("a");
+		assert!(Arc::ptr_eq(&a, &interner.intern("a")));
+		assert!(Arc::ptr_eq(&a, &interner.get("a").unwrap()));
+		assert!(!Arc::ptr_eq(&a, &interner.intern("b")));
+		assert_eq!(interner.len(), 2);
+
+		interner.clear();
+		assert!(!Arc::ptr_eq(&a, &interner.intern("a")));
+	}
+
+	#[test]
+	fn test_interner_splitters() {
+		let mut interner = Interner::new();
+		let tokens = interner.split_on_ascii_whitespace(" x y  x ");
+		assert_eq!(
+			tokens.as_slice(),
+			&[Arc::from("x"), Arc::from("y"), Arc::from("x")]
+		);
+		assert!(Arc::ptr_eq(&tokens[0], &tokens[2]));
+
+		let set = interner.split_on_ascii_whitespace_to_set("y x y");
+		assert_eq!(set.size(), 2);
+		assert!(Arc::ptr_eq(&set[0], &tokens[1]));
+
+		let tokens = interner.strictly_split("x;;z", ';');
+		assert!(Arc::ptr_eq(&tokens[0], &set[1]));
+		assert_eq!(&*tokens[1], "");
+
+		let tokens = interner.split_on_commas(" z , x");
+		assert_eq!(tokens.as_slice(), &[Arc::from("z"), Arc::from("x")]);
+		assert_eq!(interner.len(), 4);
+	}
+}
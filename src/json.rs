@@ -0,0 +1,2686 @@
+extern crate alloc;
+use alloc::collections::BTreeMap;
+use alloc::{string::String, vec::Vec};
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+
+use crate::collections::OrderedMap;
+#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+use crate::instrument::{emit, Span, TraceEvent};
+
+/// A value that can be represented in JSON, as described by the
+/// [WHATWG Infra Standard][whatwg-infra-dfn].
+///
+/// Strings are represented as Rust strings, so lone surrogates that appear
+/// in JSON escape sequences (e.g `"\uD800"`) are replaced with U+FFFD
+/// REPLACEMENT CHARACTER.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#json
+#[derive(Clone)]
+pub enum InfraValue {
+	/// The JSON `null` value
+	Null,
+	/// A boolean, either `true` or `false`
+	Boolean(bool),
+	/// A number
+	Number(InfraNumber),
+	/// A string
+	String(String),
+	/// A list of Infra values
+	List(Vec<InfraValue>),
+	/// An ordered map of string keys to Infra values
+	Map(OrderedMap<String, InfraValue>),
+}
+
+impl InfraValue {
+	/// Returns `true` if the value is [`InfraValue::Null`]
+	#[must_use]
+	pub fn is_null(&self) -> bool {
+		matches!(self, Self::Null)
+	}
+
+	/// Returns the boolean, if the value is a boolean
+	#[must_use]
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Boolean(b) => Some(*b),
+			_ => None,
+		}
+	}
+
+	/// Returns the number, if the value is a number
+	#[must_use]
+	pub fn as_number(&self) -> Option<InfraNumber> {
+		match self {
+			Self::Number(n) => Some(*n),
+			_ => None,
+		}
+	}
+
+	/// Returns the string, if the value is a string
+	#[must_use]
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	/// Returns the items of the list, if the value is a list
+	#[must_use]
+	pub fn as_list(&self) -> Option<&[InfraValue]> {
+		match self {
+			Self::List(list) => Some(list),
+			_ => None,
+		}
+	}
+
+	/// Returns the map, if the value is a map
+	#[must_use]
+	pub fn as_map(&self) -> Option<&OrderedMap<String, InfraValue>> {
+		match self {
+			Self::Map(map) => Some(map),
+			_ => None,
+		}
+	}
+
+	/// Returns a mutable reference to the list, if the value is a list
+	#[must_use]
+	pub fn as_list_mut(&mut self) -> Option<&mut Vec<InfraValue>> {
+		match self {
+			Self::List(list) => Some(list),
+			_ => None,
+		}
+	}
+
+	/// Returns a mutable reference to the map, if the value is a map
+	#[must_use]
+	pub fn as_map_mut(&mut self) -> Option<&mut OrderedMap<String, InfraValue>> {
+		match self {
+			Self::Map(map) => Some(map),
+			_ => None,
+		}
+	}
+
+	/// Returns the value of a map entry with the given key, if the value
+	/// is a map that contains the key
+	#[must_use]
+	pub fn get(&self, key: &str) -> Option<&InfraValue> {
+		self.as_map()?.get(key)
+	}
+
+	/// Returns a nested value by following a path of `/`-separated map
+	/// keys and list indices.
+	///
+	/// An empty path returns the value itself. Like in a JSON Pointer, a
+	/// `~1` in a path segment stands for `/` and a `~0` stands for `~`.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::parse_json_string_to_infra_value;
+	///
+	/// let value = parse_json_string_to_infra_value(r#"{"a": {"b": [true, "cat"]}, "c/d": 1}"#).unwrap();
+	///
+	/// assert_eq!(value.get_path("a/b/1").and_then(|v| v.as_str()), Some("cat"));
+	/// assert_eq!(value.get_path("a/b/0").and_then(|v| v.as_bool()), Some(true));
+	/// assert!(value.get_path("c~1d").is_some());
+	/// assert!(value.get_path("a/b/2").is_none());
+	/// assert!(value.get_path("a/x").is_none());
+	/// ```
+	#[must_use]
+	pub fn get_path(&self, path: &str) -> Option<&InfraValue> {
+		if path.is_empty() {
+			return Some(self);
+		}
+
+		path.split('/').try_fold(self, |value, segment| {
+			let segment = unescape_path_segment(segment);
+			match value {
+				Self::List(list) => list.get(parse_path_index(&segment)?),
+				Self::Map(map) => map.get(&*segment),
+				_ => None,
+			}
+		})
+	}
+
+	/// Returns a mutable reference to a nested value, following a path
+	/// in the same way as [`InfraValue::get_path()`].
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{parse_json_string_to_infra_value, InfraValue};
+	///
+	/// let mut value = parse_json_string_to_infra_value(r#"{"a": [1, 2]}"#).unwrap();
+	/// if let Some(item) = value.get_path_mut("a/1") {
+	///     *item = InfraValue::Null;
+	/// }
+	/// assert!(value.get_path("a/1").map_or(false, |v| v.is_null()));
+	/// ```
+	#[must_use]
+	pub fn get_path_mut(&mut self, path: &str) -> Option<&mut InfraValue> {
+		if path.is_empty() {
+			return Some(self);
+		}
+
+		path.split('/').try_fold(self, |value, segment| {
+			let segment = unescape_path_segment(segment);
+			match value {
+				Self::List(list) => list.get_mut(parse_path_index(&segment)?),
+				Self::Map(map) => map.get_mut(&*segment),
+				_ => None,
+			}
+		})
+	}
+}
+
+/// Replaces the `~1` and `~0` escapes in a path segment with `/` and `~`.
+fn unescape_path_segment(segment: &str) -> alloc::borrow::Cow<'_, str> {
+	if segment.contains('~') {
+		alloc::borrow::Cow::Owned(segment.replace("~1", "/").replace("~0", "~"))
+	} else {
+		alloc::borrow::Cow::Borrowed(segment)
+	}
+}
+
+/// Parses a list index in a path, which must be written without a sign or
+/// leading zeros.
+fn parse_path_index(segment: &str) -> Option<usize> {
+	let is_canonical = !segment.is_empty()
+		&& segment.bytes().all(|b| b.is_ascii_digit())
+		&& (segment == "0" || !segment.starts_with('0'));
+	if is_canonical {
+		segment.parse().ok()
+	} else {
+		None
+	}
+}
+
+/// A JSON number.
+///
+/// The WHATWG Infra Standard represents every number as an IEEE 754
+/// double-precision float, which can't exactly represent integers beyond
+/// 2<sup>53</sup>. Parsing only produces [`InfraNumber::I64`] and
+/// [`InfraNumber::U64`] when opted into with
+/// [`JsonParseOptions::with_preserved_integers()`].
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::InfraNumber;
+///
+/// assert_eq!(InfraNumber::F64(2.5).as_f64(), 2.5);
+/// assert_eq!(InfraNumber::U64(u64::MAX).as_u64(), Some(u64::MAX));
+/// assert_eq!(InfraNumber::I64(-1).as_u64(), None);
+/// assert_eq!(InfraNumber::F64(3.0).as_i64(), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum InfraNumber {
+	/// A signed integer
+	I64(i64),
+	/// An unsigned integer that's too large for [`InfraNumber::I64`]
+	U64(u64),
+	/// An IEEE 754 double-precision float
+	F64(f64),
+}
+
+impl InfraNumber {
+	/// Returns the number as a float, which loses precision for integers
+	/// beyond 2<sup>53</sup>
+	#[must_use]
+	pub fn as_f64(self) -> f64 {
+		match self {
+			Self::I64(n) => n as f64,
+			Self::U64(n) => n as f64,
+			Self::F64(n) => n,
+		}
+	}
+
+	/// Returns the number as an `i64`, if it's an integer that can be
+	/// represented exactly
+	#[must_use]
+	pub fn as_i64(self) -> Option<i64> {
+		match self {
+			Self::I64(n) => Some(n),
+			Self::U64(n) => i64::try_from(n).ok(),
+			Self::F64(n) => float_to_integer(n).and_then(|n| i64::try_from(n).ok()),
+		}
+	}
+
+	/// Returns the number as a `u64`, if it's a non-negative integer that
+	/// can be represented exactly
+	#[must_use]
+	pub fn as_u64(self) -> Option<u64> {
+		match self {
+			Self::I64(n) => u64::try_from(n).ok(),
+			Self::U64(n) => Some(n),
+			Self::F64(n) => float_to_integer(n).and_then(|n| u64::try_from(n).ok()),
+		}
+	}
+}
+
+/// Converts a float to an integer, if it has no fractional part and is in
+/// the range of `i64` or `u64`.
+fn float_to_integer(n: f64) -> Option<i128> {
+	// 2^64 and -2^63 are exactly representable, so these bounds are exact
+	let in_range = (-9_223_372_036_854_775_808.0..18_446_744_073_709_551_616.0).contains(&n);
+	if in_range && n == (n as i128) as f64 {
+		Some(n as i128)
+	} else {
+		None
+	}
+}
+
+impl From<f64> for InfraNumber {
+	fn from(n: f64) -> Self {
+		Self::F64(n)
+	}
+}
+
+impl From<i64> for InfraNumber {
+	fn from(n: i64) -> Self {
+		Self::I64(n)
+	}
+}
+
+impl From<u64> for InfraNumber {
+	fn from(n: u64) -> Self {
+		Self::U64(n)
+	}
+}
+
+impl InfraNumber {
+	/// Returns the number as an exact integer, if it has no fractional part.
+	fn as_i128(self) -> Option<i128> {
+		match self {
+			Self::I64(n) => Some(i128::from(n)),
+			Self::U64(n) => Some(i128::from(n)),
+			Self::F64(n) => float_to_integer(n),
+		}
+	}
+}
+
+/// Compares an integer with a float exactly, ordering NaN after every integer.
+fn cmp_integer_float(i: i128, f: f64) -> Ordering {
+	if f.is_nan() {
+		return Ordering::Less;
+	}
+
+	// `i` is always in the range of `i64` or `u64`, so it's between these bounds
+	if f >= 18_446_744_073_709_551_616.0 {
+		return Ordering::Less;
+	}
+	if f < -9_223_372_036_854_775_808.0 {
+		return Ordering::Greater;
+	}
+
+	// truncating an in-range float gives an integer that converts back exactly
+	let truncated = f as i128;
+	i.cmp(&truncated).then_with(|| {
+		if f > truncated as f64 {
+			Ordering::Less
+		} else if f < truncated as f64 {
+			Ordering::Greater
+		} else {
+			Ordering::Equal
+		}
+	})
+}
+
+/// Numbers are compared by their mathematical value, regardless of their
+/// representation, so `I64(1)`, `U64(1)`, and `F64(1.0)` are equal.
+///
+/// Like the `SameValueZero` operation in ECMAScript, `-0.0` is equal to
+/// `0.0`, and NaN is equal to itself. NaN is ordered after every other number.
+impl PartialEq for InfraNumber {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for InfraNumber {}
+
+impl PartialOrd for InfraNumber {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for InfraNumber {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self.as_i128(), other.as_i128()) {
+			(Some(a), Some(b)) => a.cmp(&b),
+			(Some(a), None) => cmp_integer_float(a, other.as_f64()),
+			(None, Some(b)) => cmp_integer_float(b, self.as_f64()).reverse(),
+			(None, None) => {
+				let (a, b) = (self.as_f64(), other.as_f64());
+				match (a.is_nan(), b.is_nan()) {
+					(true, true) => Ordering::Equal,
+					(true, false) => Ordering::Greater,
+					(false, true) => Ordering::Less,
+					(false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+				}
+			}
+		}
+	}
+}
+
+impl Hash for InfraNumber {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		// equal numbers must hash the same, whatever their representation
+		match self.as_i128() {
+			Some(n) => n.hash(state),
+			None if self.as_f64().is_nan() => f64::NAN.to_bits().hash(state),
+			None => self.as_f64().to_bits().hash(state),
+		}
+	}
+}
+
+impl InfraValue {
+	/// The position of each kind of value in the total ordering
+	fn kind_order(&self) -> u8 {
+		match self {
+			Self::Null => 0,
+			Self::Boolean(_) => 1,
+			Self::Number(_) => 2,
+			Self::String(_) => 3,
+			Self::List(_) => 4,
+			Self::Map(_) => 5,
+		}
+	}
+}
+
+/// Compares two strings by their UTF-16 code units, as in the WHATWG Infra
+/// Standard's code unit less than.
+fn cmp_code_units(a: &str, b: &str) -> Ordering {
+	a.encode_utf16().cmp(b.encode_utf16())
+}
+
+/// Infra values are deeply equal when they're the same kind of value with
+/// equal contents. Numbers are compared as described for [`InfraNumber`],
+/// and maps are only equal when their entries are in the same order.
+impl PartialEq for InfraValue {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Null, Self::Null) => true,
+			(Self::Boolean(a), Self::Boolean(b)) => a == b,
+			(Self::Number(a), Self::Number(b)) => a == b,
+			(Self::String(a), Self::String(b)) => a == b,
+			(Self::List(a), Self::List(b)) => a == b,
+			(Self::Map(a), Self::Map(b)) => a == b,
+			_ => false,
+		}
+	}
+}
+
+impl Eq for InfraValue {}
+
+impl PartialOrd for InfraValue {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// Infra values are totally ordered, first by kind (null, then booleans,
+/// numbers, strings, lists, and maps), and then by their contents.
+///
+/// Strings (including map keys) are ordered by their UTF-16 code units,
+/// and lists and maps are ordered lexicographically by their items or
+/// entries, in order.
+impl Ord for InfraValue {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(Self::Boolean(a), Self::Boolean(b)) => a.cmp(b),
+			(Self::Number(a), Self::Number(b)) => a.cmp(b),
+			(Self::String(a), Self::String(b)) => cmp_code_units(a, b),
+			(Self::List(a), Self::List(b)) => a.cmp(b),
+			(Self::Map(a), Self::Map(b)) => cmp_maps(a, b),
+			_ => self.kind_order().cmp(&other.kind_order()),
+		}
+	}
+}
+
+fn cmp_maps(a: &OrderedMap<String, InfraValue>, b: &OrderedMap<String, InfraValue>) -> Ordering {
+	for ((a_key, a_value), (b_key, b_value)) in a.iter().zip(b) {
+		let ordering = cmp_code_units(a_key, b_key).then_with(|| a_value.cmp(b_value));
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+	}
+	a.size().cmp(&b.size())
+}
+
+impl Hash for InfraValue {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.kind_order().hash(state);
+		match self {
+			Self::Null => {}
+			Self::Boolean(b) => b.hash(state),
+			Self::Number(n) => n.hash(state),
+			Self::String(s) => s.hash(state),
+			Self::List(list) => list.hash(state),
+			Self::Map(map) => map.hash(state),
+		}
+	}
+}
+
+/// Formats the number the same way as `Number::toString()` in ECMAScript.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::InfraNumber;
+///
+/// assert_eq!(InfraNumber::F64(1e21).to_string(), "1e+21");
+/// assert_eq!(InfraNumber::F64(-0.5).to_string(), "-0.5");
+/// assert_eq!(InfraNumber::F64(f64::NAN).to_string(), "NaN");
+/// assert_eq!(InfraNumber::U64(u64::MAX).to_string(), "18446744073709551615");
+/// ```
+impl fmt::Display for InfraNumber {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::I64(n) => n.fmt(f),
+			Self::U64(n) => n.fmt(f),
+			Self::F64(n) => write_js_number(f, *n),
+		}
+	}
+}
+
+/// Formats the value as JSON text. The alternate flag (`{:#}`)
+/// pretty-prints it with an indent of 2 spaces.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parse_json_string_to_infra_value;
+///
+/// let value = parse_json_string_to_infra_value(r#"{ "a": [1, 2] }"#).unwrap();
+/// assert_eq!(format!("{}", value), r#"{"a":[1,2]}"#);
+/// assert_eq!(format!("{:#}", value), "{\n  \"a\": [\n    1,\n    2\n  ]\n}");
+/// ```
+impl fmt::Display for InfraValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let indent = if f.alternate() { 2 } else { 0 };
+		let options = JsonSerializeOptions::new().with_indent(indent);
+		f.write_str(&serialize_infra_value_to_json_string_with_options(
+			self, options,
+		))
+	}
+}
+
+/// Formats the value in the notation of the WHATWG Infra Standard, where
+/// lists are written as `« 1, 2 »` and ordered maps as `«[ "a" → 1 ]»`.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parse_json_string_to_infra_value;
+///
+/// let value = parse_json_string_to_infra_value(r#"{"a": [1, "b"], "c": {}}"#).unwrap();
+/// assert_eq!(format!("{:?}", value), r#"«[ "a" → « 1, "b" », "c" → «[ ]» ]»"#);
+/// ```
+impl fmt::Debug for InfraValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Null => f.write_str("null"),
+			Self::Boolean(b) => b.fmt(f),
+			Self::Number(n) => fmt::Display::fmt(n, f),
+			Self::String(s) => fmt::Debug::fmt(s.as_str(), f),
+			Self::List(list) if list.is_empty() => f.write_str("« »"),
+			Self::List(list) => {
+				f.write_str("« ")?;
+				for (i, item) in list.iter().enumerate() {
+					if i > 0 {
+						f.write_str(", ")?;
+					}
+					item.fmt(f)?;
+				}
+				f.write_str(" »")
+			}
+			Self::Map(map) if map.is_empty() => f.write_str("«[ ]»"),
+			Self::Map(map) => {
+				f.write_str("«[ ")?;
+				for (i, (key, value)) in map.iter().enumerate() {
+					if i > 0 {
+						f.write_str(", ")?;
+					}
+					write!(f, "{:?} → {:?}", key, value)?;
+				}
+				f.write_str(" ]»")
+			}
+		}
+	}
+}
+
+/// An error returned when a string can't be parsed as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonParseError {
+	/// The input ended before a complete JSON value was parsed
+	UnexpectedEnd,
+	/// A code point was found where it isn't allowed by the JSON grammar
+	UnexpectedCharacter {
+		/// The unexpected code point
+		character: char,
+		/// The byte offset of the code point in the input
+		position: usize,
+	},
+	/// A backslash in a string wasn't followed by a valid escape sequence
+	InvalidEscape {
+		/// The byte offset of the backslash in the input
+		position: usize,
+	},
+	/// A key appeared more than once in the same map, and
+	/// [`DuplicateKeyPolicy::Error`] was used
+	DuplicateKey {
+		/// The byte offset of the opening quote of the repeated key
+		position: usize,
+	},
+	/// A list or map was nested more deeply than
+	/// [`JsonParseOptions::max_depth()`] allows
+	DepthLimitExceeded {
+		/// The byte offset of the `[` or `{` that exceeded the limit
+		position: usize,
+	},
+	/// A complete JSON value was followed by non-whitespace code points
+	TrailingCharacters {
+		/// The byte offset of the first trailing code point in the input
+		position: usize,
+	},
+}
+
+impl fmt::Display for JsonParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnexpectedEnd => f.write_str("unexpected end of JSON input"),
+			Self::UnexpectedCharacter {
+				character,
+				position,
+			} => write!(
+				f,
+				"unexpected character {:?} in JSON at position {}",
+				character, position
+			),
+			Self::InvalidEscape { position } => {
+				write!(
+					f,
+					"invalid escape sequence in JSON at position {}",
+					position
+				)
+			}
+			Self::DuplicateKey { position } => {
+				write!(f, "duplicate key in JSON at position {}", position)
+			}
+			Self::DepthLimitExceeded { position } => write!(
+				f,
+				"JSON nesting depth limit exceeded at position {}",
+				position
+			),
+			Self::TrailingCharacters { position } => write!(
+				f,
+				"unexpected non-whitespace character after JSON at position {}",
+				position
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonParseError {}
+
+/// An error returned when a byte sequence can't be parsed as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonBytesParseError {
+	/// The byte sequence isn't valid UTF-8
+	Decode(core::str::Utf8Error),
+	/// The decoded string isn't valid JSON
+	Syntax(JsonParseError),
+}
+
+impl fmt::Display for JsonBytesParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Decode(e) => write!(f, "JSON bytes are not valid UTF-8: {}", e),
+			Self::Syntax(e) => e.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonBytesParseError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Decode(e) => Some(e),
+			Self::Syntax(e) => Some(e),
+		}
+	}
+}
+
+impl From<core::str::Utf8Error> for JsonBytesParseError {
+	fn from(e: core::str::Utf8Error) -> Self {
+		Self::Decode(e)
+	}
+}
+
+impl From<JsonParseError> for JsonBytesParseError {
+	fn from(e: JsonParseError) -> Self {
+		Self::Syntax(e)
+	}
+}
+
+/// How duplicate keys in a JSON map are handled while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeyPolicy {
+	/// The last value for a key wins, keeping the position of the first
+	/// occurrence of the key. This is what `JSON.parse()` does.
+	#[default]
+	LastWins,
+	/// The first value for a key wins, and later values are ignored
+	FirstWins,
+	/// A duplicate key fails with [`JsonParseError::DuplicateKey`]
+	Error,
+}
+
+/// Options for parsing JSON into an [`InfraValue`].
+///
+/// The default options follow the WHATWG Infra Standard.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{DuplicateKeyPolicy, JsonParseOptions};
+///
+/// let options = JsonParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::Error);
+/// assert_eq!(options.duplicate_keys(), DuplicateKeyPolicy::Error);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonParseOptions {
+	duplicate_keys: DuplicateKeyPolicy,
+	preserve_integers: bool,
+	max_depth: usize,
+}
+
+impl Default for JsonParseOptions {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl JsonParseOptions {
+	/// The default maximum number of lists and maps that can be nested
+	/// inside each other
+	pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+	/// Creates the default options, which follow the WHATWG Infra Standard
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			duplicate_keys: DuplicateKeyPolicy::LastWins,
+			preserve_integers: false,
+			max_depth: Self::DEFAULT_MAX_DEPTH,
+		}
+	}
+
+	/// Sets how duplicate keys in a JSON map are handled
+	#[must_use]
+	pub const fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+		self.duplicate_keys = policy;
+		self
+	}
+
+	/// Sets whether numbers written as integers (without a fraction or
+	/// exponent) are parsed as [`InfraNumber::I64`] or [`InfraNumber::U64`]
+	/// when they fit, instead of always as [`InfraNumber::F64`].
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{parse_json_string_to_infra_value_with_options, InfraNumber, InfraValue, JsonParseOptions};
+	///
+	/// let options = JsonParseOptions::new().with_preserved_integers(true);
+	/// assert_eq!(
+	///     parse_json_string_to_infra_value_with_options("9007199254740993", options),
+	///     Ok(InfraValue::Number(InfraNumber::I64(9007199254740993)))
+	/// );
+	/// ```
+	#[must_use]
+	pub const fn with_preserved_integers(mut self, preserve_integers: bool) -> Self {
+		self.preserve_integers = preserve_integers;
+		self
+	}
+
+	/// Sets the maximum number of lists and maps that can be nested inside
+	/// each other. Deeper input fails with
+	/// [`JsonParseError::DepthLimitExceeded`].
+	///
+	/// Parsing itself doesn't recurse, but dropping, comparing, formatting
+	/// and serializing an [`InfraValue`] do, so raising the limit far above
+	/// [`JsonParseOptions::DEFAULT_MAX_DEPTH`] lets untrusted input overflow
+	/// the stack.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{parse_json_string_to_infra_value_with_options, JsonParseError, JsonParseOptions};
+	///
+	/// let options = JsonParseOptions::new().with_max_depth(2);
+	/// assert!(parse_json_string_to_infra_value_with_options("[[1]]", options).is_ok());
+	/// assert_eq!(
+	///     parse_json_string_to_infra_value_with_options("[[[1]]]", options),
+	///     Err(JsonParseError::DepthLimitExceeded { position: 2 })
+	/// );
+	/// ```
+	#[must_use]
+	pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+		self.max_depth = max_depth;
+		self
+	}
+
+	/// Returns how duplicate keys in a JSON map are handled
+	#[must_use]
+	pub const fn duplicate_keys(&self) -> DuplicateKeyPolicy {
+		self.duplicate_keys
+	}
+
+	/// Returns whether integers are parsed without converting them to floats
+	#[must_use]
+	pub const fn preserved_integers(&self) -> bool {
+		self.preserve_integers
+	}
+
+	/// Returns the maximum number of lists and maps that can be nested
+	/// inside each other
+	#[must_use]
+	pub const fn max_depth(&self) -> usize {
+		self.max_depth
+	}
+}
+
+/// Parses a string of JSON text into an [`InfraValue`].
+///
+/// Like `JSON.parse()`, when a map contains duplicate keys the last value
+/// wins, and keys that are array indices (e.g `"0"`, `"42"`) are ordered
+/// before all other keys, in ascending numeric order.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#parse-a-json-string-to-an-infra-value
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{parse_json_string_to_infra_value, InfraNumber, InfraValue, OrderedMap};
+///
+/// let mut map = OrderedMap::new();
+/// map.set(
+///     String::from("a"),
+///     InfraValue::List(vec![
+///         InfraValue::Number(InfraNumber::F64(1.0)),
+///         InfraValue::Boolean(true),
+///         InfraValue::Null,
+///     ]),
+/// );
+///
+/// let value = parse_json_string_to_infra_value(r#"{"a": [1, true, null]}"#);
+/// assert_eq!(value, Ok(InfraValue::Map(map)));
+/// ```
+pub fn parse_json_string_to_infra_value(s: &str) -> Result<InfraValue, JsonParseError> {
+	parse_json_string_to_infra_value_with_options(s, JsonParseOptions::new())
+}
+
+/// Parses a string of JSON text into an [`InfraValue`], with the given options.
+///
+/// See also: [`parse_json_string_to_infra_value()`]
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{
+///     parse_json_string_to_infra_value_with_options,
+///     DuplicateKeyPolicy,
+///     InfraNumber,
+///     InfraValue,
+///     JsonParseError,
+///     JsonParseOptions,
+///     OrderedMap,
+/// };
+///
+/// let json = r#"{"a": 1, "a": 2}"#;
+///
+/// let mut map = OrderedMap::new();
+/// map.set(String::from("a"), InfraValue::Number(InfraNumber::F64(1.0)));
+///
+/// let first_wins = JsonParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::FirstWins);
+/// assert_eq!(
+///     parse_json_string_to_infra_value_with_options(json, first_wins),
+///     Ok(InfraValue::Map(map))
+/// );
+///
+/// let error = JsonParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::Error);
+/// assert_eq!(
+///     parse_json_string_to_infra_value_with_options(json, error),
+///     Err(JsonParseError::DuplicateKey { position: 9 })
+/// );
+/// ```
+pub fn parse_json_string_to_infra_value_with_options(
+	s: &str,
+	options: JsonParseOptions,
+) -> Result<InfraValue, JsonParseError> {
+	#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+	let _span = Span::enter("parse a JSON string to an Infra value");
+
+	let mut parser = JsonStreamParser::with_options(options);
+	let result = parser.feed_str(s).and_then(|()| parser.finish_str());
+	#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+	if let Err(e) = result {
+		emit(TraceEvent::JsonError(&e.into()));
+	}
+	result
+}
+
+/// Decodes a byte sequence as UTF-8 and parses the result into an [`InfraValue`].
+///
+/// A leading UTF-8 byte order mark is ignored, as in UTF-8 decode. Unlike
+/// UTF-8 decode, invalid UTF-8 is reported as [`JsonBytesParseError::Decode`]
+/// rather than replaced with U+FFFD REPLACEMENT CHARACTER, so that decoding
+/// failures can be told apart from JSON syntax errors.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#parse-json-bytes-to-an-infra-value
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{parse_json_bytes_to_infra_value, InfraValue, JsonBytesParseError};
+///
+/// let value = parse_json_bytes_to_infra_value(b"\xEF\xBB\xBF\"cat\"");
+/// assert_eq!(value, Ok(InfraValue::String(String::from("cat"))));
+///
+/// let invalid = parse_json_bytes_to_infra_value(b"\"\xFF\"");
+/// assert!(matches!(invalid, Err(JsonBytesParseError::Decode(_))));
+/// ```
+pub fn parse_json_bytes_to_infra_value(bytes: &[u8]) -> Result<InfraValue, JsonBytesParseError> {
+	parse_json_bytes_to_infra_value_with_options(bytes, JsonParseOptions::new())
+}
+
+/// Decodes a byte sequence as UTF-8 and parses the result into an
+/// [`InfraValue`], with the given options.
+///
+/// See also: [`parse_json_bytes_to_infra_value()`]
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{
+///     parse_json_bytes_to_infra_value_with_options,
+///     DuplicateKeyPolicy,
+///     JsonParseOptions,
+/// };
+///
+/// let options = JsonParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::Error);
+/// assert!(parse_json_bytes_to_infra_value_with_options(b"{\"a\":1,\"a\":1}", options).is_err());
+/// ```
+pub fn parse_json_bytes_to_infra_value_with_options(
+	bytes: &[u8],
+	options: JsonParseOptions,
+) -> Result<InfraValue, JsonBytesParseError> {
+	#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+	let _span = Span::enter("parse JSON bytes to an Infra value");
+
+	let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+	let s = core::str::from_utf8(bytes).map_err(|e| {
+		let e = JsonBytesParseError::from(e);
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		emit(TraceEvent::JsonError(&e));
+		e
+	})?;
+	Ok(parse_json_string_to_infra_value_with_options(s, options)?)
+}
+
+/// Options for serializing an [`InfraValue`] into JSON.
+///
+/// The default options follow the WHATWG Infra Standard, producing the same
+/// output as `JSON.stringify()`.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::JsonSerializeOptions;
+///
+/// let options = JsonSerializeOptions::new().with_sorted_keys(true).with_indent(4);
+/// assert!(options.sorted_keys());
+/// assert!(!options.ascii_only());
+/// assert_eq!(options.indent(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JsonSerializeOptions {
+	sort_keys: bool,
+	ascii_only: bool,
+	indent: usize,
+}
+
+impl JsonSerializeOptions {
+	/// Creates the default options, which follow the WHATWG Infra Standard
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			sort_keys: false,
+			ascii_only: false,
+			indent: 0,
+		}
+	}
+
+	/// Sets whether map keys are sorted in ascending order of their UTF-16
+	/// code units, instead of keeping the map's order
+	#[must_use]
+	pub const fn with_sorted_keys(mut self, sort_keys: bool) -> Self {
+		self.sort_keys = sort_keys;
+		self
+	}
+
+	/// Sets whether code points outside of ASCII are escaped as `\uXXXX`
+	/// sequences (using surrogate pairs where needed)
+	#[must_use]
+	pub const fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+		self.ascii_only = ascii_only;
+		self
+	}
+
+	/// Sets the number of spaces used to indent each nesting level. An
+	/// indent of `0` produces compact output without any newlines, like
+	/// `JSON.stringify(value, null, indent)`. Like `JSON.stringify()`, an
+	/// indent of more than 10 spaces is clamped to 10.
+	#[must_use]
+	pub const fn with_indent(mut self, indent: usize) -> Self {
+		self.indent = if indent > 10 { 10 } else { indent };
+		self
+	}
+
+	/// Returns whether map keys are sorted by their UTF-16 code units
+	#[must_use]
+	pub const fn sorted_keys(&self) -> bool {
+		self.sort_keys
+	}
+
+	/// Returns whether code points outside of ASCII are escaped
+	#[must_use]
+	pub const fn ascii_only(&self) -> bool {
+		self.ascii_only
+	}
+
+	/// Returns the number of spaces used to indent each nesting level
+	#[must_use]
+	pub const fn indent(&self) -> usize {
+		self.indent
+	}
+}
+
+/// Serializes an [`InfraValue`] into a string of JSON text.
+///
+/// The output matches `JSON.stringify()`: it contains no insignificant
+/// whitespace, numbers are formatted like JavaScript numbers, and
+/// non-finite numbers are serialized as `null`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#serialize-an-infra-value-to-a-json-string
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{serialize_infra_value_to_json_string, InfraNumber, InfraValue, OrderedMap};
+///
+/// let mut map = OrderedMap::new();
+/// map.set(String::from("name"), InfraValue::String(String::from("cat")));
+/// map.set(String::from("lives"), InfraValue::Number(InfraNumber::F64(9.0)));
+///
+/// let value = InfraValue::Map(map);
+/// assert_eq!(
+///     serialize_infra_value_to_json_string(&value),
+///     String::from(r#"{"name":"cat","lives":9}"#)
+/// );
+/// ```
+#[must_use]
+pub fn serialize_infra_value_to_json_string(value: &InfraValue) -> String {
+	serialize_infra_value_to_json_string_with_options(value, JsonSerializeOptions::new())
+}
+
+/// Serializes an [`InfraValue`] into a string of JSON text, with the given options.
+///
+/// See also: [`serialize_infra_value_to_json_string()`]
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{
+///     serialize_infra_value_to_json_string_with_options,
+///     InfraValue,
+///     JsonSerializeOptions,
+///     OrderedMap,
+/// };
+///
+/// let mut map = OrderedMap::new();
+/// map.set(String::from("b"), InfraValue::String(String::from("é")));
+/// map.set(String::from("a"), InfraValue::List(vec![InfraValue::Null]));
+///
+/// let value = InfraValue::Map(map);
+/// let options = JsonSerializeOptions::new()
+///     .with_sorted_keys(true)
+///     .with_ascii_only(true)
+///     .with_indent(2);
+///
+/// assert_eq!(
+///     serialize_infra_value_to_json_string_with_options(&value, options),
+///     String::from("{\n  \"a\": [\n    null\n  ],\n  \"b\": \"\\u00e9\"\n}")
+/// );
+/// ```
+#[must_use]
+pub fn serialize_infra_value_to_json_string_with_options(
+	value: &InfraValue,
+	options: JsonSerializeOptions,
+) -> String {
+	#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+	let _span = Span::enter("serialize an Infra value to a JSON string");
+
+	let mut serializer = Serializer::new(options);
+	serializer.write_value(value);
+	serializer.out
+}
+
+/// Serializes an [`InfraValue`] into JSON text, encoded as UTF-8 bytes.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#serialize-an-infra-value-to-json-bytes
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{serialize_infra_value_to_json_bytes, InfraValue};
+///
+/// let value = InfraValue::List(vec![InfraValue::String(String::from("é"))]);
+/// assert_eq!(serialize_infra_value_to_json_bytes(&value), b"[\"\xC3\xA9\"]".to_vec());
+/// ```
+#[must_use]
+pub fn serialize_infra_value_to_json_bytes(value: &InfraValue) -> Vec<u8> {
+	serialize_infra_value_to_json_string(value).into_bytes()
+}
+
+/// Serializes an [`InfraValue`] into JSON text encoded as UTF-8 bytes,
+/// with the given options.
+///
+/// See also: [`serialize_infra_value_to_json_bytes()`]
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{serialize_infra_value_to_json_bytes_with_options, InfraValue, JsonSerializeOptions};
+///
+/// let value = InfraValue::String(String::from("é"));
+/// let options = JsonSerializeOptions::new().with_ascii_only(true);
+/// assert_eq!(
+///     serialize_infra_value_to_json_bytes_with_options(&value, options),
+///     b"\"\\u00e9\"".to_vec()
+/// );
+/// ```
+#[must_use]
+pub fn serialize_infra_value_to_json_bytes_with_options(
+	value: &InfraValue,
+	options: JsonSerializeOptions,
+) -> Vec<u8> {
+	serialize_infra_value_to_json_string_with_options(value, options).into_bytes()
+}
+
+/// A push-based JSON parser that produces an [`InfraValue`] from chunks of
+/// UTF-8 bytes, without buffering the whole input first.
+///
+/// Chunks may be split anywhere, including inside a token or a multi-byte
+/// UTF-8 sequence. The result is the same as calling
+/// [`parse_json_bytes_to_infra_value_with_options()`] on the concatenated
+/// chunks, except that the offsets in a [`JsonBytesParseError::Decode`] error
+/// are relative to the chunk that contained the invalid byte sequence.
+///
+/// Once an error has been returned, every later call returns the same error.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{InfraValue, JsonStreamParser};
+///
+/// let mut parser = JsonStreamParser::new();
+/// parser.feed(b"[\"caf\xC3").unwrap();
+/// parser.feed(b"\xA9\", tr").unwrap();
+/// parser.feed(b"ue]").unwrap();
+///
+/// assert_eq!(
+///     parser.finish(),
+///     Ok(InfraValue::List(vec![
+///         InfraValue::String(String::from("café")),
+///         InfraValue::Boolean(true),
+///     ]))
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonStreamParser {
+	options: JsonParseOptions,
+	state: StreamState,
+	stack: Vec<Frame>,
+	result: Option<InfraValue>,
+	position: usize,
+	error: Option<JsonBytesParseError>,
+	seen_input: bool,
+	pending: [u8; 4],
+	pending_len: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Frame {
+	List(Vec<InfraValue>),
+	Map {
+		entries: Vec<(String, InfraValue)>,
+		key: Option<(String, usize)>,
+		/// The position of each key in `entries`, so that finding a
+		/// duplicate key doesn't scan the entries before it
+		index: BTreeMap<String, usize>,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum StreamState {
+	Value {
+		allow_close: bool,
+	},
+	Key {
+		allow_close: bool,
+	},
+	Colon,
+	AfterValue,
+	Done,
+	Literal {
+		literal: &'static str,
+		matched: usize,
+	},
+	Number {
+		state: NumberState,
+		text: String,
+	},
+	String(StringState),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct StringState {
+	text: String,
+	is_key: bool,
+	start: usize,
+	escape: EscapeState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EscapeState {
+	None,
+	Backslash {
+		start: usize,
+	},
+	Unicode {
+		start: usize,
+		value: u16,
+		digits: u8,
+		leading: Option<u16>,
+	},
+	AfterLeading {
+		leading: u16,
+	},
+	AfterLeadingBackslash {
+		leading: u16,
+		start: usize,
+	},
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberState {
+	Minus,
+	Zero,
+	Integer,
+	Dot,
+	Fraction,
+	Exponent,
+	ExponentSign,
+	ExponentDigits,
+}
+
+impl NumberState {
+	fn next(self, b: u8) -> Option<Self> {
+		match (self, b) {
+			(Self::Minus, b'0') => Some(Self::Zero),
+			(Self::Minus, b'1'..=b'9') => Some(Self::Integer),
+			(Self::Integer, b'0'..=b'9') => Some(Self::Integer),
+			(Self::Zero | Self::Integer, b'.') => Some(Self::Dot),
+			(Self::Dot | Self::Fraction, b'0'..=b'9') => Some(Self::Fraction),
+			(Self::Zero | Self::Integer | Self::Fraction, b'e' | b'E') => Some(Self::Exponent),
+			(Self::Exponent, b'+' | b'-') => Some(Self::ExponentSign),
+			(Self::Exponent | Self::ExponentSign | Self::ExponentDigits, b'0'..=b'9') => {
+				Some(Self::ExponentDigits)
+			}
+			_ => None,
+		}
+	}
+
+	fn is_complete(self) -> bool {
+		matches!(
+			self,
+			Self::Zero | Self::Integer | Self::Fraction | Self::ExponentDigits
+		)
+	}
+}
+
+fn is_json_whitespace(b: u8) -> bool {
+	matches!(b, b'\t' | b'\n' | b'\r' | b' ')
+}
+
+impl Default for JsonStreamParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl JsonStreamParser {
+	/// Creates a parser with the default options, which follow the
+	/// WHATWG Infra Standard
+	#[must_use]
+	pub fn new() -> Self {
+		Self::with_options(JsonParseOptions::new())
+	}
+
+	/// Creates a parser with the given options
+	#[must_use]
+	pub fn with_options(options: JsonParseOptions) -> Self {
+		Self {
+			options,
+			state: StreamState::Value { allow_close: false },
+			stack: Vec::new(),
+			result: None,
+			position: 0,
+			error: None,
+			seen_input: false,
+			pending: [0; 4],
+			pending_len: 0,
+		}
+	}
+
+	/// Parses the next chunk of UTF-8 bytes.
+	///
+	/// A UTF-8 byte order mark at the very start of the input is ignored.
+	///
+	/// # Errors
+	/// Returns an error as soon as the input so far can't be the start of
+	/// valid JSON, or isn't valid UTF-8.
+	pub fn feed(&mut self, mut bytes: &[u8]) -> Result<(), JsonBytesParseError> {
+		if let Some(e) = self.error {
+			return Err(e);
+		}
+
+		// complete a code point that was split by the previous chunk
+		while self.pending_len > 0 && !bytes.is_empty() {
+			self.pending[self.pending_len] = bytes[0];
+			self.pending_len += 1;
+			bytes = &bytes[1..];
+
+			let pending = self.pending;
+			match core::str::from_utf8(&pending[..self.pending_len]) {
+				Ok(s) => {
+					self.pending_len = 0;
+					self.feed_decoded(s)?;
+				}
+				Err(e) if e.error_len().is_some() => return Err(self.fail(e.into())),
+				Err(_) => {}
+			}
+		}
+
+		match core::str::from_utf8(bytes) {
+			Ok(s) => self.feed_decoded(s),
+			Err(e) if e.error_len().is_none() => {
+				let (valid, rest) = bytes.split_at(e.valid_up_to());
+				// `valid_up_to()` is always a code point boundary
+				self.feed_decoded(core::str::from_utf8(valid).unwrap_or_default())?;
+				self.pending[..rest.len()].copy_from_slice(rest);
+				self.pending_len = rest.len();
+				Ok(())
+			}
+			Err(e) => Err(self.fail(e.into())),
+		}
+	}
+
+	/// Finishes parsing, returning the parsed value.
+	///
+	/// # Errors
+	/// Returns an error if the input ended before a complete JSON value,
+	/// or ended in the middle of a UTF-8 sequence.
+	pub fn finish(mut self) -> Result<InfraValue, JsonBytesParseError> {
+		if let Some(e) = self.error {
+			return Err(e);
+		}
+		if self.pending_len > 0 {
+			if let Err(e) = core::str::from_utf8(&self.pending[..self.pending_len]) {
+				return Err(self.fail(e.into()));
+			}
+		}
+
+		let result = self.finish_str().map_err(JsonBytesParseError::from);
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		if let Err(e) = &result {
+			emit(TraceEvent::JsonError(e));
+		}
+		result
+	}
+
+	fn fail(&mut self, e: JsonBytesParseError) -> JsonBytesParseError {
+		#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+		emit(TraceEvent::JsonError(&e));
+		self.error = Some(e);
+		e
+	}
+
+	fn feed_decoded(&mut self, mut s: &str) -> Result<(), JsonBytesParseError> {
+		if !self.seen_input && !s.is_empty() {
+			self.seen_input = true;
+			s = s.strip_prefix('\u{FEFF}').unwrap_or(s);
+		}
+
+		self.feed_str(s).map_err(|e| self.fail(e.into()))
+	}
+
+	fn feed_str(&mut self, s: &str) -> Result<(), JsonParseError> {
+		let bytes = s.as_bytes();
+		let mut i = 0;
+
+		while i < bytes.len() {
+			let b = bytes[i];
+			let position = self.position + i;
+			let start = i;
+			let unexpected = || JsonParseError::UnexpectedCharacter {
+				character: s[start..].chars().next().unwrap_or_default(),
+				position,
+			};
+
+			match &mut self.state {
+				StreamState::Value { .. }
+				| StreamState::Key { .. }
+				| StreamState::Colon
+				| StreamState::AfterValue
+				| StreamState::Done
+					if is_json_whitespace(b) =>
+				{
+					i += 1;
+				}
+				StreamState::Value { allow_close } => {
+					let allow_close = *allow_close;
+					i += 1;
+					self.state = match b {
+						b'n' => StreamState::Literal {
+							literal: "null",
+							matched: 1,
+						},
+						b't' => StreamState::Literal {
+							literal: "true",
+							matched: 1,
+						},
+						b'f' => StreamState::Literal {
+							literal: "false",
+							matched: 1,
+						},
+						b'"' => StreamState::String(StringState {
+							text: String::new(),
+							is_key: false,
+							start: position,
+							escape: EscapeState::None,
+						}),
+						b'[' | b'{' if self.stack.len() >= self.options.max_depth => {
+							return Err(JsonParseError::DepthLimitExceeded { position });
+						}
+						b'[' => {
+							self.stack.push(Frame::List(Vec::new()));
+							StreamState::Value { allow_close: true }
+						}
+						b'{' => {
+							self.stack.push(Frame::Map {
+								entries: Vec::new(),
+								key: None,
+								index: BTreeMap::new(),
+							});
+							StreamState::Key { allow_close: true }
+						}
+						b'-' | b'0'..=b'9' => StreamState::Number {
+							state: NumberState::Minus.next(b).unwrap_or(NumberState::Minus),
+							text: String::from(char::from(b)),
+						},
+						b']' if allow_close => {
+							self.close()?;
+							continue;
+						}
+						_ => return Err(unexpected()),
+					};
+				}
+				StreamState::Key { allow_close } => {
+					let allow_close = *allow_close;
+					i += 1;
+					match b {
+						b'"' => {
+							self.state = StreamState::String(StringState {
+								text: String::new(),
+								is_key: true,
+								start: position,
+								escape: EscapeState::None,
+							});
+						}
+						b'}' if allow_close => self.close()?,
+						_ => return Err(unexpected()),
+					}
+				}
+				StreamState::Colon => {
+					if b != b':' {
+						return Err(unexpected());
+					}
+					i += 1;
+					self.state = StreamState::Value { allow_close: false };
+				}
+				StreamState::AfterValue => {
+					i += 1;
+					match (b, self.stack.last()) {
+						(b',', Some(Frame::List(_))) => {
+							self.state = StreamState::Value { allow_close: false };
+						}
+						(b',', Some(Frame::Map { .. })) => {
+							self.state = StreamState::Key { allow_close: false };
+						}
+						(b']', Some(Frame::List(_))) | (b'}', Some(Frame::Map { .. })) => {
+							self.close()?;
+						}
+						_ => return Err(unexpected()),
+					}
+				}
+				StreamState::Done => {
+					return Err(JsonParseError::TrailingCharacters { position });
+				}
+				StreamState::Literal { literal, matched } => {
+					if literal.as_bytes()[*matched] != b {
+						return Err(unexpected());
+					}
+					i += 1;
+					*matched += 1;
+					if *matched == literal.len() {
+						let value = match *literal {
+							"null" => InfraValue::Null,
+							"true" => InfraValue::Boolean(true),
+							_ => InfraValue::Boolean(false),
+						};
+						self.push_value(value)?;
+					}
+				}
+				StreamState::Number { state, text } => match state.next(b) {
+					Some(next) => {
+						*state = next;
+						text.push(char::from(b));
+						i += 1;
+					}
+					None if state.is_complete() => self.finish_number()?,
+					None => return Err(unexpected()),
+				},
+				StreamState::String(string) => match string.escape {
+					EscapeState::None => {
+						let rest = &bytes[i..];
+						let run = rest
+							.iter()
+							.position(|&b| b == b'"' || b == b'\\' || b < 0x20)
+							.unwrap_or(rest.len());
+						string.text.push_str(&s[i..i + run]);
+						i += run;
+
+						match bytes.get(i) {
+							Some(b'"') => {
+								i += 1;
+								self.finish_string()?;
+							}
+							Some(b'\\') => {
+								string.escape = EscapeState::Backslash {
+									start: self.position + i,
+								};
+								i += 1;
+							}
+							Some(_) => {
+								return Err(JsonParseError::UnexpectedCharacter {
+									character: char::from(bytes[i]),
+									position: self.position + i,
+								})
+							}
+							None => {}
+						}
+					}
+					EscapeState::Backslash { start } => {
+						let escaped = match b {
+							b'"' => '"',
+							b'\\' => '\\',
+							b'/' => '/',
+							b'b' => '\u{0008}',
+							b'f' => '\u{000C}',
+							b'n' => '\u{000A}',
+							b'r' => '\u{000D}',
+							b't' => '\u{0009}',
+							b'u' => {
+								i += 1;
+								string.escape = EscapeState::Unicode {
+									start,
+									value: 0,
+									digits: 0,
+									leading: None,
+								};
+								continue;
+							}
+							_ => return Err(JsonParseError::InvalidEscape { position: start }),
+						};
+						i += 1;
+						string.text.push(escaped);
+						string.escape = EscapeState::None;
+					}
+					EscapeState::Unicode {
+						start,
+						value,
+						digits,
+						leading,
+					} => {
+						let digit = char::from(b)
+							.to_digit(16)
+							.ok_or(JsonParseError::InvalidEscape { position: start })?;
+						i += 1;
+						let value = (value << 4) | digit as u16;
+						string.escape = if digits < 3 {
+							EscapeState::Unicode {
+								start,
+								value,
+								digits: digits + 1,
+								leading,
+							}
+						} else {
+							string.push_code_unit(leading, value)
+						};
+					}
+					EscapeState::AfterLeading { leading } => {
+						if b == b'\\' {
+							string.escape = EscapeState::AfterLeadingBackslash {
+								leading,
+								start: position,
+							};
+							i += 1;
+						} else {
+							// a lone leading surrogate, so reprocess this byte normally
+							string.text.push('\u{FFFD}');
+							string.escape = EscapeState::None;
+						}
+					}
+					EscapeState::AfterLeadingBackslash { leading, start } => {
+						if b == b'u' {
+							string.escape = EscapeState::Unicode {
+								start,
+								value: 0,
+								digits: 0,
+								leading: Some(leading),
+							};
+							i += 1;
+						} else {
+							string.text.push('\u{FFFD}');
+							string.escape = EscapeState::Backslash { start };
+						}
+					}
+				},
+			}
+		}
+
+		self.position += bytes.len();
+		Ok(())
+	}
+
+	fn finish_str(&mut self) -> Result<InfraValue, JsonParseError> {
+		if let StreamState::Number { state, .. } = &self.state {
+			if state.is_complete() {
+				self.finish_number()?;
+			}
+		}
+
+		match &self.state {
+			StreamState::Done => Ok(self.result.take().unwrap_or(InfraValue::Null)),
+			StreamState::String(StringState {
+				escape:
+					EscapeState::Backslash { start }
+					| EscapeState::Unicode { start, .. }
+					| EscapeState::AfterLeadingBackslash { start, .. },
+				..
+			}) => Err(JsonParseError::InvalidEscape { position: *start }),
+			_ => Err(JsonParseError::UnexpectedEnd),
+		}
+	}
+
+	fn finish_number(&mut self) -> Result<(), JsonParseError> {
+		let (state, text) = match &mut self.state {
+			StreamState::Number { state, text } => (*state, core::mem::take(text)),
+			_ => (NumberState::Zero, String::new()),
+		};
+
+		// `-0` is kept as a float, since an integer can't keep its sign
+		let is_integer = matches!(state, NumberState::Zero | NumberState::Integer) && text != "-0";
+		let integer = if self.options.preserve_integers && is_integer {
+			text.parse::<i64>()
+				.map(InfraNumber::I64)
+				.or_else(|_| text.parse::<u64>().map(InfraNumber::U64))
+				.ok()
+		} else {
+			None
+		};
+
+		// the JSON number grammar is a subset of what `f64` accepts, so this can't fail
+		let number = integer.unwrap_or_else(|| InfraNumber::F64(text.parse().unwrap_or_default()));
+		self.push_value(InfraValue::Number(number))
+	}
+
+	fn finish_string(&mut self) -> Result<(), JsonParseError> {
+		let state = core::mem::replace(&mut self.state, StreamState::Colon);
+		let string = match state {
+			StreamState::String(string) => string,
+			_ => return Ok(()),
+		};
+
+		if string.is_key {
+			if let Some(Frame::Map { key, .. }) = self.stack.last_mut() {
+				*key = Some((string.text, string.start));
+			}
+			Ok(())
+		} else {
+			self.push_value(InfraValue::String(string.text))
+		}
+	}
+
+	fn push_value(&mut self, value: InfraValue) -> Result<(), JsonParseError> {
+		self.state = StreamState::AfterValue;
+		match self.stack.last_mut() {
+			None => {
+				self.result = Some(value);
+				self.state = StreamState::Done;
+			}
+			Some(Frame::List(items)) => items.push(value),
+			Some(Frame::Map {
+				entries,
+				key,
+				index,
+			}) => {
+				let (key, key_position) = key.take().unwrap_or_default();
+				match index.get(&key) {
+					Some(&i) => match self.options.duplicate_keys {
+						DuplicateKeyPolicy::LastWins => entries[i].1 = value,
+						DuplicateKeyPolicy::FirstWins => {}
+						DuplicateKeyPolicy::Error => {
+							return Err(JsonParseError::DuplicateKey {
+								position: key_position,
+							})
+						}
+					},
+					None => {
+						index.insert(key.clone(), entries.len());
+						entries.push((key, value));
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn close(&mut self) -> Result<(), JsonParseError> {
+		let value = match self.stack.pop() {
+			Some(Frame::List(items)) => InfraValue::List(items),
+			Some(Frame::Map { entries, .. }) => {
+				// JavaScript objects enumerate array index keys first, in ascending order
+				let (mut indices, rest): (Vec<_>, Vec<_>) = entries
+					.into_iter()
+					.partition(|(k, _)| array_index(k).is_some());
+				indices.sort_by_key(|(k, _)| array_index(k));
+				indices.extend(rest);
+				InfraValue::Map(OrderedMap::from_unique_entries(indices))
+			}
+			None => return Ok(()),
+		};
+		self.push_value(value)
+	}
+}
+
+impl StringState {
+	/// Handles a complete `\uXXXX` escape, returning the next escape state.
+	fn push_code_unit(&mut self, leading: Option<u16>, unit: u16) -> EscapeState {
+		if let Some(leading) = leading {
+			if crate::is_trailing_surrogate_utf16(unit) {
+				let c =
+					0x10000 + ((u32::from(leading) - 0xD800) << 10) + (u32::from(unit) - 0xDC00);
+				self.text.push(char::from_u32(c).unwrap_or('\u{FFFD}'));
+				return EscapeState::None;
+			}
+			self.text.push('\u{FFFD}');
+		}
+
+		if crate::is_leading_surrogate_utf16(unit) {
+			EscapeState::AfterLeading { leading: unit }
+		} else {
+			self.text
+				.push(char::from_u32(u32::from(unit)).unwrap_or('\u{FFFD}'));
+			EscapeState::None
+		}
+	}
+}
+
+/// Returns the numeric value of a key if it's an ECMAScript array index.
+fn array_index(key: &str) -> Option<u32> {
+	let is_canonical = !key.is_empty()
+		&& key.bytes().all(|b| b.is_ascii_digit())
+		&& (key == "0" || !key.starts_with('0'));
+	if !is_canonical {
+		return None;
+	}
+
+	key.parse::<u32>().ok().filter(|&i| i != u32::MAX)
+}
+
+struct Serializer {
+	out: String,
+	options: JsonSerializeOptions,
+	depth: usize,
+}
+
+impl Serializer {
+	fn new(options: JsonSerializeOptions) -> Self {
+		Self {
+			out: String::new(),
+			options,
+			depth: 0,
+		}
+	}
+
+	fn write_newline_indent(&mut self) {
+		if self.options.indent > 0 {
+			self.out.push('\n');
+			let width = self.options.indent * self.depth;
+			self.out.extend(core::iter::repeat(' ').take(width));
+		}
+	}
+
+	fn write_value(&mut self, value: &InfraValue) {
+		match value {
+			InfraValue::Null => self.out.push_str("null"),
+			InfraValue::Boolean(true) => self.out.push_str("true"),
+			InfraValue::Boolean(false) => self.out.push_str("false"),
+			InfraValue::Number(InfraNumber::I64(n)) => {
+				// writing to a `String` never fails
+				let _ = write!(self.out, "{}", n);
+			}
+			InfraValue::Number(InfraNumber::U64(n)) => {
+				let _ = write!(self.out, "{}", n);
+			}
+			InfraValue::Number(InfraNumber::F64(n)) if !n.is_finite() => {
+				self.out.push_str("null");
+			}
+			InfraValue::Number(InfraNumber::F64(n)) => {
+				let _ = write_js_number(&mut self.out, *n);
+			}
+			InfraValue::String(s) => self.write_string(s),
+			InfraValue::List(list) => {
+				if list.is_empty() {
+					self.out.push_str("[]");
+					return;
+				}
+
+				self.out.push('[');
+				self.depth += 1;
+				for (i, item) in list.iter().enumerate() {
+					if i > 0 {
+						self.out.push(',');
+					}
+					self.write_newline_indent();
+					self.write_value(item);
+				}
+				self.depth -= 1;
+				self.write_newline_indent();
+				self.out.push(']');
+			}
+			InfraValue::Map(map) => {
+				if map.is_empty() {
+					self.out.push_str("{}");
+					return;
+				}
+
+				let mut entries: Vec<(&String, &InfraValue)> = map.iter().collect();
+				if self.options.sort_keys {
+					entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+				}
+
+				self.out.push('{');
+				self.depth += 1;
+				for (i, (key, item)) in entries.into_iter().enumerate() {
+					if i > 0 {
+						self.out.push(',');
+					}
+					self.write_newline_indent();
+					self.write_string(key);
+					self.out.push(':');
+					if self.options.indent > 0 {
+						self.out.push(' ');
+					}
+					self.write_value(item);
+				}
+				self.depth -= 1;
+				self.write_newline_indent();
+				self.out.push('}');
+			}
+		}
+	}
+
+	/// Writes a string literal the same way as `QuoteJSONString` in ECMAScript.
+	fn write_string(&mut self, s: &str) {
+		let out = &mut self.out;
+		out.push('"');
+		for c in s.chars() {
+			match c {
+				'\u{0008}' => out.push_str("\\b"),
+				'\u{0009}' => out.push_str("\\t"),
+				'\u{000A}' => out.push_str("\\n"),
+				'\u{000C}' => out.push_str("\\f"),
+				'\u{000D}' => out.push_str("\\r"),
+				'"' => out.push_str("\\\""),
+				'\\' => out.push_str("\\\\"),
+				c if c < '\u{0020}' || (self.options.ascii_only && !c.is_ascii()) => {
+					let mut units = [0u16; 2];
+					for unit in c.encode_utf16(&mut units) {
+						// writing to a `String` never fails
+						let _ = write!(out, "\\u{:04x}", unit);
+					}
+				}
+				c => out.push(c),
+			}
+		}
+		out.push('"');
+	}
+}
+
+/// Writes a number the same way as `Number::toString()` in ECMAScript.
+fn write_js_number<W: Write>(out: &mut W, n: f64) -> fmt::Result {
+	if n.is_nan() {
+		return out.write_str("NaN");
+	}
+	if n == 0.0 {
+		return out.write_char('0');
+	}
+	if n < 0.0 {
+		out.write_char('-')?;
+	}
+	if n.is_infinite() {
+		return out.write_str("Infinity");
+	}
+
+	// `{:e}` produces the shortest digits that round-trip, e.g `1.2345e-7`
+	let mut scientific = String::new();
+	write!(scientific, "{:e}", n.abs())?;
+	let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+	let digits: String = mantissa.chars().filter(|&c| c != '.').collect();
+	let k = digits.len() as i32;
+	let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+
+	if k <= n && n <= 21 {
+		out.write_str(&digits)?;
+		for _ in 0..n - k {
+			out.write_char('0')?;
+		}
+	} else if 0 < n && n <= 21 {
+		out.write_str(&digits[..n as usize])?;
+		out.write_char('.')?;
+		out.write_str(&digits[n as usize..])?;
+	} else if -6 < n && n <= 0 {
+		out.write_str("0.")?;
+		for _ in 0..-n {
+			out.write_char('0')?;
+		}
+		out.write_str(&digits)?;
+	} else {
+		out.write_str(&digits[..1])?;
+		if k > 1 {
+			out.write_char('.')?;
+			out.write_str(&digits[1..])?;
+		}
+		write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs())?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::{format, vec};
+
+	fn s(s: &str) -> String {
+		String::from(s)
+	}
+
+	fn map(entries: Vec<(String, InfraValue)>) -> InfraValue {
+		let mut map = OrderedMap::new();
+		for (key, value) in entries {
+			map.set(key, value);
+		}
+		InfraValue::Map(map)
+	}
+
+	fn n(n: f64) -> InfraValue {
+		InfraValue::Number(InfraNumber::F64(n))
+	}
+
+	#[test]
+	fn test_parse_json_string_literals() {
+		assert_eq!(
+			parse_json_string_to_infra_value("null"),
+			Ok(InfraValue::Null)
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value(" true "),
+			Ok(InfraValue::Boolean(true))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("\n\tfalse\r"),
+			Ok(InfraValue::Boolean(false))
+		);
+	}
+
+	#[test]
+	fn test_parse_json_string_numbers() {
+		assert_eq!(parse_json_string_to_infra_value("-12.5e2"), Ok(n(-1250.0)));
+		assert_eq!(parse_json_string_to_infra_value("0"), Ok(n(0.0)));
+		assert!(parse_json_string_to_infra_value("01").is_err());
+		assert!(parse_json_string_to_infra_value("1.").is_err());
+		assert!(parse_json_string_to_infra_value("-").is_err());
+		assert!(parse_json_string_to_infra_value("+1").is_err());
+	}
+
+	#[test]
+	fn test_parse_json_string_strings() {
+		assert_eq!(
+			parse_json_string_to_infra_value(r#""a\"\\\/\b\f\n\r\té""#),
+			Ok(InfraValue::String(s("a\"\\/\u{8}\u{C}\n\r\té")))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value(r#""🦀""#),
+			Ok(InfraValue::String(s("🦀")))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value(r#""\ud800A""#),
+			Ok(InfraValue::String(s("\u{FFFD}A")))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value(r#""\x""#),
+			Err(JsonParseError::InvalidEscape { position: 1 })
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("\"\n\""),
+			Err(JsonParseError::UnexpectedCharacter {
+				character: '\n',
+				position: 1
+			})
+		);
+	}
+
+	#[test]
+	fn test_parse_json_string_list() {
+		assert_eq!(
+			parse_json_string_to_infra_value("[ ]"),
+			Ok(InfraValue::List(vec![]))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("[1, [\"a\"]]"),
+			Ok(InfraValue::List(vec![
+				n(1.0),
+				InfraValue::List(vec![InfraValue::String(s("a"))]),
+			]))
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("[1,]"),
+			Err(JsonParseError::UnexpectedCharacter {
+				character: ']',
+				position: 3
+			})
+		);
+	}
+
+	#[test]
+	fn test_parse_json_string_map_order() {
+		assert_eq!(
+			parse_json_string_to_infra_value(
+				r#"{"b": 1, "2": 2, "a": 3, "b": 4, "1": 5, "01": 6}"#
+			),
+			Ok(map(vec![
+				(s("1"), n(5.0)),
+				(s("2"), n(2.0)),
+				(s("b"), n(4.0)),
+				(s("a"), n(3.0)),
+				(s("01"), n(6.0)),
+			]))
+		);
+	}
+
+	#[test]
+	fn test_parse_json_string_errors() {
+		assert_eq!(
+			parse_json_string_to_infra_value(""),
+			Err(JsonParseError::UnexpectedEnd)
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("{\"a\" 1}"),
+			Err(JsonParseError::UnexpectedCharacter {
+				character: '1',
+				position: 5
+			})
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value("null null"),
+			Err(JsonParseError::TrailingCharacters { position: 5 })
+		);
+	}
+
+	#[test]
+	fn test_parse_json_bytes() {
+		assert_eq!(
+			parse_json_bytes_to_infra_value(b"[\"\xC3\xA9\"]"),
+			Ok(InfraValue::List(vec![InfraValue::String(s("é"))]))
+		);
+		assert_eq!(
+			parse_json_bytes_to_infra_value(b"\xEF\xBB\xBFnull"),
+			Ok(InfraValue::Null)
+		);
+		assert!(matches!(
+			parse_json_bytes_to_infra_value(b"\xFEnull"),
+			Err(JsonBytesParseError::Decode(_))
+		));
+		assert_eq!(
+			parse_json_bytes_to_infra_value(b"nul"),
+			Err(JsonBytesParseError::Syntax(JsonParseError::UnexpectedEnd))
+		);
+	}
+
+	#[test]
+	fn test_parse_json_duplicate_keys() {
+		let json = r#"{"a": 1, "b": {"c": 2, "c": 3}, "a": 4}"#;
+		let parse = |policy| {
+			let options = JsonParseOptions::new().with_duplicate_keys(policy);
+			parse_json_string_to_infra_value_with_options(json, options)
+		};
+
+		assert_eq!(
+			parse(DuplicateKeyPolicy::LastWins),
+			Ok(map(vec![
+				(s("a"), n(4.0)),
+				(s("b"), map(vec![(s("c"), n(3.0))])),
+			]))
+		);
+		assert_eq!(
+			parse(DuplicateKeyPolicy::FirstWins),
+			Ok(map(vec![
+				(s("a"), n(1.0)),
+				(s("b"), map(vec![(s("c"), n(2.0))])),
+			]))
+		);
+		assert_eq!(
+			parse(DuplicateKeyPolicy::Error),
+			Err(JsonParseError::DuplicateKey { position: 23 })
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value(json),
+			parse(DuplicateKeyPolicy::LastWins)
+		);
+	}
+
+	#[test]
+	fn test_parse_json_many_keys() {
+		// Each key is checked for duplicates without scanning the keys
+		// before it, so this doesn't take quadratic time
+		let count = 100_000;
+		let mut json = String::from("{");
+		for i in 0..count {
+			json.push_str(&alloc::format!("\"k{}\": {}, ", i, i));
+		}
+		json.push_str("\"k0\": -1}");
+
+		let value = parse_json_string_to_infra_value(&json).unwrap();
+		let map = value.as_map().unwrap();
+		assert_eq!(map.size(), count);
+		assert_eq!(map.get_index(0), Some((&s("k0"), &n(-1.0))));
+		for (i, (key, value)) in map.iter().enumerate().skip(1) {
+			assert_eq!(*key, alloc::format!("k{}", i));
+			assert_eq!(*value, n(i as f64));
+		}
+	}
+
+	#[test]
+	fn test_parse_json_duplicate_keys_many() {
+		// Every even key appears a second time after all the distinct keys
+		let count = 10_000;
+		let mut json = String::from("{");
+		for i in 0..count {
+			json.push_str(&alloc::format!("\"k{}\": {}, ", i, i));
+		}
+		let first_duplicate = json.len();
+		for i in (0..count).step_by(2) {
+			json.push_str(&alloc::format!("\"k{}\": {}, ", i, -(i as i64)));
+		}
+		json.push_str("\"end\": 0}");
+		let parse = |policy| {
+			let options = JsonParseOptions::new().with_duplicate_keys(policy);
+			parse_json_string_to_infra_value_with_options(&json, options)
+		};
+
+		for (policy, replaced) in [
+			(DuplicateKeyPolicy::LastWins, true),
+			(DuplicateKeyPolicy::FirstWins, false),
+		] {
+			let value = parse(policy).unwrap();
+			let map = value.as_map().unwrap();
+			assert_eq!(map.size(), count + 1);
+			for (i, (key, value)) in map.iter().take(count).enumerate() {
+				assert_eq!(*key, alloc::format!("k{}", i));
+				let expected = if replaced && i % 2 == 0 {
+					-(i as f64)
+				} else {
+					i as f64
+				};
+				assert_eq!(*value, n(expected));
+			}
+			assert_eq!(map.get_index(count), Some((&s("end"), &n(0.0))));
+		}
+		assert_eq!(
+			parse(DuplicateKeyPolicy::Error),
+			Err(JsonParseError::DuplicateKey {
+				position: first_duplicate
+			})
+		);
+	}
+
+	fn parse_chunked(chunks: &[&[u8]]) -> Result<InfraValue, JsonBytesParseError> {
+		let mut parser = JsonStreamParser::new();
+		for chunk in chunks {
+			parser.feed(chunk)?;
+		}
+		parser.finish()
+	}
+
+	#[test]
+	fn test_stream_parser_every_split() {
+		let inputs: [&[u8]; 16] = [
+			b"\xEF\xBB\xBF{\"caf\xC3\xA9\": [1, -2.5e+3, true, false, null]}",
+			b"  \"\\ud83e\\udd80 \\ud800\\n \\ud800\\ud800\\udc00\"  ",
+			b"{\"1\": 0, \"a\": {}, \"0\": [[]], \"a\": \"\xF0\x9F\xA6\x80\"}",
+			b"[0, 12, 0.5, 1E2]",
+			b"-0",
+			b"01",
+			b"[01]",
+			b"[1,]",
+			b"{\"a\" 1}",
+			b"\"\\x\"",
+			b"\"\\u12\"",
+			b"\"\\ud800\\",
+			b"\"\n\"",
+			b"nul",
+			b"null x",
+			b"[\"a\", tru]",
+		];
+
+		for input in inputs {
+			let expected = parse_json_bytes_to_infra_value(input);
+			assert_eq!(parse_chunked(&[input]), expected);
+
+			for i in 0..=input.len() {
+				let (a, b) = input.split_at(i);
+				assert_eq!(parse_chunked(&[a, &[], b]), expected, "split at {}", i);
+			}
+
+			let bytes: alloc::vec::Vec<&[u8]> = input.chunks(1).collect();
+			assert_eq!(parse_chunked(&bytes), expected);
+		}
+	}
+
+	#[test]
+	fn test_stream_parser_decode_errors() {
+		assert!(matches!(
+			parse_chunked(&[b"\"\xC3", b"\xFF\""]),
+			Err(JsonBytesParseError::Decode(_))
+		));
+		assert!(matches!(
+			parse_chunked(&[b"\"\xF0\x9F", b"\xA6"]),
+			Err(JsonBytesParseError::Decode(_))
+		));
+		assert!(matches!(
+			parse_chunked(&[b"\xEF\xBB", b"\xBF\xEF\xBB\xBFnull"]),
+			Err(JsonBytesParseError::Syntax(
+				JsonParseError::UnexpectedCharacter {
+					character: '\u{FEFF}',
+					position: 0
+				}
+			))
+		));
+	}
+
+	#[test]
+	fn test_stream_parser_error_is_sticky() {
+		let mut parser = JsonStreamParser::new();
+		let error = Err(JsonBytesParseError::Syntax(
+			JsonParseError::TrailingCharacters { position: 5 },
+		));
+		assert_eq!(parser.feed(b"null null"), error);
+		assert_eq!(parser.feed(b""), error);
+		assert_eq!(parser.finish(), error.map(|_| InfraValue::Null));
+	}
+
+	#[test]
+	fn test_stream_parser_deep_nesting() {
+		let depth = JsonParseOptions::DEFAULT_MAX_DEPTH;
+		let nested = |depth| {
+			let mut json = "[{\"a\": ".repeat(depth / 2);
+			json.push_str("null");
+			json.push_str(&"}]".repeat(depth / 2));
+			json
+		};
+
+		let value = parse_json_string_to_infra_value(&nested(depth)).unwrap();
+		assert_eq!(to_json(&value), nested(depth).replace(' ', ""));
+		assert_eq!(value.clone(), value);
+
+		let mut parser = JsonStreamParser::new();
+		for _ in 0..depth {
+			parser.feed(b"[").unwrap();
+		}
+		assert_eq!(
+			parser.feed(b"["),
+			Err(JsonBytesParseError::Syntax(
+				JsonParseError::DepthLimitExceeded { position: depth }
+			))
+		);
+
+		let json = "[".repeat(200_000);
+		assert_eq!(
+			parse_json_string_to_infra_value(&json),
+			Err(JsonParseError::DepthLimitExceeded { position: depth })
+		);
+
+		let options = JsonParseOptions::new().with_max_depth(0);
+		assert_eq!(
+			parse_json_string_to_infra_value_with_options("{}", options),
+			Err(JsonParseError::DepthLimitExceeded { position: 0 })
+		);
+		assert_eq!(
+			parse_json_string_to_infra_value_with_options("1", options),
+			Ok(n(1.0))
+		);
+	}
+
+	fn to_json(value: &InfraValue) -> String {
+		serialize_infra_value_to_json_string(value)
+	}
+
+	#[test]
+	fn test_serialize_json_string_literals() {
+		assert_eq!(to_json(&InfraValue::Null), s("null"));
+		assert_eq!(to_json(&InfraValue::Boolean(true)), s("true"));
+		assert_eq!(to_json(&InfraValue::Boolean(false)), s("false"));
+	}
+
+	#[test]
+	fn test_serialize_json_string_numbers() {
+		let cases = [
+			(0.0, "0"),
+			(-0.0, "0"),
+			(1.0, "1"),
+			(-1.5, "-1.5"),
+			(123.456, "123.456"),
+			(0.000001, "0.000001"),
+			(0.0000001, "1e-7"),
+			(1.5e-10, "1.5e-10"),
+			(1e21, "1e+21"),
+			(123e20, "1.23e+22"),
+			(1e20, "100000000000000000000"),
+			(f64::NAN, "null"),
+			(f64::INFINITY, "null"),
+			(f64::NEG_INFINITY, "null"),
+		];
+		for (n, expected) in cases {
+			assert_eq!(
+				to_json(&InfraValue::Number(InfraNumber::F64(n))),
+				s(expected)
+			);
+		}
+	}
+
+	#[test]
+	fn test_serialize_json_string_strings() {
+		assert_eq!(
+			to_json(&InfraValue::String(s(
+				"a\"\\/\u{8}\u{C}\n\r\t\u{1}\u{1F}é🦀"
+			))),
+			s(r#""a\"\\/\b\f\n\r\t\u0001\u001fé🦀""#)
+		);
+	}
+
+	#[test]
+	fn test_serialize_json_string_nested() {
+		let value = map(vec![
+			(s("list"), InfraValue::List(vec![])),
+			(s("map"), map(vec![(s("a"), InfraValue::Null)])),
+			(s("items"), InfraValue::List(vec![n(1.0), n(2.0)])),
+		]);
+		assert_eq!(
+			to_json(&value),
+			s(r#"{"list":[],"map":{"a":null},"items":[1,2]}"#)
+		);
+	}
+
+	#[test]
+	fn test_serialize_json_round_trip() {
+		let json = r#"{"1":[true,false,null],"b":"\n","a":{"c":-1.25e-30}}"#;
+		let value = parse_json_string_to_infra_value(json).unwrap();
+		assert_eq!(to_json(&value), s(json));
+	}
+
+	#[test]
+	fn test_serialize_json_bytes() {
+		assert_eq!(
+			serialize_infra_value_to_json_bytes(&InfraValue::String(s("🦀"))),
+			b"\"\xF0\x9F\xA6\x80\"".to_vec()
+		);
+	}
+
+	#[test]
+	fn test_serialize_json_sorted_keys() {
+		let value = map(vec![
+			(s("\u{FF61}"), InfraValue::Null),
+			(s("🦀"), InfraValue::Null),
+			(
+				s("b"),
+				map(vec![(s("d"), InfraValue::Null), (s("c"), InfraValue::Null)]),
+			),
+			(s("a"), InfraValue::Null),
+		]);
+		let options = JsonSerializeOptions::new().with_sorted_keys(true);
+
+		// U+1F980 is encoded as a surrogate pair, so it sorts before U+FF61
+		assert_eq!(
+			serialize_infra_value_to_json_string_with_options(&value, options),
+			s("{\"a\":null,\"b\":{\"c\":null,\"d\":null},\"🦀\":null,\"\u{FF61}\":null}")
+		);
+	}
+
+	#[test]
+	fn test_serialize_json_ascii_only() {
+		let value = InfraValue::String(s("aé🦀\n"));
+		let options = JsonSerializeOptions::new().with_ascii_only(true);
+		assert_eq!(
+			serialize_infra_value_to_json_string_with_options(&value, options),
+			s(r#""a\u00e9\ud83e\udd80\n""#)
+		);
+	}
+
+	#[test]
+	fn test_serialize_json_indent() {
+		let value = map(vec![
+			(
+				s("a"),
+				InfraValue::List(vec![n(1.0), InfraValue::List(vec![])]),
+			),
+			(s("b"), map(vec![])),
+		]);
+		let options = JsonSerializeOptions::new().with_indent(2);
+		assert_eq!(
+			serialize_infra_value_to_json_string_with_options(&value, options),
+			s("{\n  \"a\": [\n    1,\n    []\n  ],\n  \"b\": {}\n}")
+		);
+		assert_eq!(
+			serialize_infra_value_to_json_bytes_with_options(&value, JsonSerializeOptions::new()),
+			b"{\"a\":[1,[]],\"b\":{}}".to_vec()
+		);
+
+		let value = InfraValue::List(vec![n(1.0)]);
+		for indent in [11, 20, usize::MAX] {
+			let options = JsonSerializeOptions::new().with_indent(indent);
+			assert_eq!(options.indent(), 10);
+			assert_eq!(
+				serialize_infra_value_to_json_string_with_options(&value, options),
+				s("[\n          1\n]")
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_json_preserved_integers() {
+		let options = JsonParseOptions::new().with_preserved_integers(true);
+		let parse = |json| parse_json_string_to_infra_value_with_options(json, options);
+		let number = |n: InfraNumber| Ok(InfraValue::Number(n));
+
+		assert_eq!(parse("0"), number(InfraNumber::I64(0)));
+		assert_eq!(parse("-0"), number(InfraNumber::F64(-0.0)));
+		assert_eq!(
+			parse("-9223372036854775808"),
+			number(InfraNumber::I64(i64::MIN))
+		);
+		assert_eq!(
+			parse("18446744073709551615"),
+			number(InfraNumber::U64(u64::MAX))
+		);
+		assert_eq!(
+			parse("18446744073709551616"),
+			number(InfraNumber::F64(18446744073709551616.0))
+		);
+		assert_eq!(parse("1.0"), number(InfraNumber::F64(1.0)));
+		assert_eq!(parse("1e2"), number(InfraNumber::F64(100.0)));
+		assert_eq!(
+			parse_json_string_to_infra_value("1"),
+			number(InfraNumber::F64(1.0))
+		);
+	}
+
+	#[test]
+	fn test_infra_number_conversions() {
+		assert_eq!(InfraNumber::F64(-2.0).as_i64(), Some(-2));
+		assert_eq!(InfraNumber::F64(-2.0).as_u64(), None);
+		assert_eq!(InfraNumber::F64(0.5).as_i64(), None);
+		assert_eq!(InfraNumber::F64(f64::NAN).as_i64(), None);
+		assert_eq!(InfraNumber::F64(18446744073709551616.0).as_u64(), None);
+		assert_eq!(
+			InfraNumber::F64(9223372036854775808.0).as_u64(),
+			Some(1 << 63)
+		);
+		assert_eq!(InfraNumber::U64(u64::MAX).as_i64(), None);
+		assert_eq!(InfraNumber::I64(i64::MIN).as_f64(), -9223372036854775808.0);
+	}
+
+	#[test]
+	fn test_serialize_json_integers() {
+		let value = InfraValue::List(vec![
+			InfraValue::Number(InfraNumber::I64(i64::MIN)),
+			InfraValue::Number(InfraNumber::U64(u64::MAX)),
+			InfraValue::Number(InfraNumber::I64(9007199254740993)),
+		]);
+		let json = "[-9223372036854775808,18446744073709551615,9007199254740993]";
+		assert_eq!(to_json(&value), s(json));
+
+		let options = JsonParseOptions::new().with_preserved_integers(true);
+		assert_eq!(
+			parse_json_string_to_infra_value_with_options(json, options),
+			Ok(value)
+		);
+	}
+
+	#[test]
+	fn test_infra_number_eq() {
+		assert_eq!(InfraNumber::I64(1), InfraNumber::F64(1.0));
+		assert_eq!(InfraNumber::U64(1), InfraNumber::I64(1));
+		assert_eq!(InfraNumber::F64(-0.0), InfraNumber::F64(0.0));
+		assert_eq!(InfraNumber::F64(-0.0), InfraNumber::I64(0));
+		assert_eq!(InfraNumber::F64(f64::NAN), InfraNumber::F64(f64::NAN));
+		assert_ne!(
+			InfraNumber::I64(9007199254740993),
+			InfraNumber::F64(9007199254740992.0)
+		);
+		assert_ne!(InfraNumber::F64(0.5), InfraNumber::I64(0));
+	}
+
+	#[test]
+	fn test_infra_number_ord() {
+		let mut numbers = vec![
+			InfraNumber::F64(f64::NAN),
+			InfraNumber::U64(u64::MAX),
+			InfraNumber::F64(f64::INFINITY),
+			InfraNumber::F64(0.5),
+			InfraNumber::I64(-1),
+			InfraNumber::F64(-1.5),
+			InfraNumber::F64(f64::NEG_INFINITY),
+			InfraNumber::I64(i64::MIN),
+			InfraNumber::F64(1e300),
+			InfraNumber::I64(0),
+		];
+		numbers.sort();
+		assert_eq!(
+			numbers,
+			vec![
+				InfraNumber::F64(f64::NEG_INFINITY),
+				InfraNumber::I64(i64::MIN),
+				InfraNumber::F64(-1.5),
+				InfraNumber::I64(-1),
+				InfraNumber::I64(0),
+				InfraNumber::F64(0.5),
+				InfraNumber::U64(u64::MAX),
+				InfraNumber::F64(1e300),
+				InfraNumber::F64(f64::INFINITY),
+				InfraNumber::F64(f64::NAN),
+			]
+		);
+		assert!(InfraNumber::F64(9007199254740992.0) < InfraNumber::I64(9007199254740993));
+		assert!(InfraNumber::F64(-0.5) < InfraNumber::I64(0));
+		assert!(InfraNumber::F64(-0.5) > InfraNumber::I64(-1));
+	}
+
+	#[test]
+	fn test_infra_value_eq_hash() {
+		// a 64-bit FNV-1a hasher, since `std` isn't available
+		struct FnvHasher(u64);
+		impl Hasher for FnvHasher {
+			fn finish(&self) -> u64 {
+				self.0
+			}
+			fn write(&mut self, bytes: &[u8]) {
+				for &b in bytes {
+					self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100_0000_01b3);
+				}
+			}
+		}
+		let hash = |value: &InfraValue| {
+			let mut hasher = FnvHasher(0xcbf2_9ce4_8422_2325);
+			value.hash(&mut hasher);
+			hasher.finish()
+		};
+
+		let a = InfraValue::List(vec![n(1.0), n(-0.0), n(f64::NAN)]);
+		let b = InfraValue::List(vec![
+			InfraValue::Number(InfraNumber::I64(1)),
+			InfraValue::Number(InfraNumber::U64(0)),
+			n(f64::NAN),
+		]);
+		assert_eq!(a, b);
+		assert_eq!(hash(&a), hash(&b));
+
+		let map_ab = map(vec![(s("a"), n(1.0)), (s("b"), n(2.0))]);
+		let map_ba = map(vec![(s("b"), n(2.0)), (s("a"), n(1.0))]);
+		assert_ne!(map_ab, map_ba);
+		assert_ne!(InfraValue::Null, InfraValue::Boolean(false));
+	}
+
+	#[test]
+	fn test_infra_value_ord() {
+		let mut values = vec![
+			map(vec![]),
+			InfraValue::List(vec![n(2.0)]),
+			InfraValue::String(s("\u{FF61}")),
+			InfraValue::String(s("🦀")),
+			n(1.0),
+			InfraValue::List(vec![n(1.0), n(3.0)]),
+			InfraValue::Boolean(true),
+			InfraValue::Boolean(false),
+			InfraValue::Null,
+		];
+		values.sort();
+		assert_eq!(
+			values,
+			vec![
+				InfraValue::Null,
+				InfraValue::Boolean(false),
+				InfraValue::Boolean(true),
+				n(1.0),
+				InfraValue::String(s("🦀")),
+				InfraValue::String(s("\u{FF61}")),
+				InfraValue::List(vec![n(1.0), n(3.0)]),
+				InfraValue::List(vec![n(2.0)]),
+				map(vec![]),
+			]
+		);
+		assert!(map(vec![(s("a"), n(2.0))]) < map(vec![(s("b"), n(1.0))]));
+	}
+
+	#[test]
+	fn test_infra_value_accessors() {
+		let value =
+			parse_json_string_to_infra_value(r#"[null, false, 1.5, "a", [], {"k": 0}]"#).unwrap();
+		let items = value.as_list().unwrap();
+
+		assert!(items[0].is_null());
+		assert_eq!(items[1].as_bool(), Some(false));
+		assert_eq!(items[2].as_number(), Some(InfraNumber::F64(1.5)));
+		assert_eq!(items[3].as_str(), Some("a"));
+		assert_eq!(items[4].as_list(), Some(&[][..]));
+		assert_eq!(items[5].as_map().and_then(|m| m.get("k")), Some(&n(0.0)));
+		assert_eq!(items[5].get("k"), Some(&n(0.0)));
+		assert_eq!(items[5].get("x"), None);
+		assert_eq!(items[3].as_bool(), None);
+		assert_eq!(items[0].as_str(), None);
+		assert_eq!(value.as_map(), None);
+	}
+
+	#[test]
+	fn test_infra_value_get_path() {
+		let mut value =
+			parse_json_string_to_infra_value(r#"{"a": [{"b": 1}], "": 2, "~/": {"x": 3}}"#)
+				.unwrap();
+
+		assert_eq!(value.get_path(""), Some(&value));
+		assert_eq!(value.get_path("a/0/b"), Some(&n(1.0)));
+		assert_eq!(value.get_path("/"), None);
+		assert_eq!(value.get_path("~0~1/x"), Some(&n(3.0)));
+		assert_eq!(value.get_path("a/00/b"), None);
+		assert_eq!(value.get_path("a/+0/b"), None);
+		assert_eq!(value.get_path("a/0/b/c"), None);
+
+		if let Some(list) = value.get_path_mut("a").and_then(|v| v.as_list_mut()) {
+			list.push(InfraValue::Null);
+		}
+		assert_eq!(value.get_path("a/1"), Some(&InfraValue::Null));
+
+		if let Some(map) = value.get_path_mut("~0~1").and_then(|v| v.as_map_mut()) {
+			map.clear();
+		}
+		assert_eq!(value.get_path("~0~1"), Some(&map(vec![])));
+	}
+
+	#[test]
+	fn test_infra_value_debug() {
+		let value = InfraValue::List(vec![
+			InfraValue::Null,
+			InfraValue::Boolean(true),
+			n(f64::NEG_INFINITY),
+			InfraValue::Number(InfraNumber::I64(-3)),
+			InfraValue::String(s("a\"\n")),
+			InfraValue::List(vec![]),
+			map(vec![(s("k"), InfraValue::List(vec![n(0.5)]))]),
+		]);
+		assert_eq!(
+			format!("{:?}", value),
+			r#"« null, true, -Infinity, -3, "a\"\n", « », «[ "k" → « 0.5 » ]» »"#
+		);
+	}
+
+	#[test]
+	fn test_infra_value_display() {
+		let value = map(vec![
+			(s("a"), n(f64::NAN)),
+			(s("b"), InfraValue::List(vec![])),
+		]);
+		assert_eq!(format!("{}", value), r#"{"a":null,"b":[]}"#);
+		assert_eq!(format!("{:#}", value), "{\n  \"a\": null,\n  \"b\": []\n}");
+		assert_eq!(format!("{}", InfraNumber::F64(0.0000001)), "1e-7");
+		assert_eq!(format!("{}", InfraNumber::F64(f64::INFINITY)), "Infinity");
+	}
+}
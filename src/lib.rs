@@ -1,17 +1,130 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
-/// Detection of UTF-16 surrogate codepoints for `u16`
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Module for codepoints, UTF-16 code units, and sets of ASCII codepoints,
+/// which don't need `alloc`
 ///
-/// This module exposes predicate functions for detecting surrogates,
-/// including trailing and leading surrogates.
-pub mod surrogates;
-pub use crate::surrogates::*;
+/// Its `scalar`, `surrogates`, and `ascii_set` modules are also re-exported
+/// at the root of the crate, as `whatwg_infra::scalar` and so on.
+pub mod codepoints;
+pub use crate::codepoints::*;
+
+/// Variants of the string operations that split large inputs across threads,
+/// with the `parallel` feature
+#[cfg(feature = "parallel")]
+pub mod parallel;
 
-/// Module for Unicode scalar values
-pub mod scalar;
-pub use crate::scalar::*;
+/// Byte scanning for the string algorithms, vectorized with the `simd` feature
+// Without the `strings` feature, only the cursors and the display wrappers use the scans,
+// and some go unused
+#[cfg_attr(not(feature = "strings"), allow(dead_code))]
+mod scan;
 
 /// Module for Unicode strings
+#[cfg(feature = "strings")]
 pub mod strings;
+#[cfg(feature = "strings")]
 pub use crate::strings::*;
+
+/// Module for the UTF-8 decode hooks and BOM sniffing of the Encoding
+/// Standard
+pub mod encoding;
+pub use crate::encoding::*;
+
+/// Module for formatting transformed strings without allocating them
+pub mod display;
+pub use crate::display::*;
+
+/// Module for strings that only contain ASCII codepoints
+#[cfg(feature = "strings")]
+pub mod ascii_string;
+#[cfg(feature = "strings")]
+pub use crate::ascii_string::*;
+
+/// Module for strings of 16-bit code units that may be ill-formed UTF-16,
+/// like JavaScript strings
+#[cfg(feature = "strings")]
+pub mod wtf16;
+#[cfg(feature = "strings")]
+pub use crate::wtf16::*;
+
+/// Module for the errors of parsing operations
+pub mod error;
+pub use crate::error::*;
+
+/// Module for cursors that parse strings one codepoint at a time
+pub mod cursor;
+pub use crate::cursor::*;
+
+/// Module for interning the tokens of split strings, with the `intern` feature
+#[cfg(all(feature = "intern", target_has_atomic = "ptr"))]
+pub mod intern;
+#[cfg(all(feature = "intern", target_has_atomic = "ptr"))]
+pub use crate::intern::*;
+
+/// Module for observing the cursors and the JSON functions while they run,
+/// with the `instrument` feature
+#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+pub mod instrument;
+#[cfg(all(feature = "instrument", target_has_atomic = "ptr"))]
+pub use crate::instrument::*;
+
+/// Module for the data structures of the WHATWG Infra Standard
+#[cfg(feature = "collections")]
+pub mod collections;
+#[cfg(feature = "collections")]
+pub use crate::collections::*;
+
+/// Module for converting between JSON and Infra values
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "json")]
+pub use crate::json::*;
+
+/// Module for the URL Standard's URL code points and input preprocessing,
+/// built on this crate's codepoint classes, with the `url` feature
+#[cfg(feature = "url")]
+pub mod url;
+
+/// Module for parsing and serializing the MIME types of the MIME Sniffing
+/// Standard, with the `mime` feature
+#[cfg(feature = "mime")]
+pub mod mime;
+
+/// Module for test inputs and expected outputs that other crates can run
+/// against their own wrappers, with the `test-support` feature
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+/// Module for calling the string operations and predicates from C, with the
+/// `ffi` feature
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Module for the namespaces of the WHATWG Infra Standard
+pub mod namespaces;
+pub use crate::namespaces::*;
+
+/// Module that re-exports the extension traits and the most used types,
+/// for importing them all at once
+///
+/// # Examples
+#[cfg_attr(all(feature = "strings", feature = "collections"), doc = "```")]
+#[cfg_attr(
+	not(all(feature = "strings", feature = "collections")),
+	doc = "```ignore"
+)]
+/// use whatwg_infra::prelude::*;
+///
+/// let mut set = OrderedSet::new();
+/// for token in "b a  b".split_on_ascii_whitespace() {
+///     set.append(token);
+/// }
+/// assert_eq!(set.as_slice(), &["b", "a"]);
+/// assert!('\u{FDD0}'.is_noncharacter());
+/// ```
+pub mod prelude;
@@ -0,0 +1,367 @@
+extern crate alloc;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use crate::codepoints::ascii_set::AsciiSet;
+use crate::collections::OrderedMap;
+use crate::cursor::{Checkpoint, CodePointCursor};
+use crate::error::InfraParseError;
+use crate::strings::{ascii_lowercase, isomorphic_decode};
+
+/// The [HTTP whitespace][whatwg-fetch-dfn] codepoints: U+0009 TAB, U+000A
+/// LF, U+000D CR, and U+0020 SPACE
+///
+/// [whatwg-fetch-dfn]: https://fetch.spec.whatwg.org/#http-whitespace
+pub const HTTP_WHITESPACE: AsciiSet = AsciiSet::ASCII_TAB_OR_NEWLINE.add(b' ');
+
+/// The [HTTP token code points][whatwg-mimesniff-dfn]: the ASCII
+/// alphanumerics, and ``!#$%&'*+-.^_`|~``
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#http-token-code-point
+pub const HTTP_TOKEN_CODE_POINTS: AsciiSet =
+	AsciiSet::ASCII_ALPHANUMERIC.union(AsciiSet::from_bytes(b"!#$%&'*+-.^_`|~"));
+
+const OPERATION: &str = "parse a MIME type";
+
+/// A [MIME type][whatwg-mimesniff-dfn], made of a type, a subtype, and an
+/// ordered map of parameters.
+///
+/// It's created by [`parse_mime_type()`], which lowercases the type, the
+/// subtype, and the names of the parameters, and it's serialized by its
+/// [`Display`](fmt::Display) impl.
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#mime-type
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::mime::MimeType;
+///
+/// let mime_type: MimeType = "Text/HTML; Charset=\"utf-8\"".parse().unwrap();
+/// assert_eq!(mime_type.essence(), "text/html");
+/// assert_eq!(mime_type.parameters().get("charset").unwrap(), "utf-8");
+/// assert_eq!(mime_type.to_string(), "text/html;charset=utf-8");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimeType {
+	type_: String,
+	subtype: String,
+	parameters: OrderedMap<String, String>,
+}
+
+impl MimeType {
+	/// Returns the type of the MIME type, like `text` in `text/html`
+	#[must_use]
+	pub fn type_(&self) -> &str {
+		&self.type_
+	}
+
+	/// Returns the subtype of the MIME type, like `html` in `text/html`
+	#[must_use]
+	pub fn subtype(&self) -> &str {
+		&self.subtype
+	}
+
+	/// Returns the [essence][whatwg-mimesniff-dfn] of the MIME type, which
+	/// is its type and subtype separated by `/`, without its parameters
+	///
+	/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#mime-type-essence
+	#[must_use]
+	pub fn essence(&self) -> String {
+		let mut essence = String::with_capacity(self.type_.len() + 1 + self.subtype.len());
+		essence.push_str(&self.type_);
+		essence.push('/');
+		essence.push_str(&self.subtype);
+		essence
+	}
+
+	/// Returns the parameters of the MIME type, with their names lowercased
+	#[must_use]
+	pub fn parameters(&self) -> &OrderedMap<String, String> {
+		&self.parameters
+	}
+
+	/// Returns the parameters of the MIME type, to change them before
+	/// serializing it.
+	///
+	/// Serializing quotes the values that need it, but the names of new
+	/// parameters should only contain [`HTTP_TOKEN_CODE_POINTS`], or the
+	/// serialized MIME type won't parse back to the same parameters.
+	#[must_use]
+	pub fn parameters_mut(&mut self) -> &mut OrderedMap<String, String> {
+		&mut self.parameters
+	}
+}
+
+impl FromStr for MimeType {
+	type Err = InfraParseError;
+
+	fn from_str(s: &str) -> Result<Self, InfraParseError> {
+		parse_mime_type(s)
+	}
+}
+
+impl fmt::Display for MimeType {
+	/// Serializes the MIME type, quoting the parameter values that are empty
+	/// or contain codepoints other than HTTP token code points
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}/{}", self.type_, self.subtype)?;
+		for (name, value) in self.parameters.iter() {
+			write!(f, ";{}=", name)?;
+			if is_http_token(value) {
+				f.write_str(value)?;
+				continue;
+			}
+			f.write_char('"')?;
+			for c in value.chars() {
+				if matches!(c, '"' | '\\') {
+					f.write_char('\\')?;
+				}
+				f.write_char(c)?;
+			}
+			f.write_char('"')?;
+		}
+		Ok(())
+	}
+}
+
+/// Parses a string into a MIME type.
+///
+/// Parameters that are invalid, or repeat the name of an earlier parameter,
+/// are skipped instead of failing the parse, like the MIME Sniffing
+/// Standard does.
+///
+/// See also: [WHATWG MIME Sniffing Standard definition][whatwg-mimesniff-dfn]
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#parse-a-mime-type
+///
+/// # Errors
+/// Returns an [`InfraParseError`] if the type or the subtype is empty or
+/// isn't an HTTP token, or if the input has no `/` after the type.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::mime::parse_mime_type;
+///
+/// let mime_type = parse_mime_type(" image/svg+xml;charset=utf-8;charset=ascii ").unwrap();
+/// assert_eq!((mime_type.type_(), mime_type.subtype()), ("image", "svg+xml"));
+/// assert_eq!(mime_type.parameters().get("charset").unwrap(), "utf-8");
+///
+/// assert!(parse_mime_type("text").is_err());
+/// assert!(parse_mime_type("text/ html").is_err());
+/// ```
+pub fn parse_mime_type(input: &str) -> Result<MimeType, InfraParseError> {
+	let input = input.trim_end_matches(|c| HTTP_WHITESPACE.contains_char(c));
+	let mut cursor = CodePointCursor::new(input);
+	cursor.collect_while(|c| HTTP_WHITESPACE.contains_char(c));
+
+	let checkpoint = cursor.checkpoint();
+	let type_ = cursor.collect_while(|c| c != '/');
+	require_http_token(&mut cursor, checkpoint, type_)?;
+	if cursor.is_past_end() {
+		return Err(cursor.error(OPERATION, "\"/\""));
+	}
+	cursor.next();
+
+	let checkpoint = cursor.checkpoint();
+	let subtype = trim_end_http_whitespace(cursor.collect_while(|c| c != ';'));
+	require_http_token(&mut cursor, checkpoint, subtype)?;
+
+	let mut mime_type = MimeType {
+		type_: ascii_lowercase(type_),
+		subtype: ascii_lowercase(subtype),
+		parameters: OrderedMap::new(),
+	};
+	while !cursor.is_past_end() {
+		// Skip the `;` before the parameter, and the whitespace after it
+		cursor.next();
+		cursor.collect_while(|c| HTTP_WHITESPACE.contains_char(c));
+
+		let name = cursor.collect_while(|c| c != ';' && c != '=');
+		if cursor.peek() == Some(';') {
+			continue;
+		}
+		cursor.next();
+		if cursor.is_past_end() {
+			break;
+		}
+
+		let value = if cursor.peek() == Some('"') {
+			let value = collect_http_quoted_string_value(&mut cursor);
+			cursor.collect_while(|c| c != ';');
+			Cow::Owned(value)
+		} else {
+			let value = trim_end_http_whitespace(cursor.collect_while(|c| c != ';'));
+			if value.is_empty() {
+				continue;
+			}
+			Cow::Borrowed(value)
+		};
+
+		let name = ascii_lowercase(name);
+		if is_http_token(&name)
+			&& value.chars().all(is_http_quoted_string_token_code_point)
+			&& !mime_type.parameters.contains_key(name.as_str())
+		{
+			mime_type.parameters.set(name, value.into_owned());
+		}
+	}
+	Ok(mime_type)
+}
+
+/// Parses a byte sequence into a MIME type, by isomorphic decoding it and
+/// parsing the string, like the `Content-Type` header's value.
+///
+/// See also: [WHATWG MIME Sniffing Standard definition][whatwg-mimesniff-dfn]
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#parse-a-mime-type-from-bytes
+///
+/// # Errors
+/// Returns an [`InfraParseError`] like [`parse_mime_type()`]. Its offset is
+/// a byte offset of the decoded string.
+pub fn parse_mime_type_from_bytes(input: &[u8]) -> Result<MimeType, InfraParseError> {
+	parse_mime_type(&isomorphic_decode(input))
+}
+
+/// Serializes a MIME type into a string, like its
+/// [`Display`](fmt::Display) impl.
+///
+/// See also: [WHATWG MIME Sniffing Standard definition][whatwg-mimesniff-dfn]
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#serialize-a-mime-type
+#[must_use]
+pub fn serialize_mime_type(mime_type: &MimeType) -> String {
+	mime_type.to_string()
+}
+
+/// Checks that a token collected since a checkpoint is an HTTP token, or
+/// returns an error at its first codepoint that isn't
+fn require_http_token(
+	cursor: &mut CodePointCursor<'_>,
+	checkpoint: Checkpoint,
+	token: &str,
+) -> Result<(), InfraParseError> {
+	if is_http_token(token) {
+		return Ok(());
+	}
+	cursor.restore(checkpoint);
+	cursor.collect_while(|c| HTTP_TOKEN_CODE_POINTS.contains_char(c));
+	Err(cursor.error(OPERATION, "an HTTP token code point"))
+}
+
+/// Checks if a string is non-empty and only contains HTTP token code points
+fn is_http_token(s: &str) -> bool {
+	!s.is_empty() && s.bytes().all(|byte| HTTP_TOKEN_CODE_POINTS.contains(byte))
+}
+
+/// Checks if a codepoint is an [HTTP quoted-string token code
+/// point][whatwg-mimesniff-dfn]: U+0009 TAB, U+0020 SPACE to U+007E (~), or
+/// U+0080 to U+00FF
+///
+/// [whatwg-mimesniff-dfn]: https://mimesniff.spec.whatwg.org/#http-quoted-string-token-code-point
+fn is_http_quoted_string_token_code_point(c: char) -> bool {
+	matches!(c, '\t' | ' '..='~' | '\u{80}'..='\u{FF}')
+}
+
+fn trim_end_http_whitespace(s: &str) -> &str {
+	s.trim_end_matches(|c| HTTP_WHITESPACE.contains_char(c))
+}
+
+/// Collects an [HTTP quoted string][whatwg-fetch-dfn] at the position of a
+/// cursor, which must be at a `"`, and returns its value without the quotes
+/// and backslash escapes
+///
+/// [whatwg-fetch-dfn]: https://fetch.spec.whatwg.org/#collect-an-http-quoted-string
+fn collect_http_quoted_string_value(cursor: &mut CodePointCursor<'_>) -> String {
+	let mut value = String::new();
+	cursor.next();
+	loop {
+		value.push_str(cursor.collect_while(|c| c != '"' && c != '\\'));
+		match cursor.next() {
+			Some('\\') => match cursor.next() {
+				Some(c) => value.push(c),
+				None => {
+					value.push('\\');
+					break;
+				}
+			},
+			_ => break,
+		}
+	}
+	value
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn parse(input: &str) -> Option<String> {
+		parse_mime_type(input)
+			.ok()
+			.map(|mime_type| mime_type.to_string())
+	}
+
+	#[test]
+	fn test_parse_mime_type() {
+		assert_eq!(parse("text/html").unwrap(), "text/html");
+		assert_eq!(parse("\t TEXT/Html \r\n").unwrap(), "text/html");
+		assert_eq!(parse("text/html;").unwrap(), "text/html");
+		assert_eq!(parse("text/html;charset").unwrap(), "text/html");
+		assert_eq!(parse("text/html;charset=").unwrap(), "text/html");
+		assert_eq!(parse("text/html;charset= ;a=b").unwrap(), "text/html;a=b");
+		assert_eq!(parse("text/html;;; a=b ").unwrap(), "text/html;a=b");
+		assert_eq!(parse("text/html;a=\"b;c\"").unwrap(), "text/html;a=\"b;c\"");
+		assert_eq!(
+			parse("text/html;a=\"b\"junk;c=d").unwrap(),
+			"text/html;a=b;c=d"
+		);
+		assert_eq!(parse("text/html;a=\"\"").unwrap(), "text/html;a=\"\"");
+		assert_eq!(
+			parse("text/html;a=\"\\\"\\\\\"").unwrap(),
+			"text/html;a=\"\\\"\\\\\""
+		);
+		assert_eq!(parse("text/html;a=\"b\\").unwrap(), "text/html;a=\"b\\\\\"");
+		assert_eq!(parse("text/html;A=1;a=2").unwrap(), "text/html;a=1");
+		assert_eq!(parse("text/html;a b=1;c=\u{100}").unwrap(), "text/html");
+		assert_eq!(
+			parse("text/html;a=\u{E9}").unwrap(),
+			"text/html;a=\"\u{E9}\""
+		);
+	}
+
+	#[test]
+	fn test_parse_mime_type_failure() {
+		for (input, offset) in [
+			("", 0),
+			("  ", 0),
+			("/html", 0),
+			("text", 4),
+			("text/", 5),
+			(" text /html", 5),
+			("text/;a=b", 5),
+			("text/ht ml", 7),
+			("t\u{E9}xt/html", 1),
+		] {
+			let error = parse_mime_type(input).unwrap_err();
+			assert_eq!(error.offset(), offset, "{:?}", input);
+		}
+	}
+
+	#[test]
+	fn test_parse_mime_type_from_bytes() {
+		let mime_type = parse_mime_type_from_bytes(b"text/plain;a=\"\xE9\"").unwrap();
+		assert_eq!(mime_type.parameters().get("a").unwrap(), "\u{E9}");
+		assert_eq!(serialize_mime_type(&mime_type), "text/plain;a=\"\u{E9}\"");
+	}
+
+	#[test]
+	fn test_mime_type_parameters_mut() {
+		let mut mime_type: MimeType = "text/plain".parse().unwrap();
+		mime_type
+			.parameters_mut()
+			.set(String::from("charset"), String::from("utf 8"));
+		assert_eq!(mime_type.to_string(), "text/plain;charset=\"utf 8\"");
+		assert_eq!(mime_type.essence(), "text/plain");
+	}
+}
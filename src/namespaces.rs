@@ -0,0 +1,280 @@
+/// The HTML namespace, `http://www.w3.org/1999/xhtml`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#html-namespace
+pub const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// The MathML namespace, `http://www.w3.org/1998/Math/MathML`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#mathml-namespace
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// The SVG namespace, `http://www.w3.org/2000/svg`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#svg-namespace
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// The XLink namespace, `http://www.w3.org/1999/xlink`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#xlink-namespace
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// The XML namespace, `http://www.w3.org/XML/1998/namespace`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#xml-namespace
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The XMLNS namespace, `http://www.w3.org/2000/xmlns/`.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#xmlns-namespace
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// One of the namespaces defined by the WHATWG Infra Standard.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#namespaces
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::Namespace;
+///
+/// let namespace = Namespace::from_uri("http://www.w3.org/2000/svg");
+/// assert_eq!(namespace, Some(Namespace::Svg));
+/// assert_eq!(Namespace::Svg.as_uri(), "http://www.w3.org/2000/svg");
+/// assert_eq!(Namespace::from_uri("http://example.com/"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Namespace {
+	/// The [HTML namespace](HTML_NAMESPACE)
+	Html,
+	/// The [MathML namespace](MATHML_NAMESPACE)
+	MathMl,
+	/// The [SVG namespace](SVG_NAMESPACE)
+	Svg,
+	/// The [XLink namespace](XLINK_NAMESPACE)
+	XLink,
+	/// The [XML namespace](XML_NAMESPACE)
+	Xml,
+	/// The [XMLNS namespace](XMLNS_NAMESPACE)
+	Xmlns,
+}
+
+impl Namespace {
+	/// Returns the namespace with the given URI, if the URI is exactly the
+	/// URI of one of the namespaces
+	#[must_use]
+	pub fn from_uri(uri: &str) -> Option<Self> {
+		match uri {
+			HTML_NAMESPACE => Some(Self::Html),
+			MATHML_NAMESPACE => Some(Self::MathMl),
+			SVG_NAMESPACE => Some(Self::Svg),
+			XLINK_NAMESPACE => Some(Self::XLink),
+			XML_NAMESPACE => Some(Self::Xml),
+			XMLNS_NAMESPACE => Some(Self::Xmlns),
+			_ => None,
+		}
+	}
+
+	/// Returns the URI of the namespace
+	#[must_use]
+	pub const fn as_uri(self) -> &'static str {
+		match self {
+			Self::Html => HTML_NAMESPACE,
+			Self::MathMl => MATHML_NAMESPACE,
+			Self::Svg => SVG_NAMESPACE,
+			Self::XLink => XLINK_NAMESPACE,
+			Self::Xml => XML_NAMESPACE,
+			Self::Xmlns => XMLNS_NAMESPACE,
+		}
+	}
+}
+
+impl core::fmt::Display for Namespace {
+	/// Writes the URI of the namespace
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.write_str(self.as_uri())
+	}
+}
+
+/// Splits a qualified name into its namespace prefix and local name, at
+/// the first U+003A (:), or returns no prefix if there's no colon.
+///
+/// The parts aren't validated; see [`is_valid_namespace_prefix()`],
+/// [`is_valid_element_local_name()`], and [`is_valid_attribute_local_name()`].
+///
+/// See also: [DOM Standard definition][dom-dfn]
+///
+/// [dom-dfn]: https://dom.spec.whatwg.org/#validate-and-extract
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::split_qualified_name;
+///
+/// assert_eq!(split_qualified_name("xlink:href"), (Some("xlink"), "href"));
+/// assert_eq!(split_qualified_name("href"), (None, "href"));
+/// ```
+#[must_use]
+pub fn split_qualified_name(qualified_name: &str) -> (Option<&str>, &str) {
+	match qualified_name.split_once(':') {
+		Some((prefix, local_name)) => (Some(prefix), local_name),
+		None => (None, qualified_name),
+	}
+}
+
+/// Checks if a string is a valid namespace prefix: it isn't empty, and
+/// doesn't contain ASCII whitespace, U+0000 NULL, U+002F (/), or
+/// U+003E (>).
+///
+/// See also: [DOM Standard definition][dom-dfn]
+///
+/// [dom-dfn]: https://dom.spec.whatwg.org/#valid-namespace-prefix
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_valid_namespace_prefix;
+///
+/// assert!(is_valid_namespace_prefix("svg"));
+/// assert!(!is_valid_namespace_prefix(""));
+/// assert!(!is_valid_namespace_prefix("a/b"));
+/// ```
+#[must_use]
+pub fn is_valid_namespace_prefix(prefix: &str) -> bool {
+	!prefix.is_empty()
+		&& !prefix
+			.chars()
+			.any(|c| c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '>'))
+}
+
+/// Checks if a string is a valid attribute local name: it isn't empty,
+/// and doesn't contain ASCII whitespace, U+0000 NULL, U+002F (/),
+/// U+003D (=), or U+003E (>).
+///
+/// See also: [DOM Standard definition][dom-dfn]
+///
+/// [dom-dfn]: https://dom.spec.whatwg.org/#valid-attribute-local-name
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_valid_attribute_local_name;
+///
+/// assert!(is_valid_attribute_local_name("data-id"));
+/// assert!(!is_valid_attribute_local_name("a=b"));
+/// ```
+#[must_use]
+pub fn is_valid_attribute_local_name(local_name: &str) -> bool {
+	!local_name.is_empty()
+		&& !local_name
+			.chars()
+			.any(|c| c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '=' | '>'))
+}
+
+/// Checks if a string is a valid element local name.
+///
+/// A local name that starts with an ASCII alpha is valid unless it contains
+/// ASCII whitespace, U+0000 NULL, U+002F (/), or U+003E (>). Otherwise, it
+/// must start with U+003A (:), U+005F (_), or a code point of U+0080 or
+/// above, followed by ASCII alphanumerics, U+002D (-), U+002E (.),
+/// U+003A (:), U+005F (_), or code points of U+0080 or above.
+///
+/// See also: [DOM Standard definition][dom-dfn]
+///
+/// [dom-dfn]: https://dom.spec.whatwg.org/#valid-element-local-name
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_valid_element_local_name;
+///
+/// assert!(is_valid_element_local_name("my-element"));
+/// assert!(is_valid_element_local_name("_private"));
+/// assert!(!is_valid_element_local_name("1st"));
+/// assert!(!is_valid_element_local_name("a b"));
+/// ```
+#[must_use]
+pub fn is_valid_element_local_name(local_name: &str) -> bool {
+	let mut chars = local_name.chars();
+	match chars.next() {
+		None => false,
+		Some(first) if first.is_ascii_alphabetic() => {
+			!chars.any(|c| c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '>'))
+		}
+		Some(first) => {
+			matches!(first, ':' | '_' | '\u{80}'..='\u{10FFFF}')
+				&& chars.all(|c| {
+					c.is_ascii_alphanumeric()
+						|| matches!(c, '-' | '.' | ':' | '_' | '\u{80}'..='\u{10FFFF}')
+				})
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	extern crate alloc;
+	use alloc::string::ToString;
+
+	const ALL: [Namespace; 6] = [
+		Namespace::Html,
+		Namespace::MathMl,
+		Namespace::Svg,
+		Namespace::XLink,
+		Namespace::Xml,
+		Namespace::Xmlns,
+	];
+
+	#[test]
+	fn test_namespace_round_trip() {
+		for namespace in ALL {
+			assert_eq!(Namespace::from_uri(namespace.as_uri()), Some(namespace));
+			assert_eq!(namespace.to_string(), namespace.as_uri());
+		}
+	}
+
+	#[test]
+	fn test_namespace_from_uri_exact() {
+		assert_eq!(Namespace::from_uri(""), None);
+		assert_eq!(Namespace::from_uri("http://www.w3.org/1999/XHTML"), None);
+		assert_eq!(Namespace::from_uri("http://www.w3.org/2000/xmlns"), None);
+		assert_eq!(Namespace::from_uri(" http://www.w3.org/2000/svg"), None);
+	}
+
+	#[test]
+	fn test_split_qualified_name() {
+		assert_eq!(split_qualified_name(""), (None, ""));
+		assert_eq!(split_qualified_name(":a"), (Some(""), "a"));
+		assert_eq!(split_qualified_name("a:"), (Some("a"), ""));
+		assert_eq!(split_qualified_name("a:b:c"), (Some("a"), "b:c"));
+	}
+
+	#[test]
+	fn test_is_valid_names() {
+		assert!(is_valid_namespace_prefix("\u{1F980}"));
+		assert!(!is_valid_namespace_prefix("a\0"));
+		assert!(!is_valid_namespace_prefix("a\tb"));
+
+		assert!(is_valid_attribute_local_name(":"));
+		assert!(!is_valid_attribute_local_name(""));
+		assert!(!is_valid_attribute_local_name("a>"));
+
+		assert!(is_valid_element_local_name("a:b"));
+		assert!(is_valid_element_local_name("\u{E9}l\u{E9}ment"));
+		assert!(is_valid_element_local_name(":a.b-c"));
+		assert!(!is_valid_element_local_name(""));
+		assert!(!is_valid_element_local_name("a/"));
+		assert!(!is_valid_element_local_name("_a b"));
+		assert!(!is_valid_element_local_name("-a"));
+		assert!(!is_valid_element_local_name("_a!"));
+	}
+}
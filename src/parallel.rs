@@ -0,0 +1,321 @@
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+use std::thread;
+
+use crate::strings;
+
+/// The length of input that each thread gets at least. Shorter inputs are
+/// processed on the calling thread, since spawning threads for them costs
+/// more than it saves.
+const MIN_CHUNK_LEN: usize = 256 * 1024;
+
+/// Normalizes newlines like [`normalize_newlines()`](crate::normalize_newlines),
+/// splitting large strings into chunks that are normalized on separate threads.
+///
+/// Chunks never end between a CR and the LF after it, so the result is
+/// the same as the one of `normalize_newlines()`.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parallel;
+///
+/// let document = "Hello,\r\nworld!\r".repeat(100_000);
+/// assert_eq!(
+///     parallel::normalize_newlines(&document),
+///     whatwg_infra::normalize_newlines(&document),
+/// );
+/// ```
+#[must_use]
+pub fn normalize_newlines(s: &str) -> String {
+	concat(map_chunks(s, |_, chunk| strings::normalize_newlines(chunk)))
+}
+
+/// Removes newlines like [`strip_newlines()`](crate::strip_newlines),
+/// splitting large strings into chunks that are stripped on separate threads.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parallel;
+///
+/// let document = "Hello,\r\nworld!\n".repeat(100_000);
+/// assert_eq!(
+///     parallel::strip_newlines(&document),
+///     "Hello,world!".repeat(100_000),
+/// );
+/// ```
+#[must_use]
+pub fn strip_newlines(s: &str) -> String {
+	concat(map_chunks(s, |_, chunk| strings::strip_newlines(chunk)))
+}
+
+/// Checks if a string contains a [noncharacter](crate::is_noncharacter)
+/// like [`contains_noncharacter()`](crate::contains_noncharacter),
+/// splitting large strings into chunks that are checked on separate threads.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parallel;
+///
+/// let mut document = "text ".repeat(100_000);
+/// assert!(!parallel::contains_noncharacter(&document));
+///
+/// document.push('\u{FDD0}');
+/// assert!(parallel::contains_noncharacter(&document));
+/// ```
+#[must_use]
+pub fn contains_noncharacter(s: &str) -> bool {
+	map_chunks(s, |_, chunk| strings::contains_noncharacter(chunk))
+		.into_iter()
+		.any(|found| found)
+}
+
+/// Returns the byte index of the first [C0 control](crate::is_c0_control)
+/// codepoint of a string like [`find_first_c0_control()`](crate::find_first_c0_control),
+/// splitting large strings into chunks that are checked on separate threads.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::parallel;
+///
+/// let mut document = "text ".repeat(100_000);
+/// assert_eq!(parallel::find_first_c0_control(&document), None);
+///
+/// document.push('\u{0007}');
+/// assert_eq!(parallel::find_first_c0_control(&document), Some(500_000));
+/// ```
+#[must_use]
+pub fn find_first_c0_control(s: &str) -> Option<usize> {
+	map_chunks(s, |offset, chunk| {
+		strings::find_first_c0_control(chunk).map(|index| offset + index)
+	})
+	.into_iter()
+	.flatten()
+	.next()
+}
+
+/// Joins the strings of the chunks, reusing the allocation of the first one,
+/// which is the whole result when the input is one chunk
+fn concat(strings: Vec<String>) -> String {
+	let len: usize = strings.iter().map(String::len).sum();
+	let mut strings = strings.into_iter();
+	let mut joined = strings.next().unwrap_or_default();
+	joined.reserve(len - joined.len());
+	for string in strings {
+		joined.push_str(&string);
+	}
+	joined
+}
+
+/// Calls `f` with the byte offset of each chunk of a string, and the chunk,
+/// with one chunk for each available thread. Returns the results in the
+/// order of the chunks.
+fn map_chunks<'a, T, F>(s: &'a str, f: F) -> Vec<T>
+where
+	T: Send,
+	F: Fn(usize, &'a str) -> T + Sync,
+{
+	let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+	map_chunks_with(s, threads, MIN_CHUNK_LEN, f)
+}
+
+/// Splits a string into at most `max_chunks` chunks of at least `min_len`
+/// bytes, and calls `f` on each one of them, on a thread of its own. The
+/// first chunk is handled by the calling thread.
+fn map_chunks_with<'a, T, F>(s: &'a str, max_chunks: usize, min_len: usize, f: F) -> Vec<T>
+where
+	T: Send,
+	F: Fn(usize, &'a str) -> T + Sync,
+{
+	let chunks = split_chunks(s, max_chunks, min_len);
+	if chunks.len() == 1 {
+		return chunks
+			.into_iter()
+			.map(|(offset, chunk)| f(offset, chunk))
+			.collect();
+	}
+
+	let f = &f;
+	thread::scope(|scope| {
+		let handles: Vec<_> = chunks[1..]
+			.iter()
+			.map(|&(offset, chunk)| scope.spawn(move || f(offset, chunk)))
+			.collect();
+
+		let mut results = Vec::with_capacity(chunks.len());
+		results.push(f(chunks[0].0, chunks[0].1));
+		for handle in handles {
+			// Pass a panic of `f` on to the caller
+			results.push(
+				handle
+					.join()
+					.unwrap_or_else(|payload| std::panic::resume_unwind(payload)),
+			);
+		}
+		results
+	})
+}
+
+/// Splits a string into at most `max_chunks` chunks of about the same
+/// length, and at least `min_len` bytes, with the byte offset of each one.
+///
+/// Chunks end on char boundaries, and never between a CR and the LF after
+/// it, so that a CRLF pair is normalized as one newline.
+fn split_chunks(s: &str, max_chunks: usize, min_len: usize) -> Vec<(usize, &str)> {
+	let count = (s.len() / min_len.max(1)).clamp(1, max_chunks.max(1));
+	let target_len = s.len() / count;
+
+	let mut chunks = Vec::with_capacity(count);
+	let mut offset = 0;
+	for _ in 1..count {
+		let end = chunk_end(s, offset + target_len);
+		if end == s.len() {
+			break;
+		}
+		chunks.push((offset, &s[offset..end]));
+		offset = end;
+	}
+	chunks.push((offset, &s[offset..]));
+	chunks
+}
+
+/// Returns the first index at or after `index` where a string can be split
+/// into chunks: on a char boundary, and not between a CR and an LF
+fn chunk_end(s: &str, mut index: usize) -> usize {
+	if index >= s.len() {
+		return s.len();
+	}
+	while !s.is_char_boundary(index) {
+		index += 1;
+	}
+
+	let bytes = s.as_bytes();
+	if index > 0 && bytes[index - 1] == b'\r' && bytes.get(index) == Some(&b'\n') {
+		index += 1;
+	}
+	index
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Strings with CRLF pairs, lone CRs and LFs, noncharacters, C0 controls,
+	/// and multi-byte codepoints at every position relative to the chunk ends
+	fn inputs() -> Vec<String> {
+		let pieces = [
+			"a",
+			"\r\n",
+			"\r",
+			"\n",
+			"\u{E9}",
+			"\u{1F980}",
+			"\u{FDD0}",
+			"\u{0007}",
+		];
+		let mut inputs = Vec::new();
+		for first in pieces {
+			for second in pieces {
+				for len in 0..8 {
+					let mut input = "x".repeat(len);
+					for _ in 0..4 {
+						input.push_str(first);
+						input.push_str("yz");
+						input.push_str(second);
+					}
+					inputs.push(input);
+				}
+			}
+		}
+		inputs
+	}
+
+	#[test]
+	fn test_split_chunks() {
+		for input in inputs() {
+			for max_chunks in 1..8 {
+				let chunks = split_chunks(&input, max_chunks, 1);
+				assert!(chunks.len() <= max_chunks);
+				assert_eq!(
+					chunks.iter().map(|(_, chunk)| *chunk).collect::<String>(),
+					input
+				);
+
+				for (offset, chunk) in &chunks {
+					assert_eq!(&input[*offset..*offset + chunk.len()], *chunk);
+				}
+				for window in chunks.windows(2) {
+					assert!(!(window[0].1.ends_with('\r') && window[1].1.starts_with('\n')));
+				}
+			}
+		}
+
+		assert_eq!(split_chunks("", 4, 1), [(0, "")]);
+		assert_eq!(split_chunks("abcdef", 4, 4), [(0, "abcdef")]);
+		assert_eq!(split_chunks("abcdefgh", 4, 4), [(0, "abcd"), (4, "efgh")]);
+		assert_eq!(
+			split_chunks("abc\r\ndef", 2, 1),
+			[(0, "abc\r\n"), (5, "def")]
+		);
+		assert_eq!(
+			split_chunks("ab\u{1F980}cd", 2, 1),
+			[(0, "ab\u{1F980}"), (6, "cd")]
+		);
+	}
+
+	#[test]
+	fn test_map_chunks_with() {
+		for input in inputs() {
+			for max_chunks in 1..8 {
+				let normalized = map_chunks_with(&input, max_chunks, 1, |_, chunk| {
+					strings::normalize_newlines(chunk)
+				});
+				assert_eq!(concat(normalized), strings::normalize_newlines(&input));
+
+				let stripped = map_chunks_with(&input, max_chunks, 1, |_, chunk| {
+					strings::strip_newlines(chunk)
+				});
+				assert_eq!(concat(stripped), strings::strip_newlines(&input));
+
+				assert_eq!(
+					map_chunks_with(&input, max_chunks, 1, |offset, chunk| {
+						strings::find_first_c0_control(chunk).map(|index| offset + index)
+					})
+					.into_iter()
+					.flatten()
+					.next(),
+					strings::find_first_c0_control(&input),
+				);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "chunk")]
+	fn test_map_chunks_with_panic() {
+		let _ = map_chunks_with("abcdefgh", 4, 1, |offset, _| {
+			assert!(offset == 0, "chunk");
+		});
+	}
+
+	#[test]
+	fn test_parallel_operations() {
+		let document = "caf\u{E9}\r\nline\rline\n".repeat(2 * MIN_CHUNK_LEN / 16);
+		assert_eq!(
+			normalize_newlines(&document),
+			strings::normalize_newlines(&document)
+		);
+		assert_eq!(
+			strip_newlines(&document),
+			strings::strip_newlines(&document)
+		);
+		assert_eq!(find_first_c0_control(&document), Some(5));
+
+		let mut document = "caf\u{E9} ".repeat(2 * MIN_CHUNK_LEN / 6);
+		assert!(!contains_noncharacter(&document));
+		assert_eq!(find_first_c0_control(&document), None);
+
+		document.push_str("\u{0007}\u{FFFF}");
+		assert!(contains_noncharacter(&document));
+		assert_eq!(find_first_c0_control(&document), Some(document.len() - 4));
+	}
+}
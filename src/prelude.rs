@@ -0,0 +1,20 @@
+pub use crate::codepoints::ascii_set::AsciiSet;
+pub use crate::codepoints::code_point::CodePoint;
+pub use crate::codepoints::code_point_like::CodePointLike;
+pub use crate::codepoints::scalar::InfraScalarValue;
+pub use crate::codepoints::surrogates::InfraUtf16Surrogate;
+pub use crate::cursor::{ByteCursor, CodePointCursor, CodeUnitCursor};
+pub use crate::error::InfraParseError;
+
+#[cfg(feature = "strings")]
+pub use crate::ascii_string::AsciiString;
+#[cfg(feature = "collections")]
+pub use crate::collections::{
+	AsciiCaseInsensitive, DeepClone, List, OrderedMap, OrderedMultiMap, OrderedSet, Queue, Stack,
+};
+#[cfg(feature = "json")]
+pub use crate::json::{InfraNumber, InfraValue};
+#[cfg(feature = "strings")]
+pub use crate::strings::InfraStr;
+#[cfg(feature = "strings")]
+pub use crate::wtf16::{Wtf16Str, Wtf16String};
@@ -0,0 +1,558 @@
+// Byte scanning for the string algorithms.
+//
+// Each scan looks for the first byte of a `ByteSet`. With the `simd`
+// feature on x86-64, bytes are checked 16 at a time with SSE2, which every
+// x86-64 CPU supports. If the `std` feature is enabled too, inputs of at
+// least 32 bytes are checked 32 at a time with AVX2 on CPUs that support
+// it. Support is detected at runtime (and cached by the standard library
+// after the first check), so the same build still runs on CPUs without
+// AVX2. Builds that enable AVX2 at compile time use it without detecting
+// it. Otherwise, bytes are checked one at a time.
+
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+use core::arch::x86_64::{
+	__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_min_epu8, _mm_movemask_epi8,
+	_mm_or_si128, _mm_set1_epi8, _mm_xor_si128,
+};
+#[cfg(all(
+	feature = "simd",
+	target_arch = "x86_64",
+	target_feature = "sse2",
+	any(feature = "std", target_feature = "avx2")
+))]
+use core::arch::x86_64::{
+	__m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_max_epu8, _mm256_min_epu8,
+	_mm256_movemask_epi8, _mm256_or_si256, _mm256_set1_epi8, _mm256_xor_si256,
+};
+
+/// Returns the index of the first byte that's equal to `needle`
+#[inline]
+pub(crate) fn find_byte(bytes: &[u8], needle: u8) -> Option<usize> {
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	{
+		position(bytes, Byte(needle))
+	}
+
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2")))]
+	{
+		find_byte_by_word(bytes, needle)
+	}
+}
+
+/// Returns the index of the first byte that's equal to `needle`, checking a
+/// word at a time like the standard library's `memchr`
+#[cfg(not(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2")))]
+#[inline]
+fn find_byte_by_word(bytes: &[u8], needle: u8) -> Option<usize> {
+	const WORD: usize = core::mem::size_of::<usize>();
+	const LO: usize = usize::MAX / 0xFF;
+	const HI: usize = LO << 7;
+
+	let repeated = LO * usize::from(needle);
+	let mut offset = 0;
+
+	for chunk in bytes.chunks_exact(WORD) {
+		let mut word = [0; WORD];
+		word.copy_from_slice(chunk);
+
+		// A byte of `x` is zero where the chunk has the needle, and the
+		// expression is only non-zero if `x` has a zero byte
+		let x = usize::from_ne_bytes(word) ^ repeated;
+		if x.wrapping_sub(LO) & !x & HI != 0 {
+			break;
+		}
+		offset += WORD;
+	}
+
+	bytes[offset..]
+		.iter()
+		.position(|&b| b == needle)
+		.map(|i| offset + i)
+}
+
+/// Returns the index of the first byte that's equal to either `a` or `b`
+#[inline]
+pub(crate) fn find_either_byte(bytes: &[u8], a: u8, b: u8) -> Option<usize> {
+	position(bytes, EitherByte(a, b))
+}
+
+/// Returns the index of the first ASCII whitespace byte
+#[inline]
+pub(crate) fn find_ascii_whitespace(bytes: &[u8]) -> Option<usize> {
+	position(bytes, AsciiWhitespace)
+}
+
+/// Returns the index of the first byte that isn't ASCII whitespace
+#[inline]
+pub(crate) fn find_non_ascii_whitespace(bytes: &[u8]) -> Option<usize> {
+	position(bytes, NonAsciiWhitespace)
+}
+
+/// Returns the index of the first byte that's less than `bound`
+#[inline]
+pub(crate) fn find_byte_below(bytes: &[u8], bound: u8) -> Option<usize> {
+	match bound.checked_sub(1) {
+		Some(max) => position(bytes, AtMost(max)),
+		None => None,
+	}
+}
+
+/// Returns the index of the first byte that's greater than or equal to `bound`
+#[inline]
+pub(crate) fn find_byte_at_least(bytes: &[u8], bound: u8) -> Option<usize> {
+	position(bytes, AtLeast(bound))
+}
+
+/// Checks if every byte is ASCII
+#[inline]
+pub(crate) fn is_ascii(bytes: &[u8]) -> bool {
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	{
+		position(bytes, AtLeast(0x80)).is_none()
+	}
+
+	// The standard library checks a word at a time
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2")))]
+	{
+		bytes.is_ascii()
+	}
+}
+
+/// Returns the index of the first byte of the set, with the widest vectors
+/// that the target and the CPU support
+#[inline]
+fn position<S: ByteSet>(bytes: &[u8], set: S) -> Option<usize> {
+	#[cfg(all(
+		feature = "simd",
+		target_arch = "x86_64",
+		target_feature = "sse2",
+		any(feature = "std", target_feature = "avx2")
+	))]
+	if bytes.len() >= 32 && has_avx2() {
+		// SAFETY: the CPU supports AVX2
+		return unsafe { position_avx2(bytes, set) };
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	{
+		// SAFETY: SSE2 is enabled for this target
+		unsafe { position_vectors::<__m128i, S>(bytes, set) }
+	}
+
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2")))]
+	{
+		bytes.iter().position(|&b| set.contains(b))
+	}
+}
+
+/// Checks if the CPU supports AVX2
+#[cfg(all(
+	feature = "simd",
+	target_arch = "x86_64",
+	target_feature = "sse2",
+	any(feature = "std", target_feature = "avx2")
+))]
+#[inline]
+fn has_avx2() -> bool {
+	#[cfg(target_feature = "avx2")]
+	{
+		true
+	}
+
+	#[cfg(not(target_feature = "avx2"))]
+	{
+		std::is_x86_feature_detected!("avx2")
+	}
+}
+
+/// Returns the index of the first byte of the set, checking 32 bytes at a
+/// time with AVX2 and the bytes after the last full 32 with SSE2.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[cfg(all(
+	feature = "simd",
+	target_arch = "x86_64",
+	target_feature = "sse2",
+	any(feature = "std", target_feature = "avx2")
+))]
+#[target_feature(enable = "avx2")]
+unsafe fn position_avx2<S: ByteSet>(bytes: &[u8], set: S) -> Option<usize> {
+	let (wide, rest) = bytes.split_at(bytes.len() - bytes.len() % <__m256i as Vector>::LANES);
+	match position_vectors::<__m256i, S>(wide, set) {
+		Some(i) => Some(i),
+		None => position_vectors::<__m128i, S>(rest, set).map(|i| wide.len() + i),
+	}
+}
+
+/// Returns the index of the first byte of the set, checking a vector of
+/// bytes at a time, and checking the bytes after the last full vector one
+/// at a time.
+///
+/// # Safety
+/// The CPU must support the instructions of `V`.
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+#[inline(always)]
+unsafe fn position_vectors<V: Vector, S: ByteSet>(bytes: &[u8], set: S) -> Option<usize> {
+	let mut chunks = bytes.chunks_exact(V::LANES);
+	let mut offset = 0;
+
+	for chunk in &mut chunks {
+		let bits = set.mask(V::load(chunk)).movemask();
+		if bits != 0 {
+			return Some(offset + bits.trailing_zeros() as usize);
+		}
+		offset += V::LANES;
+	}
+
+	chunks
+		.remainder()
+		.iter()
+		.position(|&b| set.contains(b))
+		.map(|i| offset + i)
+}
+
+/// A set of bytes to scan for.
+///
+/// Sets are checked one byte at a time with [`ByteSet::contains()`], or a
+/// vector of bytes at a time with [`ByteSet::mask()`] where the target has
+/// SIMD instructions.
+trait ByteSet: Copy {
+	/// Checks if the set contains a byte
+	fn contains(self, byte: u8) -> bool;
+
+	/// Sets every byte of the vector that the set contains to 0xFF, and
+	/// every other byte to 0x00.
+	///
+	/// # Safety
+	/// The CPU must support the instructions of `V`.
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V;
+}
+
+/// A single byte, which is searched for a word at a time without SIMD
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+#[derive(Clone, Copy)]
+struct Byte(u8);
+
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+impl ByteSet for Byte {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		byte == self.0
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		chunk.eq(self.0)
+	}
+}
+
+/// Either of two bytes
+#[derive(Clone, Copy)]
+struct EitherByte(u8, u8);
+
+impl ByteSet for EitherByte {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		byte == self.0 || byte == self.1
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		chunk.eq(self.0).or(chunk.eq(self.1))
+	}
+}
+
+/// The ASCII whitespace bytes: U+0009 TAB, U+000A LF, U+000C FF, U+000D CR,
+/// and U+0020 SPACE
+#[derive(Clone, Copy)]
+struct AsciiWhitespace;
+
+impl ByteSet for AsciiWhitespace {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		byte.is_ascii_whitespace()
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		let tab_or_lf = chunk.eq(b'\t').or(chunk.eq(b'\n'));
+		let ff_or_cr = chunk.eq(b'\x0C').or(chunk.eq(b'\r'));
+		tab_or_lf.or(ff_or_cr).or(chunk.eq(b' '))
+	}
+}
+
+/// Every byte that isn't ASCII whitespace
+#[derive(Clone, Copy)]
+struct NonAsciiWhitespace;
+
+impl ByteSet for NonAsciiWhitespace {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		!byte.is_ascii_whitespace()
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		AsciiWhitespace.mask(chunk).not()
+	}
+}
+
+/// Every byte that's less than or equal to a bound
+#[derive(Clone, Copy)]
+struct AtMost(u8);
+
+impl ByteSet for AtMost {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		byte <= self.0
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		chunk.at_most(self.0)
+	}
+}
+
+/// Every byte that's greater than or equal to a bound
+#[derive(Clone, Copy)]
+struct AtLeast(u8);
+
+impl ByteSet for AtLeast {
+	#[inline(always)]
+	fn contains(self, byte: u8) -> bool {
+		byte >= self.0
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+	#[inline(always)]
+	unsafe fn mask<V: Vector>(self, chunk: V) -> V {
+		chunk.at_least(self.0)
+	}
+}
+
+/// A SIMD vector of bytes, with the operations that byte sets are checked
+/// with. Comparisons set the bytes that match to 0xFF, and the other bytes
+/// to 0x00. Bytes are compared as unsigned.
+///
+/// # Safety
+/// The CPU must support the vector's instructions to call its methods.
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+trait Vector: Copy {
+	/// The number of bytes in the vector
+	const LANES: usize;
+
+	/// Loads the first [`Vector::LANES`] bytes of a slice, which must be at
+	/// least that long
+	unsafe fn load(bytes: &[u8]) -> Self;
+
+	/// Returns a bit for each byte of the vector, set if the byte's highest
+	/// bit is set, with the first byte in the lowest bit
+	unsafe fn movemask(self) -> u32;
+
+	/// Compares each byte of the vector to `byte`
+	unsafe fn eq(self, byte: u8) -> Self;
+
+	/// Checks which bytes are less than or equal to `bound`
+	unsafe fn at_most(self, bound: u8) -> Self;
+
+	/// Checks which bytes are greater than or equal to `bound`
+	unsafe fn at_least(self, bound: u8) -> Self;
+
+	/// Combines the matching bytes of two vectors
+	unsafe fn or(self, other: Self) -> Self;
+
+	/// Inverts which bytes of the vector match
+	unsafe fn not(self) -> Self;
+}
+
+// SSE2 is enabled for every target that these are compiled for, so they're
+// always safe to call
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "sse2"))]
+impl Vector for __m128i {
+	const LANES: usize = 16;
+
+	#[inline(always)]
+	unsafe fn load(bytes: &[u8]) -> Self {
+		debug_assert!(bytes.len() >= Self::LANES);
+		// `loadu` has no alignment requirement
+		_mm_loadu_si128(bytes.as_ptr().cast())
+	}
+
+	#[inline(always)]
+	unsafe fn movemask(self) -> u32 {
+		_mm_movemask_epi8(self) as u32
+	}
+
+	#[inline(always)]
+	unsafe fn eq(self, byte: u8) -> Self {
+		_mm_cmpeq_epi8(self, _mm_set1_epi8(byte as i8))
+	}
+
+	#[inline(always)]
+	unsafe fn at_most(self, bound: u8) -> Self {
+		_mm_cmpeq_epi8(_mm_min_epu8(self, _mm_set1_epi8(bound as i8)), self)
+	}
+
+	#[inline(always)]
+	unsafe fn at_least(self, bound: u8) -> Self {
+		_mm_cmpeq_epi8(_mm_max_epu8(self, _mm_set1_epi8(bound as i8)), self)
+	}
+
+	#[inline(always)]
+	unsafe fn or(self, other: Self) -> Self {
+		_mm_or_si128(self, other)
+	}
+
+	#[inline(always)]
+	unsafe fn not(self) -> Self {
+		_mm_xor_si128(self, _mm_set1_epi8(-1))
+	}
+}
+
+#[cfg(all(
+	feature = "simd",
+	target_arch = "x86_64",
+	target_feature = "sse2",
+	any(feature = "std", target_feature = "avx2")
+))]
+impl Vector for __m256i {
+	const LANES: usize = 32;
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn load(bytes: &[u8]) -> Self {
+		debug_assert!(bytes.len() >= Self::LANES);
+		// `loadu` has no alignment requirement
+		_mm256_loadu_si256(bytes.as_ptr().cast())
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn movemask(self) -> u32 {
+		_mm256_movemask_epi8(self) as u32
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn eq(self, byte: u8) -> Self {
+		_mm256_cmpeq_epi8(self, _mm256_set1_epi8(byte as i8))
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn at_most(self, bound: u8) -> Self {
+		_mm256_cmpeq_epi8(_mm256_min_epu8(self, _mm256_set1_epi8(bound as i8)), self)
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn at_least(self, bound: u8) -> Self {
+		_mm256_cmpeq_epi8(_mm256_max_epu8(self, _mm256_set1_epi8(bound as i8)), self)
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn or(self, other: Self) -> Self {
+		_mm256_or_si256(self, other)
+	}
+
+	#[inline]
+	#[target_feature(enable = "avx2")]
+	unsafe fn not(self) -> Self {
+		_mm256_xor_si256(self, _mm256_set1_epi8(-1))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Inputs of every length up to a few chunks long, with the needle at
+	/// every position, to cover full chunks and the remainder
+	fn for_each_haystack<F: FnMut(&[u8], Option<usize>)>(needle: u8, mut f: F) {
+		let mut haystack = [b'a'; 100];
+		for len in 0..haystack.len() {
+			f(&haystack[..len], None);
+			for i in 0..len {
+				haystack[i] = needle;
+				f(&haystack[..len], Some(i));
+				haystack[i] = b'a';
+			}
+		}
+	}
+
+	#[test]
+	fn test_find_byte() {
+		for_each_haystack(b'\r', |haystack, expected| {
+			assert_eq!(find_byte(haystack, b'\r'), expected, "{haystack:?}");
+		});
+		assert_eq!(find_byte(&[0x80, 0x0D], b'\r'), Some(1));
+		assert_eq!(find_byte(&[0xFF; 20], 0xFF), Some(0));
+	}
+
+	#[test]
+	fn test_find_either_byte() {
+		for_each_haystack(b'\n', |haystack, expected| {
+			assert_eq!(find_either_byte(haystack, b'\r', b'\n'), expected);
+		});
+		for_each_haystack(b'\r', |haystack, expected| {
+			assert_eq!(find_either_byte(haystack, b'\r', b'\n'), expected);
+		});
+	}
+
+	#[test]
+	fn test_find_ascii_whitespace() {
+		for needle in [b'\t', b'\n', b'\x0C', b'\r', b' '] {
+			for_each_haystack(needle, |haystack, expected| {
+				assert_eq!(find_ascii_whitespace(haystack), expected);
+			});
+		}
+		// U+000B LINE TABULATION isn't ASCII whitespace
+		assert_eq!(find_ascii_whitespace(&[0x0B; 20]), None);
+	}
+
+	#[test]
+	fn test_find_non_ascii_whitespace() {
+		let mut haystack = [b' '; 100];
+		for len in 0..haystack.len() {
+			assert_eq!(find_non_ascii_whitespace(&haystack[..len]), None);
+			for i in 0..len {
+				haystack[i] = b'a';
+				assert_eq!(find_non_ascii_whitespace(&haystack[..len]), Some(i));
+				haystack[i] = b' ';
+			}
+		}
+		assert_eq!(find_non_ascii_whitespace(b"\t\n\x0C\r \x0B"), Some(5));
+	}
+
+	#[test]
+	fn test_find_byte_below_at_least() {
+		for_each_haystack(0x1F, |haystack, expected| {
+			assert_eq!(find_byte_below(haystack, 0x20), expected);
+		});
+		for_each_haystack(0xEF, |haystack, expected| {
+			assert_eq!(find_byte_at_least(haystack, 0xEF), expected);
+		});
+		assert_eq!(find_byte_below(&[0x20; 20], 0x20), None);
+		assert_eq!(find_byte_below(&[0x00; 20], 0x00), None);
+		assert_eq!(find_byte_at_least(&[0xEE; 20], 0xEF), None);
+		assert_eq!(find_byte_at_least(&[0xFF; 20], 0xEF), Some(0));
+		assert_eq!(find_byte_at_least(&[0x00; 20], 0x00), Some(0));
+	}
+
+	#[test]
+	fn test_is_ascii() {
+		for_each_haystack(0x80, |haystack, expected| {
+			assert_eq!(is_ascii(haystack), expected.is_none());
+		});
+		assert!(is_ascii(&[0x7F; 100]));
+		assert!(!is_ascii(&[0xFF; 100]));
+	}
+}
@@ -1,8 +1,52 @@
 extern crate alloc;
-use alloc::{borrow::ToOwned, string::String};
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::str::Chars;
 
-/// Methods from the WHATWG Infra Standard for strings
+use crate::codepoints::scalar::{is_noncharacter, is_noncharacter_codepoint};
+use crate::cursor::CodePointCursor;
+use crate::error::{Found, InfraParseError, ViolationPolicy};
+use crate::scan::{
+	find_ascii_whitespace, find_byte, find_byte_at_least, find_byte_below, find_either_byte,
+	find_non_ascii_whitespace, is_ascii,
+};
+
+/// Methods from the WHATWG Infra Standard for strings.
+///
+/// Each method calls the free function of the same name, so string
+/// operations can be chained like the steps of an algorithm in a
+/// specification. The methods with the Infra Standard's names for the
+/// operations, like [`InfraStr::strip_and_collapse_ascii_whitespace()`],
+/// are aliases of the ones this crate names after `str::trim()`.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::InfraStr;
+///
+/// let value = " Text/HTML;\r\n  charset=utf-8 ";
+/// let normalized = value.strip_and_collapse_ascii_whitespace().ascii_lowercase();
+/// assert_eq!(normalized, "text/html; charset=utf-8");
+///
+/// let tokens: Vec<&str> = "a, b,,c".split_on_commas().collect();
+/// assert_eq!(tokens, ["a", "b", "", "c"]);
+/// ```
 pub trait InfraStr {
+	/// See the documentation for [`is_ascii_fast()`]
+	fn is_ascii_fast(&self) -> bool;
+	/// See the documentation for [`is_isomorphic_string()`]
+	fn is_isomorphic_string(&self) -> bool;
+	/// See the documentation for [`ascii_lowercase()`]
+	fn ascii_lowercase(&self) -> String;
+	/// See the documentation for [`ascii_uppercase()`]
+	fn ascii_uppercase(&self) -> String;
+	/// See the documentation for [`isomorphic_encode()`]
+	fn isomorphic_encode(&self) -> Option<Vec<u8>>;
+	/// See the documentation for [`ascii_encode()`]
+	fn ascii_encode(&self) -> Option<Vec<u8>>;
 	/// See the documentation for [`normalize_newlines()`]
 	fn normalize_newlines(&self) -> String;
 	/// See the documentation for [`strip_newlines()`]
@@ -15,9 +59,85 @@ pub trait InfraStr {
 	fn collect_codepoints<P>(&self, position: &mut usize, predicate: P) -> String
 	where
 		P: FnMut(char) -> bool;
+	/// See the documentation for [`split_on_ascii_whitespace()`]
+	fn split_on_ascii_whitespace(&self) -> SplitOnAsciiWhitespace<'_>;
+	/// See the documentation for [`strictly_split()`]
+	fn strictly_split(&self, delimiter: char) -> StrictlySplit<'_>;
+	/// See the documentation for [`split_on_commas()`]
+	fn split_on_commas(&self) -> SplitOnCommas<'_>;
+	/// See the documentation for [`try_collect_codepoints()`]
+	fn try_collect_codepoints<P>(
+		&self,
+		position: &mut usize,
+		predicate: P,
+	) -> Result<String, InfraParseError>
+	where
+		P: FnMut(char) -> bool;
+	/// See the documentation for [`code_point_substring()`]
+	fn code_point_substring(&self, start: usize, length: usize) -> &str;
+	/// See the documentation for [`try_code_point_substring()`]
+	fn try_code_point_substring(
+		&self,
+		start: usize,
+		length: usize,
+	) -> Result<&str, InfraParseError>;
+	/// See the documentation for [`contains_noncharacter()`]
+	fn contains_noncharacter(&self) -> bool;
+	/// See the documentation for [`find_first_c0_control()`]
+	fn find_first_c0_control(&self) -> Option<usize>;
+	/// See the documentation for [`utf16_indices()`]
+	fn utf16_indices(&self) -> Utf16Indices<'_>;
+	/// See the documentation for [`utf16_char_indices()`]
+	fn utf16_char_indices(&self) -> Utf16CharIndices<'_>;
+
+	/// Removes ASCII whitespace from before and after the string, like
+	/// [`InfraStr::trim_ascii_whitespace()`], under the Infra Standard's
+	/// name for it.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strip-leading-and-trailing-ascii-whitespace
+	fn strip_leading_and_trailing_ascii_whitespace(&self) -> &str {
+		self.trim_ascii_whitespace()
+	}
+
+	/// Removes ASCII whitespace from before and after the string and
+	/// collapses the rest, like [`InfraStr::trim_collapse_ascii_whitespace()`],
+	/// under the Infra Standard's name for it.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace
+	fn strip_and_collapse_ascii_whitespace(&self) -> String {
+		self.trim_collapse_ascii_whitespace()
+	}
 }
 
 impl InfraStr for str {
+	fn is_ascii_fast(&self) -> bool {
+		is_ascii_fast(self)
+	}
+
+	fn is_isomorphic_string(&self) -> bool {
+		is_isomorphic_string(self)
+	}
+
+	fn ascii_lowercase(&self) -> String {
+		ascii_lowercase(self)
+	}
+
+	fn ascii_uppercase(&self) -> String {
+		ascii_uppercase(self)
+	}
+
+	fn isomorphic_encode(&self) -> Option<Vec<u8>> {
+		isomorphic_encode(self)
+	}
+
+	fn ascii_encode(&self) -> Option<Vec<u8>> {
+		ascii_encode(self)
+	}
+
 	fn normalize_newlines(&self) -> String {
 		normalize_newlines(self)
 	}
@@ -40,6 +160,379 @@ impl InfraStr for str {
 	{
 		collect_codepoints(self, position, predicate)
 	}
+
+	fn split_on_ascii_whitespace(&self) -> SplitOnAsciiWhitespace<'_> {
+		split_on_ascii_whitespace(self)
+	}
+
+	fn strictly_split(&self, delimiter: char) -> StrictlySplit<'_> {
+		strictly_split(self, delimiter)
+	}
+
+	fn split_on_commas(&self) -> SplitOnCommas<'_> {
+		split_on_commas(self)
+	}
+
+	fn try_collect_codepoints<P>(
+		&self,
+		position: &mut usize,
+		predicate: P,
+	) -> Result<String, InfraParseError>
+	where
+		P: FnMut(char) -> bool,
+	{
+		try_collect_codepoints(self, position, predicate)
+	}
+
+	fn code_point_substring(&self, start: usize, length: usize) -> &str {
+		code_point_substring(self, start, length)
+	}
+
+	fn try_code_point_substring(
+		&self,
+		start: usize,
+		length: usize,
+	) -> Result<&str, InfraParseError> {
+		try_code_point_substring(self, start, length)
+	}
+
+	fn contains_noncharacter(&self) -> bool {
+		contains_noncharacter(self)
+	}
+
+	fn find_first_c0_control(&self) -> Option<usize> {
+		find_first_c0_control(self)
+	}
+
+	fn utf16_indices(&self) -> Utf16Indices<'_> {
+		utf16_indices(self)
+	}
+
+	fn utf16_char_indices(&self) -> Utf16CharIndices<'_> {
+		utf16_char_indices(self)
+	}
+}
+
+/// Checks if a string is an ASCII string, whose codepoints are all in the
+/// range U+0000 NULL to U+007F DELETE, inclusive.
+///
+/// The string's bytes are checked 16 or 32 at a time with the `simd`
+/// feature, and a machine word at a time otherwise. The other string
+/// operations use this check to take byte-oriented paths for ASCII strings.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-string
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_ascii_fast;
+///
+/// assert!(is_ascii_fast("text/html; charset=utf-8"));
+/// assert!(!is_ascii_fast("caf\u{E9}"));
+/// ```
+#[must_use]
+#[inline]
+pub fn is_ascii_fast(s: &str) -> bool {
+	is_ascii(s.as_bytes())
+}
+
+/// Checks if a string is an isomorphic string, whose codepoints are all in
+/// the range U+0000 NULL to U+00FF (ÿ), inclusive.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#isomorphic-string
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_isomorphic_string;
+///
+/// assert!(is_isomorphic_string("caf\u{E9}"));
+/// assert!(!is_isomorphic_string("\u{1F431}"));
+/// ```
+#[must_use]
+pub fn is_isomorphic_string(s: &str) -> bool {
+	is_ascii_fast(s) || s.chars().all(|c| c <= '\u{00FF}')
+}
+
+/// Replaces every ASCII upper alpha codepoint (A to Z) of a string with
+/// its lowercase form, leaving every other codepoint as-is.
+///
+/// ASCII letters are single bytes that can't appear inside a multi-byte
+/// UTF-8 sequence, so this converts the string's bytes directly, whether
+/// or not it's an ASCII string.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-lowercase
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::ascii_lowercase;
+///
+/// assert_eq!(ascii_lowercase("Content-Type"), String::from("content-type"));
+/// assert_eq!(ascii_lowercase("\u{C9}T\u{C9}"), String::from("\u{C9}t\u{C9}"));
+/// ```
+#[must_use]
+pub fn ascii_lowercase(s: &str) -> String {
+	s.to_ascii_lowercase()
+}
+
+/// Replaces every ASCII lower alpha codepoint (a to z) of a string with
+/// its uppercase form, leaving every other codepoint as-is.
+///
+/// Like [`ascii_lowercase()`], this converts the string's bytes directly.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-uppercase
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::ascii_uppercase;
+///
+/// assert_eq!(ascii_uppercase("get"), String::from("GET"));
+/// assert_eq!(ascii_uppercase("stra\u{DF}e"), String::from("STRA\u{DF}E"));
+/// ```
+#[must_use]
+pub fn ascii_uppercase(s: &str) -> String {
+	s.to_ascii_uppercase()
+}
+
+/// Converts an isomorphic string into a byte sequence, where each byte is
+/// the value of the codepoint at the same index. Returns `None` if the
+/// string isn't an [isomorphic string](is_isomorphic_string()).
+///
+/// ASCII strings are copied as-is, since their bytes are already their
+/// codepoints.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#isomorphic-encode
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::isomorphic_encode;
+///
+/// assert_eq!(isomorphic_encode("caf\u{E9}"), Some(vec![b'c', b'a', b'f', 0xE9]));
+/// assert_eq!(isomorphic_encode("\u{100}"), None);
+/// ```
+#[must_use]
+pub fn isomorphic_encode(s: &str) -> Option<Vec<u8>> {
+	isomorphic_encode_with_policy(s, ViolationPolicy::Error).ok()
+}
+
+/// Converts a string into a byte sequence like [`isomorphic_encode()`], and
+/// handles the codepoints above U+00FF with a [`ViolationPolicy`].
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#isomorphic-encode
+///
+/// # Errors
+/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+/// found the first codepoint above U+00FF.
+pub fn isomorphic_encode_with_policy(
+	s: &str,
+	policy: ViolationPolicy,
+) -> Result<Vec<u8>, InfraParseError> {
+	let mut bytes = Vec::with_capacity(s.len());
+	encode_isomorphic_bytes(s, policy, |b| bytes.extend_from_slice(b))?;
+	Ok(bytes)
+}
+
+/// Converts an isomorphic string into a byte sequence like
+/// [`isomorphic_encode()`], allocated with the given allocator.
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use whatwg_infra::isomorphic_encode_in;
+///
+/// let bytes = isomorphic_encode_in("caf\u{E9}", Global).unwrap();
+/// assert_eq!(bytes, [b'c', b'a', b'f', 0xE9]);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[must_use]
+pub fn isomorphic_encode_in<A: Allocator>(s: &str, alloc: A) -> Option<Vec<u8, A>> {
+	isomorphic_encode_with_policy_in(s, ViolationPolicy::Error, alloc).ok()
+}
+
+/// Converts a string into a byte sequence like
+/// [`isomorphic_encode_with_policy()`], allocated with the given allocator.
+///
+/// # Errors
+/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+/// found the first codepoint above U+00FF.
+#[cfg(feature = "allocator_api")]
+pub fn isomorphic_encode_with_policy_in<A: Allocator>(
+	s: &str,
+	policy: ViolationPolicy,
+	alloc: A,
+) -> Result<Vec<u8, A>, InfraParseError> {
+	let mut bytes = Vec::with_capacity_in(s.len(), alloc);
+	encode_isomorphic_bytes(s, policy, |b| bytes.extend_from_slice(b))?;
+	Ok(bytes)
+}
+
+/// Isomorphic encodes a string, writing the bytes with `write`
+fn encode_isomorphic_bytes<W: FnMut(&[u8])>(
+	s: &str,
+	policy: ViolationPolicy,
+	write: W,
+) -> Result<(), InfraParseError> {
+	encode_codepoints_below(
+		s,
+		0x100,
+		"isomorphic encode",
+		"a codepoint of U+00FF or below",
+		policy,
+		write,
+	)
+}
+
+/// Converts an ASCII string into a byte sequence, where each byte is the
+/// value of the codepoint at the same index. Returns `None` if the string
+/// isn't an [ASCII string](is_ascii_fast()).
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-encode
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::ascii_encode;
+///
+/// assert_eq!(ascii_encode("GET"), Some(b"GET".to_vec()));
+/// assert_eq!(ascii_encode("caf\u{E9}"), None);
+/// ```
+#[must_use]
+pub fn ascii_encode(s: &str) -> Option<Vec<u8>> {
+	ascii_encode_with_policy(s, ViolationPolicy::Error).ok()
+}
+
+/// Converts a string into a byte sequence like [`ascii_encode()`], and
+/// handles the codepoints that aren't ASCII with a [`ViolationPolicy`].
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#ascii-encode
+///
+/// # Errors
+/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+/// found the first codepoint that isn't ASCII.
+pub fn ascii_encode_with_policy(
+	s: &str,
+	policy: ViolationPolicy,
+) -> Result<Vec<u8>, InfraParseError> {
+	let mut bytes = Vec::with_capacity(s.len());
+	encode_ascii_bytes(s, policy, |b| bytes.extend_from_slice(b))?;
+	Ok(bytes)
+}
+
+/// Converts an ASCII string into a byte sequence like [`ascii_encode()`],
+/// allocated with the given allocator.
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use whatwg_infra::ascii_encode_in;
+///
+/// assert_eq!(ascii_encode_in("GET", Global).unwrap(), b"GET");
+/// assert_eq!(ascii_encode_in("caf\u{E9}", Global), None);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[must_use]
+pub fn ascii_encode_in<A: Allocator>(s: &str, alloc: A) -> Option<Vec<u8, A>> {
+	ascii_encode_with_policy_in(s, ViolationPolicy::Error, alloc).ok()
+}
+
+/// Converts a string into a byte sequence like
+/// [`ascii_encode_with_policy()`], allocated with the given allocator.
+///
+/// # Errors
+/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+/// found the first codepoint that isn't ASCII.
+#[cfg(feature = "allocator_api")]
+pub fn ascii_encode_with_policy_in<A: Allocator>(
+	s: &str,
+	policy: ViolationPolicy,
+	alloc: A,
+) -> Result<Vec<u8, A>, InfraParseError> {
+	let mut bytes = Vec::with_capacity_in(s.len(), alloc);
+	encode_ascii_bytes(s, policy, |b| bytes.extend_from_slice(b))?;
+	Ok(bytes)
+}
+
+/// ASCII encodes a string, writing the bytes with `write`
+fn encode_ascii_bytes<W: FnMut(&[u8])>(
+	s: &str,
+	policy: ViolationPolicy,
+	write: W,
+) -> Result<(), InfraParseError> {
+	encode_codepoints_below(s, 0x80, "ASCII encode", "an ASCII codepoint", policy, write)
+}
+
+/// Converts each codepoint below `bound` into the byte with its value, and
+/// handles the others with a policy, writing the bytes with `write` so that
+/// the caller chooses how the byte sequence is allocated
+fn encode_codepoints_below<W: FnMut(&[u8])>(
+	s: &str,
+	bound: u32,
+	operation: &'static str,
+	expected: &'static str,
+	policy: ViolationPolicy,
+	mut write: W,
+) -> Result<(), InfraParseError> {
+	if is_ascii_fast(s) {
+		write(s.as_bytes());
+		return Ok(());
+	}
+
+	for (index, (offset, c)) in s.char_indices().enumerate() {
+		if u32::from(c) < bound {
+			// The bound is at most 0x100, so the codepoint fits in a byte
+			write(&[u32::from(c) as u8]);
+			continue;
+		}
+		match policy {
+			ViolationPolicy::Error => {
+				let error = InfraParseError::new(operation, offset, expected, Found::CodePoint(c));
+				return Err(error.with_code_point_index(index));
+			}
+			ViolationPolicy::Replace => write(b"?"),
+			ViolationPolicy::Ignore => {}
+		}
+	}
+	Ok(())
+}
+
+/// Converts a byte sequence into a string, where each codepoint has the
+/// value of the byte at the same index.
+///
+/// ASCII byte sequences are copied as-is, since they're already valid
+/// UTF-8.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#isomorphic-decode
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::isomorphic_decode;
+///
+/// assert_eq!(isomorphic_decode(&[b'c', b'a', b'f', 0xE9]), String::from("caf\u{E9}"));
+/// ```
+#[must_use]
+pub fn isomorphic_decode(bytes: &[u8]) -> String {
+	if is_ascii(bytes) {
+		// SAFETY: ASCII bytes are always valid UTF-8
+		return String::from(unsafe { core::str::from_utf8_unchecked(bytes) });
+	}
+	bytes.iter().map(|&b| char::from(b)).collect()
 }
 
 /// Replaces every U+000D U+000A pair of codepoints with a single U+000A
@@ -59,9 +552,86 @@ impl InfraStr for str {
 #[must_use]
 #[inline]
 pub fn normalize_newlines(s: &str) -> String {
-	s.replace("\u{000D}\u{000A}", "\u{000A}")
-		.as_str()
-		.replace('\u{000D}', "\u{000A}")
+	let mut result = String::with_capacity(s.len());
+	let mut rest = s;
+
+	// Copy each run of text up to the next CR, then replace the CR (and the
+	// LF after it, if there is one) with a single LF
+	while let Some(cr) = find_byte(rest.as_bytes(), b'\r') {
+		result.push_str(&rest[..cr]);
+		result.push('\u{000A}');
+		rest = &rest[cr + 1..];
+		if let Some(after_lf) = rest.strip_prefix('\u{000A}') {
+			rest = after_lf;
+		}
+	}
+
+	result.push_str(rest);
+	result
+}
+
+/// Normalizes the newlines of a string that arrives in chunks, like
+/// [`normalize_newlines()`] does for a whole string.
+///
+/// A U+000D CR at the end of one chunk followed by a U+000A LF at the start
+/// of the next is normalized to a single LF, like it would be if both were
+/// in the same chunk. The LF for the CR is written immediately, and the
+/// normalizer remembers to skip the LF that may follow, so nothing is held
+/// back between chunks. Chunks without CRs are returned as-is, without
+/// copying them.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::NewlineNormalizer;
+///
+/// let mut normalizer = NewlineNormalizer::new();
+/// let mut normalized = String::new();
+/// for chunk in ["alice\r", "\nbob\r", "\r", "\ncarol"] {
+///     normalized.push_str(&normalizer.push_chunk(chunk));
+/// }
+/// normalizer.finish();
+///
+/// assert_eq!(normalized, "alice\nbob\n\ncarol");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NewlineNormalizer {
+	after_cr: bool,
+}
+
+impl NewlineNormalizer {
+	/// Creates a normalizer for a new string
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { after_cr: false }
+	}
+
+	/// Normalizes the newlines of the next chunk of the string
+	pub fn push_chunk<'a>(&mut self, chunk: &'a str) -> Cow<'a, str> {
+		if chunk.is_empty() {
+			return Cow::Borrowed(chunk);
+		}
+
+		// The LF of a CRLF pair that was split between chunks was already
+		// written for the CR
+		let chunk = match chunk.strip_prefix('\u{000A}') {
+			Some(rest) if self.after_cr => rest,
+			_ => chunk,
+		};
+		self.after_cr = chunk.ends_with('\u{000D}');
+
+		if find_byte(chunk.as_bytes(), b'\r').is_none() {
+			Cow::Borrowed(chunk)
+		} else {
+			Cow::Owned(normalize_newlines(chunk))
+		}
+	}
+
+	/// Ends the string, so that the normalizer can be reused for another
+	/// one. Nothing is held back between chunks, so there's no output left
+	/// to return.
+	pub fn finish(&mut self) {
+		self.after_cr = false;
+	}
 }
 
 /// A string without any U+000A LINE FEED (LF) or U+000D CARIAGE RETURN (CR)
@@ -84,20 +654,31 @@ pub fn normalize_newlines(s: &str) -> String {
 #[must_use]
 #[inline]
 pub fn strip_newlines(s: &str) -> String {
-	let mut result = String::with_capacity(s.len());
-	let mut stripped_codepoints = 0usize;
-
-	for c in s.chars() {
-		if c != '\u{000A}' && c != '\u{000D}' {
-			result.push(c);
-			stripped_codepoints += 1usize;
-		}
+	// Count the newlines first, so that the result is allocated once with
+	// exactly the length it ends up with
+	let mut newlines = 0;
+	let mut rest = s.as_bytes();
+	while let Some(newline) = find_either_byte(rest, b'\n', b'\r') {
+		newlines += 1;
+		rest = &rest[newline + 1..];
 	}
+	if newlines == 0 {
+		return String::from(s);
+	}
+
+	let mut result = String::with_capacity(s.len() - newlines);
+	let mut rest = s;
 
-	if result.len() != s.len() {
-		result.shrink_to(s.len() - stripped_codepoints);
+	// Copy each run of text between newlines in bulk, instead of one
+	// codepoint at a time. Both LF and CR are ASCII, so they can't appear
+	// inside a multi-byte UTF-8 sequence, and the index is always at a
+	// char boundary
+	while let Some(newline) = find_either_byte(rest.as_bytes(), b'\n', b'\r') {
+		result.push_str(&rest[..newline]);
+		rest = &rest[newline + 1..];
 	}
 
+	result.push_str(rest);
 	result
 }
 
@@ -127,7 +708,7 @@ pub fn trim_ascii_whitespace(s: &str) -> &str {
 ///
 /// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
 ///
-/// [whawtg-infra-dfn]: https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace
 ///
 /// # Examples
 /// ```
@@ -139,31 +720,46 @@ pub fn trim_ascii_whitespace(s: &str) -> &str {
 #[must_use]
 pub fn trim_collapse_ascii_whitespace(s: &str) -> String {
 	let mut result = String::with_capacity(s.len());
-	let mut last_seen_whitespace = false;
-
-	for c in s.chars() {
-		if c.is_ascii_whitespace() {
-			if !last_seen_whitespace {
-				last_seen_whitespace = true;
-				result.push('\u{0020}');
-				continue;
+	let mut rest = s;
+
+	// Copy each run of text between whitespace in bulk, separated by single
+	// spaces. Whitespace is ASCII, so every index found is at a char boundary
+	while let Some(start) = find_non_ascii_whitespace(rest.as_bytes()) {
+		if !result.is_empty() {
+			result.push('\u{0020}');
+		}
+		rest = &rest[start..];
+
+		match find_ascii_whitespace(rest.as_bytes()) {
+			Some(end) => {
+				result.push_str(&rest[..end]);
+				rest = &rest[end..];
+			}
+			None => {
+				result.push_str(rest);
+				break;
 			}
-		} else {
-			last_seen_whitespace = false;
-			result.push(c);
 		}
 	}
 
-	trim_ascii_whitespace(result.as_str()).to_owned()
+	result
 }
 
 /// Collects a sequence of Unicode codepoints given a predicate function
 /// and position to move forward.
 ///
+/// `position` is a byte offset into `s`. Collecting starts at `position`
+/// and moves it past the collected codepoints, so each call only looks at
+/// the rest of the string, and collecting a string piece by piece takes
+/// time linear in its length.
+///
 /// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
 ///
 /// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#collect-a-sequence-of-code-points
 ///
+/// # Panics
+/// Panics if `position` is inside the string, but not on a codepoint boundary.
+///
 /// # Examples
 /// ```
 /// use whatwg_infra::collect_codepoints;
@@ -175,37 +771,716 @@ pub fn trim_collapse_ascii_whitespace(s: &str) -> String {
 /// assert_eq!(collected, String::from("test"));
 /// assert_eq!(position, 4);
 /// ```
-pub fn collect_codepoints<P>(s: &str, position: &mut usize, mut predicate: P) -> String
+pub fn collect_codepoints<P>(s: &str, position: &mut usize, predicate: P) -> String
 where
 	P: FnMut(char) -> bool,
 {
-	if s.is_empty() || position >= &mut s.len() {
-		return String::new();
+	match try_collect_codepoints(s, position, predicate) {
+		Ok(collected) => collected,
+		Err(_) => panic!("position {} is not a char boundary of the string", position),
 	}
+}
 
-	let mut result = String::with_capacity(s.len() - *position);
-	let rest = s.chars().skip(*position);
-	let starting_position = *position;
-
-	for c in rest {
-		if position < &mut s.len() && predicate(c) {
-			*position += 1;
-		} else {
-			break;
-		}
+/// Collects a sequence of Unicode codepoints like [`collect_codepoints()`],
+/// but returns an error instead of panicking if `position` is inside the
+/// string, but not on a codepoint boundary. `position` isn't moved then.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#collect-a-sequence-of-code-points
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{try_collect_codepoints, Found};
+///
+/// let value = "\u{E9}t\u{E9}!";
+/// let mut position = 1usize;
+/// let error = try_collect_codepoints(value, &mut position, |c| c != '!').unwrap_err();
+/// assert_eq!(error.found(), Found::Byte(0xA9));
+/// assert_eq!(position, 1);
+///
+/// position = 0;
+/// let collected = try_collect_codepoints(value, &mut position, |c| c != '!');
+/// assert_eq!(collected, Ok(String::from("\u{E9}t\u{E9}")));
+/// assert_eq!(position, 5);
+/// ```
+///
+/// # Errors
+/// Returns an [`InfraParseError`] that found the byte at `position` if
+/// it's not on a codepoint boundary.
+pub fn try_collect_codepoints<P>(
+	s: &str,
+	position: &mut usize,
+	predicate: P,
+) -> Result<String, InfraParseError>
+where
+	P: FnMut(char) -> bool,
+{
+	if *position >= s.len() {
+		return Ok(String::new());
 	}
 
-	result.push_str(&s[starting_position..*position]);
-	if result.len() < s.len() - *position {
-		result.shrink_to_fit();
-	}
+	let rest = s.get(*position..).ok_or_else(|| {
+		InfraParseError::new(
+			"collect a sequence of codepoints",
+			*position,
+			"a codepoint boundary",
+			Found::Byte(s.as_bytes()[*position]),
+		)
+	})?;
+	let mut cursor = CodePointCursor::new(rest);
+	let collected = cursor.collect_codepoints(predicate);
+	*position += cursor.byte_offset();
 
-	result
+	Ok(collected)
 }
 
-#[cfg(test)]
-mod test {
-	use super::*;
+/// Returns the substring of a string that starts at the codepoint with index
+/// `start` and is `length` codepoints long.
+///
+/// Indices count codepoints, not bytes, so they're the same as positions in
+/// the Infra Standard's string algorithms.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#code-point-substring
+///
+/// # Panics
+/// Panics if the string has fewer than `start + length` codepoints.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::code_point_substring;
+///
+/// assert_eq!(code_point_substring("\u{E9}t\u{E9}!", 1, 2), "t\u{E9}");
+/// assert_eq!(code_point_substring("\u{E9}t\u{E9}!", 4, 0), "");
+/// ```
+#[must_use]
+pub fn code_point_substring(s: &str, start: usize, length: usize) -> &str {
+	match try_code_point_substring(s, start, length) {
+		Ok(substring) => substring,
+		Err(_) => panic!(
+			"codepoints {} to {} are out of bounds of the string",
+			start,
+			start.saturating_add(length)
+		),
+	}
+}
+
+/// Returns the substring of a string like [`code_point_substring()`], but
+/// returns an error instead of panicking if the string has fewer than
+/// `start + length` codepoints.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#code-point-substring
+///
+/// # Errors
+/// Returns an [`InfraParseError`] that found the end of the string at the
+/// codepoint where it ended.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{try_code_point_substring, Found};
+///
+/// assert_eq!(try_code_point_substring("\u{E9}t\u{E9}", 1, 2), Ok("t\u{E9}"));
+///
+/// let error = try_code_point_substring("\u{E9}t\u{E9}", 1, 3).unwrap_err();
+/// assert_eq!(error.offset(), 5);
+/// assert_eq!(error.code_point_index(), Some(3));
+/// assert_eq!(error.found(), Found::EndOfInput);
+/// ```
+pub fn try_code_point_substring(
+	s: &str,
+	start: usize,
+	length: usize,
+) -> Result<&str, InfraParseError> {
+	let mut cursor = CodePointCursor::new(s);
+	if cursor.advance_by(start) == start {
+		let substring = cursor.take_codepoints(length);
+		if cursor.code_point_index() - start == length {
+			return Ok(substring);
+		}
+	}
+	Err(cursor.error("take a codepoint substring", "more codepoints"))
+}
+
+/// Splits a string into its tokens separated by ASCII whitespace,
+/// skipping the whitespace before, between, and after them.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#split-on-ascii-whitespace
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::split_on_ascii_whitespace;
+///
+/// let tokens: Vec<&str> = split_on_ascii_whitespace(" foo\tbar\n\nbaz ").collect();
+/// assert_eq!(tokens, vec!["foo", "bar", "baz"]);
+///
+/// assert_eq!(split_on_ascii_whitespace("  ").next(), None);
+/// ```
+pub fn split_on_ascii_whitespace(s: &str) -> SplitOnAsciiWhitespace<'_> {
+	SplitOnAsciiWhitespace { rest: s }
+}
+
+/// Splits a string on every occurrence of a delimiter, keeping empty
+/// tokens. A string without the delimiter, including the empty string,
+/// splits into one token.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#strictly-split
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::strictly_split;
+///
+/// let tokens: Vec<&str> = strictly_split("a;;b;", ';').collect();
+/// assert_eq!(tokens, vec!["a", "", "b", ""]);
+///
+/// assert_eq!(strictly_split("", ';').collect::<Vec<_>>(), vec![""]);
+/// ```
+pub fn strictly_split(s: &str, delimiter: char) -> StrictlySplit<'_> {
+	StrictlySplit {
+		rest: Some(s),
+		delimiter,
+	}
+}
+
+/// Splits a string on U+002C (,) codepoints, and removes ASCII whitespace
+/// from before and after each token.
+///
+/// Tokens may be empty, but a comma at the end of the string doesn't start
+/// a new token, and the empty string has no tokens.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#split-on-commas
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::split_on_commas;
+///
+/// let tokens: Vec<&str> = split_on_commas(" gzip, , br ,").collect();
+/// assert_eq!(tokens, vec!["gzip", "", "br"]);
+/// ```
+pub fn split_on_commas(s: &str) -> SplitOnCommas<'_> {
+	SplitOnCommas { rest: s }
+}
+
+/// An iterator over the tokens of a string separated by ASCII whitespace,
+/// created by [`split_on_ascii_whitespace()`]
+#[derive(Clone, Debug)]
+pub struct SplitOnAsciiWhitespace<'a> {
+	rest: &'a str,
+}
+
+impl<'a> Iterator for SplitOnAsciiWhitespace<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		// ASCII whitespace can't appear inside a multi-byte UTF-8 sequence,
+		// so the indices are always at char boundaries
+		let start = match find_non_ascii_whitespace(self.rest.as_bytes()) {
+			Some(start) => start,
+			None => {
+				self.rest = "";
+				return None;
+			}
+		};
+		let rest = &self.rest[start..];
+		let end = find_ascii_whitespace(rest.as_bytes()).unwrap_or(rest.len());
+		let (token, rest) = rest.split_at(end);
+		self.rest = rest;
+		Some(token)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Each token but the last is followed by at least one whitespace byte
+		(0, Some((self.rest.len() + 1) / 2))
+	}
+}
+
+impl DoubleEndedIterator for SplitOnAsciiWhitespace<'_> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let bytes = self.rest.as_bytes();
+		let end = match bytes.iter().rposition(|b| !b.is_ascii_whitespace()) {
+			Some(last) => last + 1,
+			None => {
+				self.rest = "";
+				return None;
+			}
+		};
+		let start = bytes[..end]
+			.iter()
+			.rposition(u8::is_ascii_whitespace)
+			.map_or(0, |i| i + 1);
+		let token = &self.rest[start..end];
+		self.rest = &self.rest[..start];
+		Some(token)
+	}
+}
+
+impl FusedIterator for SplitOnAsciiWhitespace<'_> {}
+
+/// An iterator over the tokens of a string separated by a delimiter,
+/// created by [`strictly_split()`]
+#[derive(Clone, Debug)]
+pub struct StrictlySplit<'a> {
+	rest: Option<&'a str>,
+	delimiter: char,
+}
+
+impl<'a> Iterator for StrictlySplit<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let rest = self.rest?;
+		match rest.find(self.delimiter) {
+			Some(end) => {
+				self.rest = Some(&rest[end + self.delimiter.len_utf8()..]);
+				Some(&rest[..end])
+			}
+			None => {
+				self.rest = None;
+				Some(rest)
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.rest {
+			Some(rest) => (1, Some(rest.len() / self.delimiter.len_utf8() + 1)),
+			None => (0, Some(0)),
+		}
+	}
+}
+
+impl DoubleEndedIterator for StrictlySplit<'_> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let rest = self.rest?;
+		match rest.rfind(self.delimiter) {
+			Some(start) => {
+				self.rest = Some(&rest[..start]);
+				Some(&rest[start + self.delimiter.len_utf8()..])
+			}
+			None => {
+				self.rest = None;
+				Some(rest)
+			}
+		}
+	}
+}
+
+impl FusedIterator for StrictlySplit<'_> {}
+
+/// An iterator over the tokens of a string separated by commas, with ASCII
+/// whitespace removed from before and after each token, created by
+/// [`split_on_commas()`]
+#[derive(Clone, Debug)]
+pub struct SplitOnCommas<'a> {
+	rest: &'a str,
+}
+
+impl<'a> Iterator for SplitOnCommas<'a> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.rest.is_empty() {
+			return None;
+		}
+		let token = match find_byte(self.rest.as_bytes(), b',') {
+			Some(end) => {
+				let token = &self.rest[..end];
+				self.rest = &self.rest[end + 1..];
+				token
+			}
+			None => core::mem::take(&mut self.rest),
+		};
+		Some(trim_ascii_whitespace(token))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.rest.is_empty() {
+			(0, Some(0))
+		} else {
+			(1, Some(self.rest.len()))
+		}
+	}
+}
+
+impl DoubleEndedIterator for SplitOnCommas<'_> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.rest.is_empty() {
+			return None;
+		}
+		// A comma at the end doesn't start a token, so the last token is
+		// the one before it
+		let body = self.rest.strip_suffix(',').unwrap_or(self.rest);
+		let token = match body.bytes().rposition(|b| b == b',') {
+			Some(comma) => {
+				// Keep the comma, so it's still at the end of the tokens before it
+				self.rest = &self.rest[..=comma];
+				&body[comma + 1..]
+			}
+			None => {
+				self.rest = "";
+				body
+			}
+		};
+		Some(trim_ascii_whitespace(token))
+	}
+}
+
+impl FusedIterator for SplitOnCommas<'_> {}
+
+/// Returns an iterator over the UTF-16 code units of a string, with the
+/// index of each code unit.
+///
+/// The Infra Standard's strings are sequences of code units, so the
+/// specifications built on it, like the selection APIs of HTML, count their
+/// positions and lengths in code units, and a codepoint above U+FFFF takes
+/// up two of them.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string-length
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::utf16_indices;
+///
+/// let units: Vec<(usize, u16)> = utf16_indices("a\u{1F431}b").collect();
+/// assert_eq!(units, [(0, 0x61), (1, 0xD83D), (2, 0xDC31), (3, 0x62)]);
+/// ```
+pub fn utf16_indices(s: &str) -> Utf16Indices<'_> {
+	Utf16Indices {
+		chars: s.chars(),
+		index: 0,
+		trailing: None,
+	}
+}
+
+/// Returns an iterator over the codepoints of a string, with the index of
+/// the first UTF-16 code unit of each codepoint.
+///
+/// It's like [`str::char_indices()`], but with the indices that the
+/// specifications count in code units instead of byte offsets.
+///
+/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string-length
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::utf16_char_indices;
+///
+/// let mut iter = utf16_char_indices("\u{1F431}\u{E9}!");
+/// assert_eq!(iter.next(), Some((0, '\u{1F431}')));
+/// assert_eq!(iter.next(), Some((2, '\u{E9}')));
+/// assert_eq!(iter.offset(), 3);
+/// assert_eq!(iter.as_str(), "!");
+/// ```
+pub fn utf16_char_indices(s: &str) -> Utf16CharIndices<'_> {
+	Utf16CharIndices {
+		chars: s.chars(),
+		index: 0,
+	}
+}
+
+/// An iterator over the UTF-16 code units of a string and their indices,
+/// created by [`utf16_indices()`]
+#[derive(Clone, Debug)]
+pub struct Utf16Indices<'a> {
+	chars: Chars<'a>,
+	index: usize,
+	/// The trailing surrogate of the last codepoint, if it was above U+FFFF
+	/// and the iterator has only returned its leading surrogate
+	trailing: Option<u16>,
+}
+
+impl Utf16Indices<'_> {
+	/// Returns the index of the next code unit, which is also the number of
+	/// code units returned so far
+	#[must_use]
+	pub fn offset(&self) -> usize {
+		self.index
+	}
+}
+
+impl Iterator for Utf16Indices<'_> {
+	type Item = (usize, u16);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let unit = match self.trailing.take() {
+			Some(unit) => unit,
+			None => {
+				let mut buffer = [0; 2];
+				let units = self.chars.next()?.encode_utf16(&mut buffer);
+				if let [_, trailing] = *units {
+					self.trailing = Some(trailing);
+				}
+				units[0]
+			}
+		};
+		let index = self.index;
+		self.index += 1;
+		Some((index, unit))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// Each byte of UTF-8 is at most one code unit of UTF-16, and each
+		// codepoint of up to 4 bytes is at least one
+		let bytes = self.chars.as_str().len();
+		let pending = usize::from(self.trailing.is_some());
+		((bytes + 3) / 4 + pending, Some(bytes + pending))
+	}
+}
+
+impl FusedIterator for Utf16Indices<'_> {}
+
+/// An iterator over the codepoints of a string and the UTF-16 code unit
+/// indices they start at, created by [`utf16_char_indices()`]
+#[derive(Clone, Debug)]
+pub struct Utf16CharIndices<'a> {
+	chars: Chars<'a>,
+	index: usize,
+}
+
+impl<'a> Utf16CharIndices<'a> {
+	/// Returns the index of the first code unit of the next codepoint,
+	/// which is also the number of code units before it
+	#[must_use]
+	pub fn offset(&self) -> usize {
+		self.index
+	}
+
+	/// Returns the rest of the string, from the next codepoint to the end
+	#[must_use]
+	pub fn as_str(&self) -> &'a str {
+		self.chars.as_str()
+	}
+}
+
+impl Iterator for Utf16CharIndices<'_> {
+	type Item = (usize, char);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let c = self.chars.next()?;
+		let index = self.index;
+		self.index += c.len_utf16();
+		Some((index, c))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.chars.size_hint()
+	}
+}
+
+impl FusedIterator for Utf16CharIndices<'_> {}
+
+/// Checks if a string contains a [noncharacter](crate::is_noncharacter).
+///
+/// This gives the same result as checking each codepoint with
+/// [`is_noncharacter()`](crate::is_noncharacter), but only decodes the
+/// codepoints that could be noncharacters, which all start with a byte of
+/// 0xEF or above in UTF-8. [`contains_noncharacter_const()`] is a version
+/// that can be evaluated at compile time.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::contains_noncharacter;
+///
+/// assert!(contains_noncharacter("a\u{FDD0}b"));
+/// assert!(contains_noncharacter("\u{10FFFF}"));
+/// assert!(!contains_noncharacter("plain text \u{FFFD}"));
+/// ```
+#[must_use]
+pub fn contains_noncharacter(s: &str) -> bool {
+	let mut rest = s;
+	while let Some(candidate) = find_byte_at_least(rest.as_bytes(), 0xEF) {
+		// Bytes of 0xEF and above always start a multi-byte sequence
+		rest = &rest[candidate..];
+		let mut chars = rest.chars();
+		if chars.next().map_or(false, is_noncharacter) {
+			return true;
+		}
+		rest = chars.as_str();
+	}
+	false
+}
+
+/// Returns the byte index of the first [C0 control](crate::is_c0_control)
+/// codepoint of a string, if there is one.
+///
+/// C0 controls are U+0000 to U+001F, which are all ASCII, so this checks
+/// the string's bytes directly. [`find_first_c0_control_const()`] is a
+/// version that can be evaluated at compile time.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::find_first_c0_control;
+///
+/// assert_eq!(find_first_c0_control("caf\u{E9}\u{0007}"), Some(5));
+/// assert_eq!(find_first_c0_control("no controls"), None);
+/// ```
+#[must_use]
+pub fn find_first_c0_control(s: &str) -> Option<usize> {
+	find_byte_below(s.as_bytes(), 0x20)
+}
+
+/// Checks if a string contains a [noncharacter](crate::is_noncharacter),
+/// like [`contains_noncharacter()`], but can be evaluated at compile time,
+/// for checking string literals and embedded tables while building.
+///
+/// This checks one byte at a time, so [`contains_noncharacter()`] is
+/// faster at runtime.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::contains_noncharacter_const;
+///
+/// const GREETING: &str = "hello \u{1F44B}";
+/// const _: () = assert!(!contains_noncharacter_const(GREETING));
+///
+/// assert!(contains_noncharacter_const("\u{FFFF}"));
+/// ```
+#[must_use]
+pub const fn contains_noncharacter_const(s: &str) -> bool {
+	let bytes = s.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		// Noncharacters are encoded as 3 bytes starting with 0xEF (U+FDD0 to
+		// U+FFFF) or 4 bytes starting with 0xF0 to 0xF4. Every other byte,
+		// including continuation bytes, is below 0xEF
+		let lead = bytes[i];
+		if lead < 0xEF {
+			i += 1;
+			continue;
+		}
+
+		let (cp, len) = if lead == 0xEF {
+			let cp = (lead as u32 & 0x0F) << 12
+				| (bytes[i + 1] as u32 & 0x3F) << 6
+				| (bytes[i + 2] as u32 & 0x3F);
+			(cp, 3)
+		} else {
+			let cp = (lead as u32 & 0x07) << 18
+				| (bytes[i + 1] as u32 & 0x3F) << 12
+				| (bytes[i + 2] as u32 & 0x3F) << 6
+				| (bytes[i + 3] as u32 & 0x3F);
+			(cp, 4)
+		};
+		if is_noncharacter_codepoint(cp) {
+			return true;
+		}
+		i += len;
+	}
+	false
+}
+
+/// Returns the byte index of the first [C0 control](crate::is_c0_control)
+/// codepoint of a string, like [`find_first_c0_control()`], but can be
+/// evaluated at compile time.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::find_first_c0_control_const;
+///
+/// const NAME: &str = "Content-Type";
+/// const _: () = assert!(find_first_c0_control_const(NAME).is_none());
+///
+/// assert_eq!(find_first_c0_control_const("a\tb"), Some(1));
+/// ```
+#[must_use]
+pub const fn find_first_c0_control_const(s: &str) -> Option<usize> {
+	let bytes = s.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] < 0x20 {
+			return Some(i);
+		}
+		i += 1;
+	}
+	None
+}
+
+/// Checks if a string is an [isomorphic string](is_isomorphic_string()),
+/// but can be evaluated at compile time.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::is_isomorphic_string_const;
+///
+/// const _: () = assert!(is_isomorphic_string_const("caf\u{E9}"));
+/// const _: () = assert!(!is_isomorphic_string_const("\u{100}"));
+/// ```
+#[must_use]
+pub const fn is_isomorphic_string_const(s: &str) -> bool {
+	// U+0080 to U+00FF are encoded as 2 bytes starting with 0xC2 or 0xC3,
+	// and every higher codepoint as a sequence starting with a higher
+	// byte. Continuation bytes are all below 0xC0
+	let bytes = s.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] > 0xC3 {
+			return false;
+		}
+		i += 1;
+	}
+	true
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_utf16_indices() {
+		let s = "a\u{E9}\u{1F431}\u{FFFF}\u{10000}";
+		let units: Vec<u16> = s.encode_utf16().collect();
+		let indexed: Vec<(usize, u16)> = s.utf16_indices().collect();
+		assert_eq!(
+			indexed,
+			units.iter().copied().enumerate().collect::<Vec<_>>()
+		);
+
+		let mut iter = utf16_indices(s);
+		for _ in 0..3 {
+			let (low, high) = iter.size_hint();
+			let rest = units.len() - iter.offset();
+			assert!(low <= rest && rest <= high.unwrap());
+			iter.next();
+		}
+		assert_eq!(iter.offset(), 3);
+		assert_eq!(iter.next(), Some((3, 0xDC31)));
+		assert_eq!(utf16_indices("").next(), None);
+	}
+
+	#[test]
+	fn test_utf16_char_indices() {
+		let s = "a\u{1F431}\u{E9}\u{10FFFF}z";
+		let indices: Vec<(usize, char)> = s.utf16_char_indices().collect();
+		assert_eq!(
+			indices,
+			[
+				(0, 'a'),
+				(1, '\u{1F431}'),
+				(3, '\u{E9}'),
+				(4, '\u{10FFFF}'),
+				(6, 'z')
+			]
+		);
+
+		let mut iter = utf16_char_indices(s);
+		iter.by_ref().take(4).for_each(drop);
+		assert_eq!((iter.offset(), iter.as_str()), (6, "z"));
+		iter.next();
+		assert_eq!(iter.offset(), s.encode_utf16().count());
+		assert_eq!(iter.next(), None);
+	}
 
 	#[test]
 	fn test_normalize_newlines() {
@@ -215,6 +1490,49 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn test_normalize_newlines_mixed() {
+		assert_eq!(normalize_newlines(""), String::new());
+		assert_eq!(
+			normalize_newlines("no newlines"),
+			String::from("no newlines")
+		);
+		assert_eq!(
+			normalize_newlines("\r\r\n\n\n\r"),
+			String::from("\n\n\n\n\n")
+		);
+		assert_eq!(normalize_newlines("\n\r"), String::from("\n\n"));
+		assert_eq!(normalize_newlines("é\r\n😀\rü"), String::from("é\n😀\nü"));
+	}
+
+	#[test]
+	fn test_strip_newlines_runs() {
+		assert_eq!(strip_newlines("abc"), String::from("abc"));
+		assert_eq!(strip_newlines("\nab\r\ncd\r"), String::from("abcd"));
+		assert_eq!(strip_newlines("é\n😀\r\nü"), String::from("é😀ü"));
+	}
+
+	#[test]
+	fn test_strip_newlines_exact_capacity() {
+		// Every string of up to 6 codepoints from a small alphabet
+		let alphabet = ['a', '\n', '\r', '\u{00E9}'];
+		let mut s = String::new();
+		for len in 0..=6u32 {
+			for mut n in 0..alphabet.len().pow(len) {
+				s.clear();
+				for _ in 0..len {
+					s.push(alphabet[n % alphabet.len()]);
+					n /= alphabet.len();
+				}
+
+				let stripped = strip_newlines(&s);
+				let expected: String = s.chars().filter(|&c| c != '\n' && c != '\r').collect();
+				assert_eq!(stripped, expected, "{s:?}");
+				assert_eq!(stripped.capacity(), stripped.len(), "{s:?}");
+			}
+		}
+	}
+
 	#[test]
 	fn test_strip_newlines_empty() {
 		assert_eq!("\r\r\n\n\r\n".strip_newlines(), String::from(""));
@@ -251,6 +1569,20 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn test_trim_collapse_ascii_whitespace_runs() {
+		assert_eq!(trim_collapse_ascii_whitespace(""), String::new());
+		assert_eq!(
+			trim_collapse_ascii_whitespace(" \t\n\x0C\r "),
+			String::new()
+		);
+		assert_eq!(trim_collapse_ascii_whitespace("cat"), String::from("cat"));
+		assert_eq!(
+			trim_collapse_ascii_whitespace("\u{00E9}t\u{00E9}\t\u{1F431}\x0B  \u{00A0} "),
+			String::from("\u{00E9}t\u{00E9} \u{1F431}\x0B \u{00A0}")
+		);
+	}
+
 	#[test]
 	fn test_collect_codepoints_empty() {
 		let mut position = 0usize;
@@ -286,4 +1618,369 @@ mod test {
 
 		assert_eq!(collected, String::from("Apple    Banana    Orange"));
 	}
+
+	#[test]
+	fn test_collect_codepoints_multibyte() {
+		let value = "\u{00E9}t\u{00E9} \u{1F431}\u{1F431}!";
+		let mut position = 0usize;
+		let mut pieces = alloc::vec::Vec::new();
+		while position < value.len() {
+			let piece = collect_codepoints(value, &mut position, |c| !c.is_ascii_whitespace());
+			pieces.push(piece);
+			collect_codepoints(value, &mut position, |c| c.is_ascii_whitespace());
+		}
+
+		assert_eq!(pieces, ["\u{00E9}t\u{00E9}", "\u{1F431}\u{1F431}!"]);
+		assert_eq!(position, value.len());
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_collect_codepoints_not_boundary() {
+		let mut position = 1usize;
+		let _ = collect_codepoints("\u{00E9}", &mut position, |_| true);
+	}
+
+	#[test]
+	fn test_split_on_ascii_whitespace() {
+		let split = |s| split_on_ascii_whitespace(s).collect::<alloc::vec::Vec<_>>();
+		assert!(split("").is_empty());
+		assert!(split(" \t\n\x0C\r").is_empty());
+		assert_eq!(split("a"), ["a"]);
+		assert_eq!(
+			split("\u{00E9}\u{00A0}b  \x0Bc\r\n"),
+			["\u{00E9}\u{00A0}b", "\x0Bc"]
+		);
+	}
+
+	#[test]
+	fn test_strictly_split() {
+		let split = |s, d| strictly_split(s, d).collect::<alloc::vec::Vec<_>>();
+		assert_eq!(split("", ','), [""]);
+		assert_eq!(split(",", ','), ["", ""]);
+		assert_eq!(split(" a ,b", ','), [" a ", "b"]);
+		assert_eq!(split("x\u{1F431}y\u{1F431}", '\u{1F431}'), ["x", "y", ""]);
+	}
+
+	#[test]
+	fn test_split_on_commas() {
+		let split = |s| split_on_commas(s).collect::<alloc::vec::Vec<_>>();
+		assert!(split("").is_empty());
+		assert_eq!(split(","), [""]);
+		assert_eq!(split(" "), [""]);
+		assert_eq!(split("a,"), ["a"]);
+		assert_eq!(split(",a"), ["", "a"]);
+		assert_eq!(split("\ta b ,, c\u{00A0}"), ["a b", "", "c\u{00A0}"]);
+	}
+
+	#[test]
+	fn test_split_iterators_reversed() {
+		use alloc::vec::Vec;
+
+		fn check<'a, I>(split: I)
+		where
+			I: DoubleEndedIterator<Item = &'a str> + Clone,
+		{
+			let forward: Vec<_> = split.clone().collect();
+			let (lower, upper) = split.size_hint();
+			assert!(lower <= forward.len() && forward.len() <= upper.unwrap());
+
+			let mut backward: Vec<_> = split.clone().rev().collect();
+			backward.reverse();
+			assert_eq!(forward, backward);
+
+			// Alternate between the two ends
+			let mut split = split;
+			let mut front = Vec::new();
+			let mut back = Vec::new();
+			while let Some(token) = split.next() {
+				front.push(token);
+				match split.next_back() {
+					Some(token) => back.push(token),
+					None => break,
+				}
+			}
+			assert_eq!(split.size_hint().0, 0);
+			front.extend(back.into_iter().rev());
+			assert_eq!(forward, front);
+		}
+
+		for s in [
+			"",
+			",",
+			",,",
+			" ",
+			"a,",
+			",a",
+			" a ,b,  ,c , ",
+			"\ta b ,, c\u{00A0}",
+			" foo\tbar\n\nbaz ",
+			"\u{00E9}\u{00A0}b  \x0Bc\r\n",
+			"x\u{1F431}y\u{1F431}",
+		] {
+			check(split_on_ascii_whitespace(s));
+			check(strictly_split(s, ','));
+			check(strictly_split(s, '\u{1F431}'));
+			check(split_on_commas(s));
+		}
+	}
+
+	#[test]
+	fn test_is_ascii_isomorphic_string() {
+		assert!(is_ascii_fast(""));
+		assert!("\u{0000}\u{007F}".is_ascii_fast());
+		assert!(!"\u{0080}".is_ascii_fast());
+		assert!("\u{0000}\u{00FF}".is_isomorphic_string());
+		assert!(!"\u{00FF}\u{0100}".is_isomorphic_string());
+	}
+
+	#[test]
+	fn test_ascii_case() {
+		assert_eq!("".ascii_lowercase(), "");
+		assert_eq!("AZaz@[`{".ascii_lowercase(), "azaz@[`{");
+		assert_eq!("AZaz@[`{".ascii_uppercase(), "AZAZ@[`{");
+		// Only ASCII letters change, including in non-ASCII strings
+		assert_eq!(
+			"\u{212A}\u{C9}\u{E9}A".ascii_lowercase(),
+			"\u{212A}\u{C9}\u{E9}a"
+		);
+		assert_eq!("\u{131}\u{E9}a".ascii_uppercase(), "\u{131}\u{E9}A");
+	}
+
+	#[test]
+	fn test_isomorphic_encode_decode() {
+		assert_eq!("".isomorphic_encode(), Some(Vec::new()));
+		assert_eq!("ab".isomorphic_encode(), Some(alloc::vec![b'a', b'b']));
+		assert_eq!(
+			"\u{80}\u{FF}".isomorphic_encode(),
+			Some(alloc::vec![0x80, 0xFF])
+		);
+		assert_eq!("a\u{1F431}".isomorphic_encode(), None);
+
+		let all_bytes: Vec<u8> = (0..=u8::MAX).collect();
+		let decoded = isomorphic_decode(&all_bytes);
+		assert_eq!(decoded.chars().count(), 256);
+		assert_eq!(decoded.isomorphic_encode(), Some(all_bytes));
+		assert_eq!(isomorphic_decode(b"abc"), "abc");
+	}
+
+	#[cfg(feature = "allocator_api")]
+	#[test]
+	fn test_encode_in_allocator() {
+		use alloc::alloc::{AllocError, Global, Layout};
+		use core::cell::Cell;
+		use core::ptr::NonNull;
+
+		struct Counting<'a>(&'a Cell<usize>);
+
+		unsafe impl Allocator for Counting<'_> {
+			fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+				self.0.set(self.0.get() + 1);
+				Global.allocate(layout)
+			}
+
+			unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+				Global.deallocate(ptr, layout);
+			}
+		}
+
+		let count = Cell::new(0);
+		for s in ["", "abc", "caf\u{E9}", "\u{100}", "a\u{1F431}b"] {
+			assert_eq!(
+				isomorphic_encode_in(s, Counting(&count)).as_deref(),
+				isomorphic_encode(s).as_deref()
+			);
+			assert_eq!(
+				ascii_encode_in(s, Counting(&count)).as_deref(),
+				ascii_encode(s).as_deref()
+			);
+			for policy in [ViolationPolicy::Replace, ViolationPolicy::Ignore] {
+				assert_eq!(
+					isomorphic_encode_with_policy_in(s, policy, Counting(&count)).as_deref(),
+					isomorphic_encode_with_policy(s, policy).as_deref()
+				);
+				assert_eq!(
+					ascii_encode_with_policy_in(s, policy, Counting(&count)).as_deref(),
+					ascii_encode_with_policy(s, policy).as_deref()
+				);
+			}
+		}
+		// Every call but the ones with the empty string allocates once
+		assert_eq!(count.get(), 4 * 6);
+	}
+
+	#[test]
+	fn test_const_scanners_match_runtime() {
+		let mut s = String::new();
+		for c in (0..=0x10FFFF)
+			.step_by(0x7F)
+			.filter_map(char::from_u32)
+			.chain([
+				'\u{FDD0}',
+				'\u{FDEF}',
+				'\u{FFFE}',
+				'\u{FFFF}',
+				'\u{10FFFE}',
+				'\u{00FF}',
+				'\u{0100}',
+				'\u{001F}',
+			]) {
+			s.clear();
+			s.push('a');
+			s.push(c);
+			assert_eq!(
+				contains_noncharacter_const(&s),
+				contains_noncharacter(&s),
+				"{c:?}"
+			);
+			assert_eq!(
+				find_first_c0_control_const(&s),
+				find_first_c0_control(&s),
+				"{c:?}"
+			);
+			assert_eq!(
+				is_isomorphic_string_const(&s),
+				is_isomorphic_string(&s),
+				"{c:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_infra_str_spec_names() {
+		let value = "\t a  \r\n b \x0C";
+		assert_eq!(
+			value.strip_leading_and_trailing_ascii_whitespace(),
+			trim_ascii_whitespace(value)
+		);
+		assert_eq!(value.strip_and_collapse_ascii_whitespace(), "a b");
+	}
+
+	#[test]
+	fn test_try_collect_codepoints() {
+		let value = "a\u{1F431}b";
+		for i in 2..=4 {
+			let mut position = i;
+			let error = value
+				.try_collect_codepoints(&mut position, |_| true)
+				.unwrap_err();
+			assert_eq!(
+				(error.offset(), error.expected()),
+				(i, "a codepoint boundary")
+			);
+			assert_eq!(position, i);
+		}
+		let mut position = usize::MAX;
+		assert_eq!(
+			value.try_collect_codepoints(&mut position, |_| true),
+			Ok(String::new())
+		);
+		let mut position = 1;
+		assert_eq!(
+			value.try_collect_codepoints(&mut position, |c| c != 'b'),
+			Ok(String::from("\u{1F431}"))
+		);
+		assert_eq!(position, 5);
+	}
+
+	#[test]
+	fn test_encode_with_policy() {
+		let value = "a\u{E9}\u{1F431}b";
+		let error = ascii_encode_with_policy(value, ViolationPolicy::Error).unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (1, Some(1)));
+		assert_eq!(error.found(), Found::CodePoint('\u{E9}'));
+		let error = isomorphic_encode_with_policy(value, ViolationPolicy::Error).unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (3, Some(2)));
+
+		assert_eq!(
+			ascii_encode_with_policy(value, ViolationPolicy::Replace),
+			Ok(b"a??b".to_vec())
+		);
+		assert_eq!(
+			isomorphic_encode_with_policy(value, ViolationPolicy::Ignore),
+			Ok(b"a\xE9b".to_vec())
+		);
+		assert_eq!(value.ascii_encode(), None);
+		assert_eq!("ab".ascii_encode(), Some(b"ab".to_vec()));
+	}
+
+	#[test]
+	fn test_code_point_substring() {
+		let value = "a\u{1F431}b";
+		assert_eq!(value.code_point_substring(0, 3), value);
+		assert_eq!(value.code_point_substring(1, 1), "\u{1F431}");
+		assert_eq!(value.code_point_substring(3, 0), "");
+		for (start, length) in [(0, 4), (3, 1), (4, 0), (usize::MAX, 0), (1, usize::MAX)] {
+			let error = value.try_code_point_substring(start, length).unwrap_err();
+			assert_eq!((error.offset(), error.code_point_index()), (6, Some(3)));
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_code_point_substring_out_of_bounds() {
+		let _ = code_point_substring("ab", 1, 2);
+	}
+
+	#[test]
+	fn test_newline_normalizer_every_split() {
+		let value = "a\r\nb\r\r\nc\rd\n\re\r";
+		let expected = normalize_newlines(value);
+		for first in 0..=value.len() {
+			for second in first..=value.len() {
+				let mut normalizer = NewlineNormalizer::new();
+				let mut normalized = String::new();
+				for chunk in [&value[..first], &value[first..second], &value[second..]] {
+					normalized.push_str(&normalizer.push_chunk(chunk));
+				}
+				normalizer.finish();
+				assert_eq!(normalized, expected, "{first} {second}");
+				assert_eq!(normalizer, NewlineNormalizer::new());
+			}
+		}
+	}
+
+	#[test]
+	fn test_newline_normalizer_borrows() {
+		let mut normalizer = NewlineNormalizer::new();
+		assert!(matches!(
+			normalizer.push_chunk("a\nb"),
+			Cow::Borrowed("a\nb")
+		));
+		assert!(matches!(normalizer.push_chunk("c\r"), Cow::Owned(_)));
+		assert!(matches!(normalizer.push_chunk("\nd"), Cow::Borrowed("d")));
+		assert!(matches!(normalizer.push_chunk("\n"), Cow::Borrowed("\n")));
+		// Empty chunks don't separate a CR from the LF after it
+		assert_eq!(normalizer.push_chunk("e\r"), "e\n");
+		assert!(matches!(normalizer.push_chunk(""), Cow::Borrowed("")));
+		assert!(matches!(normalizer.push_chunk("\n"), Cow::Borrowed("")));
+	}
+
+	#[test]
+	fn test_contains_noncharacter() {
+		assert!(!contains_noncharacter(""));
+		assert!(!contains_noncharacter(
+			"\u{FDCF}\u{FDF0}\u{FFFD}\u{EFFFD}\u{F000}"
+		));
+		for c in [
+			'\u{FDD0}',
+			'\u{FDEF}',
+			'\u{FFFE}',
+			'\u{FFFF}',
+			'\u{1FFFE}',
+			'\u{10FFFF}',
+		] {
+			let mut s = String::from("0123456789abcdef0123");
+			s.push(c);
+			assert!(contains_noncharacter(&s), "{c:?}");
+		}
+	}
+
+	#[test]
+	fn test_find_first_c0_control() {
+		assert_eq!(find_first_c0_control(""), None);
+		assert_eq!(find_first_c0_control("\u{0000}"), Some(0));
+		assert_eq!(find_first_c0_control("0123456789abcdef\u{1F}"), Some(16));
+		assert_eq!(find_first_c0_control(" \u{7F}\u{80}\u{9F}"), None);
+	}
 }
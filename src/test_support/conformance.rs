@@ -0,0 +1,664 @@
+extern crate alloc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::vectors::{self, SplitCase, StringCase};
+use crate::json::{parse_json_string_to_infra_value, InfraValue, JsonParseError};
+use crate::strings;
+
+/// An operation that a [`ConformanceCase`] runs, named in a test file by
+/// its name in the Infra Standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConformanceOperation {
+	/// [normalize newlines](https://infra.spec.whatwg.org/#normalize-newlines)
+	NormalizeNewlines,
+	/// [strip newlines](https://infra.spec.whatwg.org/#strip-newlines)
+	StripNewlines,
+	/// [strip leading and trailing ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#strip-leading-and-trailing-ascii-whitespace)
+	StripLeadingAndTrailingAsciiWhitespace,
+	/// [strip and collapse ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace)
+	StripAndCollapseAsciiWhitespace,
+	/// [ASCII lowercase](https://infra.spec.whatwg.org/#ascii-lowercase)
+	AsciiLowercase,
+	/// [ASCII uppercase](https://infra.spec.whatwg.org/#ascii-uppercase)
+	AsciiUppercase,
+	/// [split on ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#split-on-ascii-whitespace)
+	SplitOnAsciiWhitespace,
+	/// [split on commas](https://infra.spec.whatwg.org/#split-on-commas)
+	SplitOnCommas,
+	/// [strictly split](https://infra.spec.whatwg.org/#strictly-split), on
+	/// the codepoint of the case's `"delimiter"`
+	StrictlySplit(char),
+}
+
+impl ConformanceOperation {
+	/// Returns the Infra Standard's name for the operation, like
+	/// `"split on commas"`, which test files use to refer to it
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::NormalizeNewlines => "normalize newlines",
+			Self::StripNewlines => "strip newlines",
+			Self::StripLeadingAndTrailingAsciiWhitespace => {
+				"strip leading and trailing ASCII whitespace"
+			}
+			Self::StripAndCollapseAsciiWhitespace => "strip and collapse ASCII whitespace",
+			Self::AsciiLowercase => "ASCII lowercase",
+			Self::AsciiUppercase => "ASCII uppercase",
+			Self::SplitOnAsciiWhitespace => "split on ASCII whitespace",
+			Self::SplitOnCommas => "split on commas",
+			Self::StrictlySplit(_) => "strictly split",
+		}
+	}
+
+	/// Returns `true` if the operation returns a list of strings instead of
+	/// a string
+	#[must_use]
+	pub const fn returns_list(self) -> bool {
+		matches!(
+			self,
+			Self::SplitOnAsciiWhitespace | Self::SplitOnCommas | Self::StrictlySplit(_)
+		)
+	}
+
+	fn from_name(name: &str, delimiter: Option<char>) -> Option<Self> {
+		let operation = match name {
+			"normalize newlines" => Self::NormalizeNewlines,
+			"strip newlines" => Self::StripNewlines,
+			"strip leading and trailing ASCII whitespace" => {
+				Self::StripLeadingAndTrailingAsciiWhitespace
+			}
+			"strip and collapse ASCII whitespace" => Self::StripAndCollapseAsciiWhitespace,
+			"ASCII lowercase" => Self::AsciiLowercase,
+			"ASCII uppercase" => Self::AsciiUppercase,
+			"split on ASCII whitespace" => Self::SplitOnAsciiWhitespace,
+			"split on commas" => Self::SplitOnCommas,
+			"strictly split" => Self::StrictlySplit(delimiter?),
+			_ => return None,
+		};
+		Some(operation)
+	}
+}
+
+impl fmt::Display for ConformanceOperation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// What an operation returns: a string, or a list of strings for the split
+/// operations
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConformanceOutput {
+	/// The string that an operation returns
+	String(String),
+	/// The strings that a split operation returns, in order
+	List(Vec<String>),
+}
+
+impl From<&str> for ConformanceOutput {
+	fn from(s: &str) -> Self {
+		Self::String(s.to_string())
+	}
+}
+
+impl From<&[&str]> for ConformanceOutput {
+	fn from(tokens: &[&str]) -> Self {
+		Self::List(tokens.iter().map(|token| token.to_string()).collect())
+	}
+}
+
+/// One test case of a conformance test file: an operation, its input, and
+/// what it's expected to return
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConformanceCase {
+	/// The operation to run
+	pub operation: ConformanceOperation,
+	/// The input of the operation
+	pub input: String,
+	/// What the operation is expected to return
+	pub expected: ConformanceOutput,
+}
+
+impl ConformanceCase {
+	/// Runs the case's operation with its input, using the given
+	/// implementation of the operations
+	pub fn run<O: ConformanceOperations + ?Sized>(&self, operations: &O) -> ConformanceOutput {
+		let input = self.input.as_str();
+		match self.operation {
+			ConformanceOperation::NormalizeNewlines => {
+				ConformanceOutput::String(operations.normalize_newlines(input))
+			}
+			ConformanceOperation::StripNewlines => {
+				ConformanceOutput::String(operations.strip_newlines(input))
+			}
+			ConformanceOperation::StripLeadingAndTrailingAsciiWhitespace => {
+				ConformanceOutput::String(
+					operations.strip_leading_and_trailing_ascii_whitespace(input),
+				)
+			}
+			ConformanceOperation::StripAndCollapseAsciiWhitespace => {
+				ConformanceOutput::String(operations.strip_and_collapse_ascii_whitespace(input))
+			}
+			ConformanceOperation::AsciiLowercase => {
+				ConformanceOutput::String(operations.ascii_lowercase(input))
+			}
+			ConformanceOperation::AsciiUppercase => {
+				ConformanceOutput::String(operations.ascii_uppercase(input))
+			}
+			ConformanceOperation::SplitOnAsciiWhitespace => {
+				ConformanceOutput::List(operations.split_on_ascii_whitespace(input))
+			}
+			ConformanceOperation::SplitOnCommas => {
+				ConformanceOutput::List(operations.split_on_commas(input))
+			}
+			ConformanceOperation::StrictlySplit(delimiter) => {
+				ConformanceOutput::List(operations.strictly_split(input, delimiter))
+			}
+		}
+	}
+}
+
+/// The operations that conformance cases run.
+///
+/// Each method calls this crate's implementation of the operation by
+/// default, so a wrapper only overrides the methods for the operations it
+/// reimplements or exposes, and passes the same cases as this crate.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::test_support::conformance::*;
+///
+/// struct Uppercase;
+///
+/// impl ConformanceOperations for Uppercase {
+///     fn ascii_uppercase(&self, input: &str) -> String {
+///         input.to_uppercase()
+///     }
+/// }
+///
+/// // `str::to_uppercase()` also uppercases non-ASCII codepoints
+/// let report = run_conformance_cases(&vector_conformance_cases(), &Uppercase);
+/// assert!(!report.is_success());
+/// assert!(report.failures().iter().all(|f| f.case.operation == ConformanceOperation::AsciiUppercase));
+/// ```
+pub trait ConformanceOperations {
+	/// Runs [normalize newlines](https://infra.spec.whatwg.org/#normalize-newlines)
+	fn normalize_newlines(&self, input: &str) -> String {
+		strings::normalize_newlines(input)
+	}
+
+	/// Runs [strip newlines](https://infra.spec.whatwg.org/#strip-newlines)
+	fn strip_newlines(&self, input: &str) -> String {
+		strings::strip_newlines(input)
+	}
+
+	/// Runs [strip leading and trailing ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#strip-leading-and-trailing-ascii-whitespace)
+	fn strip_leading_and_trailing_ascii_whitespace(&self, input: &str) -> String {
+		strings::trim_ascii_whitespace(input).to_string()
+	}
+
+	/// Runs [strip and collapse ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace)
+	fn strip_and_collapse_ascii_whitespace(&self, input: &str) -> String {
+		strings::trim_collapse_ascii_whitespace(input)
+	}
+
+	/// Runs [ASCII lowercase](https://infra.spec.whatwg.org/#ascii-lowercase)
+	fn ascii_lowercase(&self, input: &str) -> String {
+		strings::ascii_lowercase(input)
+	}
+
+	/// Runs [ASCII uppercase](https://infra.spec.whatwg.org/#ascii-uppercase)
+	fn ascii_uppercase(&self, input: &str) -> String {
+		strings::ascii_uppercase(input)
+	}
+
+	/// Runs [split on ASCII
+	/// whitespace](https://infra.spec.whatwg.org/#split-on-ascii-whitespace)
+	fn split_on_ascii_whitespace(&self, input: &str) -> Vec<String> {
+		strings::split_on_ascii_whitespace(input)
+			.map(|token| token.to_string())
+			.collect()
+	}
+
+	/// Runs [split on commas](https://infra.spec.whatwg.org/#split-on-commas)
+	fn split_on_commas(&self, input: &str) -> Vec<String> {
+		strings::split_on_commas(input)
+			.map(|token| token.to_string())
+			.collect()
+	}
+
+	/// Runs [strictly split](https://infra.spec.whatwg.org/#strictly-split)
+	fn strictly_split(&self, input: &str, delimiter: char) -> Vec<String> {
+		strings::strictly_split(input, delimiter)
+			.map(|token| token.to_string())
+			.collect()
+	}
+}
+
+/// The operations of this crate, which [`ConformanceOperations`] calls by
+/// default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CrateOperations;
+
+impl ConformanceOperations for CrateOperations {}
+
+/// A case that an implementation of the operations didn't pass
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConformanceFailure {
+	/// The index of the case in the cases that were run
+	pub index: usize,
+	/// The case that didn't pass
+	pub case: ConformanceCase,
+	/// What the operation returned instead of the expected output
+	pub actual: ConformanceOutput,
+}
+
+impl fmt::Display for ConformanceFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"case {} ({} of {:?}): expected {:?}, got {:?}",
+			self.index, self.case.operation, self.case.input, self.case.expected, self.actual
+		)
+	}
+}
+
+/// The results of running conformance cases, from
+/// [`run_conformance_cases()`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ConformanceReport {
+	passed: usize,
+	failures: Vec<ConformanceFailure>,
+}
+
+impl ConformanceReport {
+	/// Returns the number of cases that passed
+	#[must_use]
+	pub fn passed(&self) -> usize {
+		self.passed
+	}
+
+	/// Returns the cases that didn't pass, in the order they were run
+	#[must_use]
+	pub fn failures(&self) -> &[ConformanceFailure] {
+		&self.failures
+	}
+
+	/// Returns the number of cases that were run
+	#[must_use]
+	pub fn total(&self) -> usize {
+		self.passed + self.failures.len()
+	}
+
+	/// Returns `true` if every case passed
+	#[must_use]
+	pub fn is_success(&self) -> bool {
+		self.failures.is_empty()
+	}
+}
+
+impl fmt::Display for ConformanceReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} of {} cases passed", self.passed, self.total())?;
+		for failure in &self.failures {
+			write!(f, "\n{}", failure)?;
+		}
+		Ok(())
+	}
+}
+
+/// Runs conformance cases against an implementation of the operations, and
+/// returns which of them passed.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::test_support::conformance::*;
+///
+/// let cases = load_conformance_cases(r#"[
+///     "Comments are skipped",
+///     {"operation": "strip newlines", "input": "a\r\nb", "expected": "ab"},
+///     {"operation": "split on commas", "input": "a, b", "expected": ["a", "b"]}
+/// ]"#).unwrap();
+///
+/// let report = run_conformance_cases(&cases, &CrateOperations);
+/// assert!(report.is_success(), "{}", report);
+/// assert_eq!(report.passed(), 2);
+/// ```
+pub fn run_conformance_cases<O: ConformanceOperations + ?Sized>(
+	cases: &[ConformanceCase],
+	operations: &O,
+) -> ConformanceReport {
+	let mut report = ConformanceReport::default();
+	for (index, case) in cases.iter().enumerate() {
+		let actual = case.run(operations);
+		if actual == case.expected {
+			report.passed += 1;
+		} else {
+			report.failures.push(ConformanceFailure {
+				index,
+				case: case.clone(),
+				actual,
+			});
+		}
+	}
+	report
+}
+
+/// An error returned when a conformance test file can't be loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceLoadError {
+	/// The file isn't valid JSON
+	Json(JsonParseError),
+	/// The file is valid JSON, but isn't a list of cases
+	NotAList,
+	/// A case of the file is missing a member, or has a member of the wrong
+	/// type
+	InvalidCase {
+		/// The index of the case in the list, counting comments
+		index: usize,
+		/// What was wrong with the case
+		reason: &'static str,
+	},
+}
+
+impl fmt::Display for ConformanceLoadError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Json(e) => e.fmt(f),
+			Self::NotAList => f.write_str("conformance test file is not a list of cases"),
+			Self::InvalidCase { index, reason } => {
+				write!(f, "invalid conformance case at index {}: {}", index, reason)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConformanceLoadError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Json(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<JsonParseError> for ConformanceLoadError {
+	fn from(e: JsonParseError) -> Self {
+		Self::Json(e)
+	}
+}
+
+/// Loads the cases of a conformance test file.
+///
+/// Like the test files of web-platform-tests, the file is a JSON list,
+/// where strings are comments and are skipped, and each map is a case with
+/// these members:
+///
+/// - `"operation"`, the Infra Standard's name for the operation, like
+///   `"strip newlines"`
+/// - `"input"`, the string to run the operation with
+/// - `"expected"`, the string the operation returns, or the list of strings
+///   for the split operations
+/// - `"delimiter"`, the codepoint to split on, only for `"strictly split"`
+///
+/// Other members are ignored.
+///
+/// # Errors
+/// Returns a [`ConformanceLoadError`] if the file isn't valid JSON or isn't
+/// a list, or if a case has an unknown operation or is missing a member.
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::test_support::conformance::*;
+///
+/// let cases = load_conformance_cases(r#"[
+///     {"operation": "strictly split", "delimiter": ";", "input": "a;b", "expected": ["a", "b"]}
+/// ]"#).unwrap();
+/// assert_eq!(cases[0].operation, ConformanceOperation::StrictlySplit(';'));
+/// assert_eq!(cases[0].expected, ConformanceOutput::from(&["a", "b"][..]));
+///
+/// let error = load_conformance_cases(r#"[{"operation": "strip newlines"}]"#).unwrap_err();
+/// assert_eq!(error, ConformanceLoadError::InvalidCase { index: 0, reason: "missing string \"input\"" });
+/// ```
+pub fn load_conformance_cases(json: &str) -> Result<Vec<ConformanceCase>, ConformanceLoadError> {
+	let value = parse_json_string_to_infra_value(json)?;
+	let entries = value.as_list().ok_or(ConformanceLoadError::NotAList)?;
+
+	let mut cases = Vec::with_capacity(entries.len());
+	for (index, entry) in entries.iter().enumerate() {
+		if entry.as_str().is_some() {
+			continue;
+		}
+		let case = load_case(entry)
+			.map_err(|reason| ConformanceLoadError::InvalidCase { index, reason })?;
+		cases.push(case);
+	}
+	Ok(cases)
+}
+
+fn load_case(entry: &InfraValue) -> Result<ConformanceCase, &'static str> {
+	if entry.as_map().is_none() {
+		return Err("expected a map or a comment string");
+	}
+	let name = entry
+		.get("operation")
+		.and_then(InfraValue::as_str)
+		.ok_or("missing string \"operation\"")?;
+	let delimiter = match entry.get("delimiter") {
+		Some(value) => {
+			let mut chars = value.as_str().unwrap_or_default().chars();
+			match (chars.next(), chars.next()) {
+				(Some(c), None) => Some(c),
+				_ => return Err("\"delimiter\" is not a single codepoint"),
+			}
+		}
+		None => None,
+	};
+	let operation = match ConformanceOperation::from_name(name, delimiter) {
+		Some(operation) => operation,
+		None if name == "strictly split" => return Err("missing string \"delimiter\""),
+		None => return Err("unknown \"operation\""),
+	};
+	let input = entry
+		.get("input")
+		.and_then(InfraValue::as_str)
+		.ok_or("missing string \"input\"")?;
+
+	let expected = entry.get("expected").ok_or("missing \"expected\"")?;
+	let expected = if operation.returns_list() {
+		let tokens = expected
+			.as_list()
+			.ok_or("\"expected\" is not a list of strings")?
+			.iter()
+			.map(|token| token.as_str().map(ToString::to_string))
+			.collect::<Option<Vec<_>>>()
+			.ok_or("\"expected\" is not a list of strings")?;
+		ConformanceOutput::List(tokens)
+	} else {
+		let s = expected.as_str().ok_or("\"expected\" is not a string")?;
+		ConformanceOutput::String(s.to_string())
+	};
+
+	Ok(ConformanceCase {
+		operation,
+		input: input.to_string(),
+		expected,
+	})
+}
+
+/// Returns the cases of [`vectors`] for the operations that
+/// [`ConformanceOperations`] covers, as conformance cases.
+///
+/// This is the suite that this crate passes, for wrappers that don't have a
+/// test file of their own.
+#[must_use]
+pub fn vector_conformance_cases() -> Vec<ConformanceCase> {
+	let strings: [(ConformanceOperation, &[StringCase]); 6] = [
+		(
+			ConformanceOperation::NormalizeNewlines,
+			vectors::NORMALIZE_NEWLINES,
+		),
+		(ConformanceOperation::StripNewlines, vectors::STRIP_NEWLINES),
+		(
+			ConformanceOperation::StripLeadingAndTrailingAsciiWhitespace,
+			vectors::STRIP_LEADING_AND_TRAILING_ASCII_WHITESPACE,
+		),
+		(
+			ConformanceOperation::StripAndCollapseAsciiWhitespace,
+			vectors::STRIP_AND_COLLAPSE_ASCII_WHITESPACE,
+		),
+		(
+			ConformanceOperation::AsciiLowercase,
+			vectors::ASCII_LOWERCASE,
+		),
+		(
+			ConformanceOperation::AsciiUppercase,
+			vectors::ASCII_UPPERCASE,
+		),
+	];
+	let splits: [(ConformanceOperation, &[SplitCase]); 3] = [
+		(
+			ConformanceOperation::SplitOnAsciiWhitespace,
+			vectors::SPLIT_ON_ASCII_WHITESPACE,
+		),
+		(
+			ConformanceOperation::SplitOnCommas,
+			vectors::SPLIT_ON_COMMAS,
+		),
+		(
+			ConformanceOperation::StrictlySplit(','),
+			vectors::STRICTLY_SPLIT_ON_COMMA,
+		),
+	];
+
+	let mut cases = Vec::new();
+	for (operation, table) in strings {
+		cases.extend(table.iter().map(|case| ConformanceCase {
+			operation,
+			input: case.input.to_string(),
+			expected: case.expected.into(),
+		}));
+	}
+	for (operation, table) in splits {
+		cases.extend(table.iter().map(|case| ConformanceCase {
+			operation,
+			input: case.input.to_string(),
+			expected: case.expected.into(),
+		}));
+	}
+	cases
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_vector_conformance_cases() {
+		let cases = vector_conformance_cases();
+		let report = run_conformance_cases(&cases, &CrateOperations);
+		assert!(report.is_success(), "{}", report);
+		assert_eq!(report.total(), cases.len());
+	}
+
+	#[test]
+	fn test_load_conformance_cases() {
+		let cases = load_conformance_cases(
+			r#"[
+				"A comment",
+				{"operation": "ASCII lowercase", "input": "ÀB", "expected": "Àb", "comment": "x"},
+				{"operation": "split on ASCII whitespace", "input": " a ", "expected": ["a"]}
+			]"#,
+		)
+		.unwrap();
+		assert_eq!(
+			cases,
+			[
+				ConformanceCase {
+					operation: ConformanceOperation::AsciiLowercase,
+					input: "\u{C0}B".to_string(),
+					expected: "\u{C0}b".into(),
+				},
+				ConformanceCase {
+					operation: ConformanceOperation::SplitOnAsciiWhitespace,
+					input: " a ".to_string(),
+					expected: (&["a"][..]).into(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_load_conformance_cases_errors() {
+		let invalid = |json: &str| match load_conformance_cases(json) {
+			Err(ConformanceLoadError::InvalidCase { index, reason }) => (index, reason),
+			other => panic!("{:?}", other),
+		};
+		assert!(matches!(
+			load_conformance_cases("[").unwrap_err(),
+			ConformanceLoadError::Json(_)
+		));
+		assert_eq!(
+			load_conformance_cases("{}").unwrap_err(),
+			ConformanceLoadError::NotAList
+		);
+		assert_eq!(invalid("[1]"), (0, "expected a map or a comment string"));
+		assert_eq!(
+			invalid(r#"["", {"operation": "trim", "input": "", "expected": ""}]"#),
+			(1, "unknown \"operation\"")
+		);
+		assert_eq!(
+			invalid(r#"[{"operation": "strictly split", "input": "", "expected": []}]"#),
+			(0, "missing string \"delimiter\"")
+		);
+		assert_eq!(
+			invalid(
+				r#"[{"operation": "strictly split", "delimiter": "ab", "input": "", "expected": []}]"#
+			),
+			(0, "\"delimiter\" is not a single codepoint")
+		);
+		assert_eq!(
+			invalid(r#"[{"operation": "split on commas", "input": "", "expected": ""}]"#),
+			(0, "\"expected\" is not a list of strings")
+		);
+		assert_eq!(
+			invalid(r#"[{"operation": "strip newlines", "input": ""}]"#),
+			(0, "missing \"expected\"")
+		);
+	}
+
+	#[test]
+	fn test_conformance_failures() {
+		struct Broken;
+
+		impl ConformanceOperations for Broken {
+			fn strip_newlines(&self, input: &str) -> String {
+				input.replace('\n', "")
+			}
+		}
+
+		let cases = load_conformance_cases(
+			r#"[
+				{"operation": "strip newlines", "input": "a\nb", "expected": "ab"},
+				{"operation": "strip newlines", "input": "a\rb", "expected": "ab"}
+			]"#,
+		)
+		.unwrap();
+		let report = run_conformance_cases(&cases, &Broken);
+		assert_eq!((report.passed(), report.total()), (1, 2));
+		assert_eq!(report.failures()[0].index, 1);
+		assert_eq!(report.failures()[0].actual, "a\rb".into());
+		assert_eq!(
+			report.to_string(),
+			"1 of 2 cases passed\ncase 1 (strip newlines of \"a\\rb\"): expected String(\"ab\"), got String(\"a\\rb\")"
+		);
+	}
+}
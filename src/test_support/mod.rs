@@ -0,0 +1,13 @@
+/// Canonical inputs and expected outputs for the operations of this crate.
+///
+/// Each constant covers one operation, with the edge cases that
+/// implementations tend to get wrong: `\r\n` pairs next to lone `\r`s,
+/// whitespace that Rust's `char::is_whitespace()` accepts but the Infra
+/// Standard doesn't, and the boundaries of the noncharacter ranges.
+pub mod vectors;
+
+/// A loader for JSON conformance test files, and a harness that runs their
+/// cases against this crate's operations or a wrapper's, with the
+/// `conformance` feature
+#[cfg(feature = "conformance")]
+pub mod conformance;
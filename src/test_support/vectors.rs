@@ -0,0 +1,258 @@
+/// A string input and the string an operation returns for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StringCase {
+	/// The input of the operation
+	pub input: &'static str,
+	/// The string that the operation returns
+	pub expected: &'static str,
+}
+
+/// A string input and the tokens that a split operation returns for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SplitCase {
+	/// The input of the operation
+	pub input: &'static str,
+	/// The tokens that the operation returns, in order
+	pub expected: &'static [&'static str],
+}
+
+/// A codepoint and whether a predicate matches it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CodePointCase {
+	/// The input of the predicate
+	pub code_point: char,
+	/// Whether the predicate matches the codepoint
+	pub expected: bool,
+}
+
+/// A string input and the byte sequence that an encoding operation returns
+/// for it, or `None` if it can't encode it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EncodeCase {
+	/// The input of the operation
+	pub input: &'static str,
+	/// The byte sequence that the operation returns
+	pub expected: Option<&'static [u8]>,
+}
+
+const fn string_case(input: &'static str, expected: &'static str) -> StringCase {
+	StringCase { input, expected }
+}
+
+const fn split_case(input: &'static str, expected: &'static [&'static str]) -> SplitCase {
+	SplitCase { input, expected }
+}
+
+const fn code_point_case(code_point: char, expected: bool) -> CodePointCase {
+	CodePointCase {
+		code_point,
+		expected,
+	}
+}
+
+/// Cases for [normalize newlines](https://infra.spec.whatwg.org/#normalize-newlines)
+pub const NORMALIZE_NEWLINES: &[StringCase] = &[
+	string_case("", ""),
+	string_case("a\r\nb", "a\nb"),
+	string_case("\r", "\n"),
+	string_case("\r\r\n", "\n\n"),
+	string_case("\n\r", "\n\n"),
+	string_case("a\r\n\r\nb", "a\n\nb"),
+	string_case("\u{E9}\r\u{1F431}\r", "\u{E9}\n\u{1F431}\n"),
+	string_case("\u{2028}\u{85}", "\u{2028}\u{85}"),
+];
+
+/// Cases for [strip newlines](https://infra.spec.whatwg.org/#strip-newlines)
+pub const STRIP_NEWLINES: &[StringCase] = &[
+	string_case("", ""),
+	string_case("\r\n", ""),
+	string_case("a\nb\rc", "abc"),
+	string_case(" \t\x0C ", " \t\x0C "),
+	string_case("\u{2028}", "\u{2028}"),
+];
+
+/// Cases for [strip leading and trailing ASCII
+/// whitespace](https://infra.spec.whatwg.org/#strip-leading-and-trailing-ascii-whitespace)
+pub const STRIP_LEADING_AND_TRAILING_ASCII_WHITESPACE: &[StringCase] = &[
+	string_case("", ""),
+	string_case(" \t\n\x0C\r", ""),
+	string_case(" a b ", "a b"),
+	string_case("\x0Ba\x0B", "\x0Ba\x0B"),
+	string_case("\u{A0}a\u{3000}", "\u{A0}a\u{3000}"),
+];
+
+/// Cases for [strip and collapse ASCII
+/// whitespace](https://infra.spec.whatwg.org/#strip-and-collapse-ascii-whitespace)
+pub const STRIP_AND_COLLAPSE_ASCII_WHITESPACE: &[StringCase] = &[
+	string_case("", ""),
+	string_case("  a  b  ", "a b"),
+	string_case("a\t\n\x0C\rb", "a b"),
+	string_case("a\u{A0} b", "a\u{A0} b"),
+	string_case("\x0B", "\x0B"),
+];
+
+/// Cases for [ASCII lowercase](https://infra.spec.whatwg.org/#ascii-lowercase)
+pub const ASCII_LOWERCASE: &[StringCase] = &[
+	string_case("", ""),
+	string_case("AbC", "abc"),
+	string_case("\u{C0}BC", "\u{C0}bc"),
+	string_case("\u{130}\u{212A}", "\u{130}\u{212A}"),
+];
+
+/// Cases for [ASCII uppercase](https://infra.spec.whatwg.org/#ascii-uppercase)
+pub const ASCII_UPPERCASE: &[StringCase] = &[
+	string_case("", ""),
+	string_case("aBc", "ABC"),
+	string_case("\u{E0}bc", "\u{E0}BC"),
+	string_case("\u{DF}\u{131}", "\u{DF}\u{131}"),
+];
+
+/// Cases for [split on ASCII
+/// whitespace](https://infra.spec.whatwg.org/#split-on-ascii-whitespace)
+pub const SPLIT_ON_ASCII_WHITESPACE: &[SplitCase] = &[
+	split_case("", &[]),
+	split_case(" \t\n\x0C\r", &[]),
+	split_case(" a\tb\n", &["a", "b"]),
+	split_case("a\x0Bb\u{A0}c", &["a\x0Bb\u{A0}c"]),
+];
+
+/// Cases for [split on commas](https://infra.spec.whatwg.org/#split-on-commas)
+pub const SPLIT_ON_COMMAS: &[SplitCase] = &[
+	split_case("", &[]),
+	split_case(",", &[""]),
+	split_case("a,", &["a"]),
+	split_case(",a", &["", "a"]),
+	split_case("a,,b", &["a", "", "b"]),
+	split_case(" a , b ", &["a", "b"]),
+];
+
+/// Cases for [strictly split](https://infra.spec.whatwg.org/#strictly-split)
+/// with `,` as the delimiter
+pub const STRICTLY_SPLIT_ON_COMMA: &[SplitCase] = &[
+	split_case("", &[""]),
+	split_case(",", &["", ""]),
+	split_case("a,b,", &["a", "b", ""]),
+	split_case(" a , b ", &[" a ", " b "]),
+];
+
+/// Cases for [noncharacter](https://infra.spec.whatwg.org/#noncharacter),
+/// at the boundaries of the noncharacter ranges
+pub const NONCHARACTER: &[CodePointCase] = &[
+	code_point_case('\u{0}', false),
+	code_point_case('\u{FDCF}', false),
+	code_point_case('\u{FDD0}', true),
+	code_point_case('\u{FDEF}', true),
+	code_point_case('\u{FDF0}', false),
+	code_point_case('\u{FFFD}', false),
+	code_point_case('\u{FFFE}', true),
+	code_point_case('\u{FFFF}', true),
+	code_point_case('\u{10000}', false),
+	code_point_case('\u{1FFFD}', false),
+	code_point_case('\u{1FFFE}', true),
+	code_point_case('\u{1FFFF}', true),
+	code_point_case('\u{10FFFD}', false),
+	code_point_case('\u{10FFFE}', true),
+	code_point_case('\u{10FFFF}', true),
+];
+
+/// Cases for [C0 control](https://infra.spec.whatwg.org/#c0-control)
+pub const C0_CONTROL: &[CodePointCase] = &[
+	code_point_case('\u{0}', true),
+	code_point_case('\u{1F}', true),
+	code_point_case(' ', false),
+	code_point_case('\u{7F}', false),
+	code_point_case('\u{80}', false),
+];
+
+/// Cases for [isomorphic encode](https://infra.spec.whatwg.org/#isomorphic-encode)
+pub const ISOMORPHIC_ENCODE: &[EncodeCase] = &[
+	EncodeCase {
+		input: "",
+		expected: Some(b""),
+	},
+	EncodeCase {
+		input: "a\u{E9}",
+		expected: Some(b"a\xE9"),
+	},
+	EncodeCase {
+		input: "\u{FF}",
+		expected: Some(b"\xFF"),
+	},
+	EncodeCase {
+		input: "\u{100}",
+		expected: None,
+	},
+];
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::codepoints::scalar::{is_c0_control, is_noncharacter};
+
+	#[test]
+	fn test_code_point_vectors() {
+		for case in NONCHARACTER {
+			assert_eq!(
+				is_noncharacter(case.code_point),
+				case.expected,
+				"{:?}",
+				case
+			);
+		}
+		for case in C0_CONTROL {
+			assert_eq!(is_c0_control(case.code_point), case.expected, "{:?}", case);
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "strings")]
+	fn test_string_vectors() {
+		extern crate alloc;
+		use crate::strings::*;
+		use alloc::vec::Vec;
+
+		let check = |cases: &[StringCase], operation: fn(&str) -> alloc::string::String| {
+			for case in cases {
+				assert_eq!(operation(case.input), case.expected, "{:?}", case);
+			}
+		};
+		check(NORMALIZE_NEWLINES, normalize_newlines);
+		check(STRIP_NEWLINES, strip_newlines);
+		check(
+			STRIP_AND_COLLAPSE_ASCII_WHITESPACE,
+			trim_collapse_ascii_whitespace,
+		);
+		check(ASCII_LOWERCASE, ascii_lowercase);
+		check(ASCII_UPPERCASE, ascii_uppercase);
+		for case in STRIP_LEADING_AND_TRAILING_ASCII_WHITESPACE {
+			assert_eq!(
+				trim_ascii_whitespace(case.input),
+				case.expected,
+				"{:?}",
+				case
+			);
+		}
+
+		let split = |cases: &[SplitCase], operation: fn(&str) -> Vec<&str>| {
+			for case in cases {
+				assert_eq!(operation(case.input), case.expected, "{:?}", case);
+			}
+		};
+		split(SPLIT_ON_ASCII_WHITESPACE, |s| {
+			split_on_ascii_whitespace(s).collect()
+		});
+		split(SPLIT_ON_COMMAS, |s| split_on_commas(s).collect());
+		split(STRICTLY_SPLIT_ON_COMMA, |s| {
+			strictly_split(s, ',').collect()
+		});
+
+		for case in ISOMORPHIC_ENCODE {
+			assert_eq!(
+				isomorphic_encode(case.input).as_deref(),
+				case.expected,
+				"{:?}",
+				case
+			);
+		}
+	}
+}
@@ -0,0 +1,243 @@
+extern crate alloc;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
+
+use crate::codepoints::ascii_set::AsciiSet;
+use crate::codepoints::scalar::is_noncharacter;
+
+/// The ASCII [URL code points][whatwg-url-dfn]: the ASCII alphanumerics, and
+/// `!$&'()*+,-./:;=?@_~`
+///
+/// [whatwg-url-dfn]: https://url.spec.whatwg.org/#url-code-points
+pub const ASCII_URL_CODE_POINTS: AsciiSet =
+	AsciiSet::ASCII_ALPHANUMERIC.union(AsciiSet::from_bytes(b"!$&'()*+,-./:;=?@_~"));
+
+/// Checks if a codepoint is a URL code point, which the URL parser accepts
+/// without a validation error: one of [`ASCII_URL_CODE_POINTS`], or a
+/// codepoint from U+00A0 to U+10FFFD, inclusive, that isn't a surrogate or a
+/// [noncharacter](crate::is_noncharacter).
+///
+/// See also: [WHATWG URL Standard definition][whatwg-url-dfn]
+///
+/// [whatwg-url-dfn]: https://url.spec.whatwg.org/#url-code-points
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::url::is_url_code_point;
+///
+/// assert!(is_url_code_point('~'));
+/// assert!(is_url_code_point('\u{E9}'));
+/// assert!(!is_url_code_point('%'));
+/// assert!(!is_url_code_point('\u{FDD0}'));
+/// ```
+#[must_use]
+#[inline]
+pub const fn is_url_code_point(c: char) -> bool {
+	if c.is_ascii() {
+		return ASCII_URL_CODE_POINTS.contains_char(c);
+	}
+	// A `char` is never a surrogate, and the only codepoints above U+10FFFD
+	// are the noncharacters U+10FFFE and U+10FFFF
+	c >= '\u{A0}' && !is_noncharacter(c)
+}
+
+/// A validation error found while preprocessing the input of the URL
+/// parser, which the URL Standard calls an
+/// [invalid-URL-unit][whatwg-url-dfn] validation error.
+///
+/// Validation errors don't stop the parser. They're reported for
+/// conformance checkers and developer tools, which is why
+/// [`preprocess_url_input_with_reporter()`] passes them to a callback
+/// instead of returning them.
+///
+/// [whatwg-url-dfn]: https://url.spec.whatwg.org/#invalid-url-unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UrlValidationError {
+	/// The input starts or ends with a C0 control or space, at a byte offset
+	/// of the input
+	LeadingOrTrailingC0ControlOrSpace {
+		/// The byte offset of the first of these codepoints
+		offset: usize,
+	},
+	/// The input contains an ASCII tab or newline, at a byte offset of the
+	/// input
+	AsciiTabOrNewline {
+		/// The byte offset of the first of these codepoints
+		offset: usize,
+	},
+}
+
+impl UrlValidationError {
+	/// Returns the byte offset of the input where the error was found
+	#[must_use]
+	pub const fn offset(&self) -> usize {
+		match self {
+			Self::LeadingOrTrailingC0ControlOrSpace { offset }
+			| Self::AsciiTabOrNewline { offset } => *offset,
+		}
+	}
+
+	/// Returns the URL Standard's name for the error, `"invalid-URL-unit"`
+	#[must_use]
+	pub const fn name(&self) -> &'static str {
+		"invalid-URL-unit"
+	}
+}
+
+impl fmt::Display for UrlValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let description = match self {
+			Self::LeadingOrTrailingC0ControlOrSpace { .. } => {
+				"leading or trailing C0 control or space"
+			}
+			Self::AsciiTabOrNewline { .. } => "ASCII tab or newline",
+		};
+		write!(
+			f,
+			"{}: {} at offset {}",
+			self.name(),
+			description,
+			self.offset()
+		)
+	}
+}
+
+/// Preprocesses the input of the URL parser, by removing the C0 controls
+/// and spaces from before and after it, and then removing all of its ASCII
+/// tabs and newlines.
+///
+/// These are the first steps of the URL Standard's basic URL parser when it
+/// isn't given a URL, and only borrow the input if it has no tabs or
+/// newlines to remove.
+///
+/// See also: [WHATWG URL Standard definition][whatwg-url-dfn]
+///
+/// [whatwg-url-dfn]: https://url.spec.whatwg.org/#concept-basic-url-parser
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::url::preprocess_url_input;
+///
+/// assert_eq!(preprocess_url_input("\0 https://example.com/ \n"), "https://example.com/");
+/// assert_eq!(preprocess_url_input("https://exa\tmple.com/\r\n"), "https://example.com/");
+/// ```
+#[must_use]
+pub fn preprocess_url_input(input: &str) -> Cow<'_, str> {
+	preprocess_url_input_with_reporter(input, |_| {})
+}
+
+/// Preprocesses the input of the URL parser, like
+/// [`preprocess_url_input()`], and calls a closure with each validation
+/// error.
+///
+/// Like the URL Standard, it reports at most one error for each step: one
+/// for the C0 controls and spaces before and after the input, and one for
+/// its ASCII tabs and newlines, each with the offset of the first codepoint
+/// it removes.
+///
+/// See also: [WHATWG URL Standard definition][whatwg-url-dfn]
+///
+/// [whatwg-url-dfn]: https://url.spec.whatwg.org/#concept-basic-url-parser
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::url::{preprocess_url_input_with_reporter, UrlValidationError};
+///
+/// let mut errors = Vec::new();
+/// let input = preprocess_url_input_with_reporter(" http://a\n/b", |e| errors.push(e));
+/// assert_eq!(input, "http://a/b");
+/// assert_eq!(errors, [
+///     UrlValidationError::LeadingOrTrailingC0ControlOrSpace { offset: 0 },
+///     UrlValidationError::AsciiTabOrNewline { offset: 9 },
+/// ]);
+/// ```
+pub fn preprocess_url_input_with_reporter<F>(input: &str, mut report: F) -> Cow<'_, str>
+where
+	F: FnMut(UrlValidationError),
+{
+	let trimmed = AsciiSet::C0_CONTROL_OR_SPACE.trim(input);
+	let start = trimmed.as_ptr() as usize - input.as_ptr() as usize;
+	if trimmed.len() != input.len() {
+		// Without leading codepoints to remove, the first one removed is
+		// right after the trimmed input
+		let offset = if start == 0 { trimmed.len() } else { 0 };
+		report(UrlValidationError::LeadingOrTrailingC0ControlOrSpace { offset });
+	}
+
+	let first = match trimmed
+		.bytes()
+		.position(|byte| AsciiSet::ASCII_TAB_OR_NEWLINE.contains(byte))
+	{
+		Some(first) => first,
+		None => return Cow::Borrowed(trimmed),
+	};
+	report(UrlValidationError::AsciiTabOrNewline {
+		offset: start + first,
+	});
+
+	let mut result = String::with_capacity(trimmed.len());
+	for token in AsciiSet::ASCII_TAB_OR_NEWLINE.split(trimmed) {
+		result.push_str(token);
+	}
+	Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::string::ToString;
+	use alloc::vec::Vec;
+
+	fn preprocess(input: &str) -> (Cow<'_, str>, Vec<UrlValidationError>) {
+		let mut errors = Vec::new();
+		let output = preprocess_url_input_with_reporter(input, |e| errors.push(e));
+		(output, errors)
+	}
+
+	#[test]
+	fn test_preprocess_url_input() {
+		let (output, errors) = preprocess("https://example.com/");
+		assert!(matches!(output, Cow::Borrowed("https://example.com/")));
+		assert!(errors.is_empty());
+
+		let (output, errors) = preprocess("a \t b");
+		assert_eq!((output.as_ref(), errors.len()), ("a  b", 1));
+		assert_eq!(errors[0].offset(), 2);
+
+		let (output, errors) = preprocess("\u{1F}\u{E9}\u{7F} ");
+		assert!(matches!(output, Cow::Borrowed("\u{E9}\u{7F}")));
+		assert_eq!(
+			errors,
+			[UrlValidationError::LeadingOrTrailingC0ControlOrSpace { offset: 0 }]
+		);
+
+		// A trailing C0 control is reported at the end of the trimmed input
+		let (_, errors) = preprocess("a\u{0}");
+		assert_eq!(errors[0].offset(), 1);
+
+		let (output, errors) = preprocess(" \t\r\n ");
+		assert_eq!((output.as_ref(), errors.len()), ("", 1));
+	}
+
+	#[test]
+	fn test_is_url_code_point() {
+		let expected = |c: char| {
+			c.is_ascii_alphanumeric()
+				|| "!$&'()*+,-./:;=?@_~".contains(c)
+				|| (('\u{A0}'..='\u{10FFFD}').contains(&c) && !is_noncharacter(c))
+		};
+		for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+			assert_eq!(is_url_code_point(c), expected(c), "{:?}", c);
+		}
+	}
+
+	#[test]
+	fn test_url_validation_error_display() {
+		let error = UrlValidationError::AsciiTabOrNewline { offset: 4 };
+		assert_eq!(
+			error.to_string(),
+			"invalid-URL-unit: ASCII tab or newline at offset 4"
+		);
+	}
+}
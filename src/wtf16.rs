@@ -0,0 +1,924 @@
+extern crate alloc;
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Write};
+use core::iter::FusedIterator;
+use core::ops::Deref;
+
+use crate::codepoints::code_point::CodePoint;
+use crate::codepoints::surrogates::{
+	combine_surrogate_pair, is_leading_surrogate_utf16, split_into_surrogate_pair,
+};
+use crate::error::{Found, InfraParseError, ViolationPolicy};
+
+/// A borrowed [string][whatwg-infra-dfn] of the WHATWG Infra Standard,
+/// which is a sequence of 16-bit code units, like a JavaScript string.
+///
+/// Unlike [`str`], the code units don't have to be well-formed UTF-16: a
+/// leading surrogate that isn't followed by a trailing surrogate, or a
+/// trailing surrogate that isn't after a leading one, is a lone surrogate,
+/// and is kept as it is. Comparing and ordering strings compares their code
+/// units, which is the Infra Standard's [code unit less
+/// than][whatwg-infra-less-than].
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string
+/// [whatwg-infra-less-than]: https://infra.spec.whatwg.org/#code-unit-less-than
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{CodePoint, Wtf16Str};
+///
+/// let s = Wtf16Str::from_code_units(&[0x61, 0xD83D, 0xDC31, 0xD800]);
+/// assert_eq!(s.len(), 4);
+/// assert_eq!(
+///     s.code_points().map(CodePoint::value).collect::<Vec<_>>(),
+///     [0x61, 0x1F431, 0xD800],
+/// );
+/// assert!(!s.is_well_formed());
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Wtf16Str {
+	units: [u16],
+}
+
+impl Wtf16Str {
+	/// Borrows a sequence of code units as a string, without checking them
+	#[must_use]
+	pub fn from_code_units(units: &[u16]) -> &Self {
+		// SAFETY: `Wtf16Str` is a `#[repr(transparent)]` wrapper of `[u16]`,
+		// and any sequence of code units is a valid string
+		unsafe { &*(units as *const [u16] as *const Self) }
+	}
+
+	/// Mutably borrows a sequence of code units as a string
+	#[must_use]
+	pub fn from_code_units_mut(units: &mut [u16]) -> &mut Self {
+		// SAFETY: as in `Wtf16Str::from_code_units()`
+		unsafe { &mut *(units as *mut [u16] as *mut Self) }
+	}
+
+	/// Returns the code units of the string
+	#[must_use]
+	pub const fn as_code_units(&self) -> &[u16] {
+		&self.units
+	}
+
+	/// Returns the code units of the string mutably
+	#[must_use]
+	pub fn as_code_units_mut(&mut self) -> &mut [u16] {
+		&mut self.units
+	}
+
+	/// Returns the [length][whatwg-infra-dfn] of the string, which is its
+	/// number of code units
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string-length
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.units.len()
+	}
+
+	/// Returns `true` if the string has no code units
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.units.is_empty()
+	}
+
+	/// Returns an iterator over the [code points][whatwg-infra-dfn] of the
+	/// string, which combines each surrogate pair into one codepoint, and
+	/// returns each lone surrogate as a codepoint of its own.
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string-code-point
+	pub fn code_points(&self) -> CodePoints<'_> {
+		CodePoints { units: &self.units }
+	}
+
+	/// Returns `true` if the string is well-formed UTF-16, without a lone
+	/// surrogate, which means it converts into a [`String`] with
+	/// [`Wtf16Str::to_scalar_value_string()`] without losing anything.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::Wtf16String;
+	///
+	/// assert!(Wtf16String::from("\u{1F431}").is_well_formed());
+	/// assert!(!Wtf16String::from_code_units(vec![0xDC31, 0xD83D]).is_well_formed());
+	/// ```
+	#[must_use]
+	pub fn is_well_formed(&self) -> bool {
+		self.code_points().all(CodePoint::is_scalar_value)
+	}
+
+	/// Converts the string into a [scalar value string][whatwg-infra-dfn], by
+	/// replacing each lone surrogate with U+FFFD REPLACEMENT CHARACTER.
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#javascript-string-convert
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::Wtf16Str;
+	///
+	/// let s = Wtf16Str::from_code_units(&[0x61, 0xD83D, 0xDC31, 0xD800]);
+	/// assert_eq!(s.to_scalar_value_string(), "a\u{1F431}\u{FFFD}");
+	/// ```
+	#[must_use]
+	pub fn to_scalar_value_string(&self) -> String {
+		match self.to_scalar_value_string_with_policy(ViolationPolicy::Replace) {
+			Ok(s) => s,
+			Err(_) => unreachable!("replacing lone surrogates can't fail"),
+		}
+	}
+
+	/// Converts the string into a scalar value string like
+	/// [`Wtf16Str::to_scalar_value_string()`], and handles its lone
+	/// surrogates with a [`ViolationPolicy`].
+	///
+	/// See also: [WHATWG Infra Standard definition][whatwg-infra-dfn]
+	///
+	/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#javascript-string-convert
+	///
+	/// # Errors
+	/// With [`ViolationPolicy::Error`], returns an [`InfraParseError`] that
+	/// found the first lone surrogate, with its offset in code units.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{Found, ViolationPolicy, Wtf16Str};
+	///
+	/// let s = Wtf16Str::from_code_units(&[0xD83D, 0xDC31, 0xDC31, 0x61]);
+	/// let error = s.to_scalar_value_string_with_policy(ViolationPolicy::Error).unwrap_err();
+	/// assert_eq!((error.offset(), error.found()), (2, Found::CodeUnit(0xDC31)));
+	/// assert_eq!(error.code_point_index(), Some(1));
+	///
+	/// let s = s.to_scalar_value_string_with_policy(ViolationPolicy::Ignore);
+	/// assert_eq!(s.as_deref(), Ok("\u{1F431}a"));
+	/// ```
+	pub fn to_scalar_value_string_with_policy(
+		&self,
+		policy: ViolationPolicy,
+	) -> Result<String, InfraParseError> {
+		let mut result = String::with_capacity(self.len());
+		let mut offset = 0;
+		for (index, cp) in self.code_points().enumerate() {
+			match cp.to_char() {
+				Some(c) => {
+					result.push(c);
+					offset += c.len_utf16();
+				}
+				None => {
+					match policy {
+						ViolationPolicy::Error => {
+							let error = InfraParseError::new(
+								"convert a string into a scalar value string",
+								offset,
+								"a scalar value",
+								Found::CodeUnit(cp.value() as u16),
+							);
+							return Err(error.with_code_point_index(index));
+						}
+						ViolationPolicy::Replace => result.push('\u{FFFD}'),
+						ViolationPolicy::Ignore => {}
+					}
+					offset += 1;
+				}
+			}
+		}
+		Ok(result)
+	}
+
+	/// Encodes the string as [WTF-8][wtf8-spec], which is UTF-8 that can
+	/// also encode lone surrogates, as the three bytes that UTF-8 would use
+	/// for them if they were scalar values.
+	///
+	/// A surrogate pair is encoded as the four bytes of its codepoint, so a
+	/// well-formed string encodes to the same bytes as in UTF-8.
+	///
+	/// [wtf8-spec]: https://simonsapin.github.io/wtf-8/#encoding-ill-formed-utf-16
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::Wtf16Str;
+	///
+	/// let s = Wtf16Str::from_code_units(&[0x61, 0xD83D, 0xDC31, 0xD800]);
+	/// assert_eq!(s.to_wtf8(), b"a\xF0\x9F\x90\xB1\xED\xA0\x80");
+	/// ```
+	#[must_use]
+	pub fn to_wtf8(&self) -> Vec<u8> {
+		let mut bytes = Vec::with_capacity(self.len());
+		for cp in self.code_points() {
+			let (encoded, len) = encode_wtf8(cp.value());
+			bytes.extend_from_slice(&encoded[..len]);
+		}
+		bytes
+	}
+
+	/// Encodes the string as WTF-8 like [`Wtf16Str::to_wtf8()`], allocated
+	/// with the given allocator.
+	///
+	/// # Examples
+	/// ```
+	/// #![feature(allocator_api)]
+	/// use std::alloc::Global;
+	/// use whatwg_infra::Wtf16Str;
+	///
+	/// let s = Wtf16Str::from_code_units(&[0x61, 0xD800]);
+	/// assert_eq!(s.to_wtf8_in(Global), b"a\xED\xA0\x80");
+	/// ```
+	#[cfg(feature = "allocator_api")]
+	#[must_use]
+	pub fn to_wtf8_in<A: Allocator>(&self, alloc: A) -> Vec<u8, A> {
+		let mut bytes = Vec::with_capacity_in(self.len(), alloc);
+		for cp in self.code_points() {
+			let (encoded, len) = encode_wtf8(cp.value());
+			bytes.extend_from_slice(&encoded[..len]);
+		}
+		bytes
+	}
+}
+
+impl ToOwned for Wtf16Str {
+	type Owned = Wtf16String;
+
+	fn to_owned(&self) -> Wtf16String {
+		Wtf16String {
+			units: self.units.to_vec(),
+		}
+	}
+}
+
+impl AsRef<[u16]> for Wtf16Str {
+	fn as_ref(&self) -> &[u16] {
+		&self.units
+	}
+}
+
+impl<'a> From<&'a [u16]> for &'a Wtf16Str {
+	fn from(units: &'a [u16]) -> Self {
+		Wtf16Str::from_code_units(units)
+	}
+}
+
+impl PartialEq<str> for Wtf16Str {
+	/// Compares the code units of the string with the UTF-16 encoding of a
+	/// [`str`]
+	fn eq(&self, other: &str) -> bool {
+		self.units.iter().copied().eq(other.encode_utf16())
+	}
+}
+
+impl PartialEq<&str> for Wtf16Str {
+	fn eq(&self, other: &&str) -> bool {
+		*self == **other
+	}
+}
+
+impl fmt::Debug for Wtf16Str {
+	/// Formats the string like a [`str`], with its lone surrogates escaped
+	/// like `\u{D800}`
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_char('"')?;
+		for cp in self.code_points() {
+			match cp.to_char() {
+				// `char::escape_debug()` would also escape the single quote
+				Some('\'') => f.write_char('\'')?,
+				Some(c) => {
+					for escaped in c.escape_debug() {
+						f.write_char(escaped)?;
+					}
+				}
+				None => write!(f, "\\u{{{:X}}}", cp.value())?,
+			}
+		}
+		f.write_char('"')
+	}
+}
+
+/// An owned [string][whatwg-infra-dfn] of the WHATWG Infra Standard, which
+/// is a sequence of 16-bit code units that may be ill-formed UTF-16, like a
+/// JavaScript string.
+///
+/// It dereferences to [`Wtf16Str`], and converts from and back into its
+/// code units without changing them, so strings with lone surrogates from a
+/// JavaScript engine round-trip without loss.
+///
+/// [whatwg-infra-dfn]: https://infra.spec.whatwg.org/#string
+///
+/// # Examples
+/// ```
+/// use whatwg_infra::{CodePoint, Wtf16String};
+///
+/// let mut s = Wtf16String::from("a");
+/// s.push(CodePoint::new(0xD800).unwrap());
+/// s.push_str("\u{1F431}");
+/// assert_eq!(s.as_code_units(), [0x61, 0xD800, 0xD83D, 0xDC31]);
+/// assert_eq!(format!("{:?}", s), r#""a\u{D800}🐱""#);
+///
+/// let units = s.clone().into_code_units();
+/// assert_eq!(Wtf16String::from_code_units(units), s);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wtf16String {
+	units: Vec<u16>,
+}
+
+impl Wtf16String {
+	/// Creates an empty string
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { units: Vec::new() }
+	}
+
+	/// Creates an empty string with room for at least `capacity` code units
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			units: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Creates a string from a sequence of code units, without copying or
+	/// checking them
+	#[must_use]
+	pub const fn from_code_units(units: Vec<u16>) -> Self {
+		Self { units }
+	}
+
+	/// Returns the code units of the string, without copying them
+	#[must_use]
+	pub fn into_code_units(self) -> Vec<u16> {
+		self.units
+	}
+
+	/// Borrows the string as a [`Wtf16Str`]
+	#[must_use]
+	pub fn as_wtf16_str(&self) -> &Wtf16Str {
+		Wtf16Str::from_code_units(&self.units)
+	}
+
+	/// Appends a codepoint, as a surrogate pair if it's above U+FFFF, or as
+	/// one code unit otherwise.
+	///
+	/// A surrogate is appended as a code unit of its own, the way a
+	/// JavaScript string concatenates, so a trailing surrogate after a lone
+	/// leading surrogate makes them a surrogate pair.
+	pub fn push(&mut self, cp: CodePoint) {
+		match split_into_surrogate_pair(cp.value()) {
+			Some((leading, trailing)) => self.units.extend_from_slice(&[leading, trailing]),
+			None => self.units.push(cp.value() as u16),
+		}
+	}
+
+	/// Appends the UTF-16 encoding of a [`str`]
+	pub fn push_str(&mut self, s: &str) {
+		self.units.extend(s.encode_utf16());
+	}
+
+	/// Appends the code units of another string
+	pub fn push_wtf16_str(&mut self, s: &Wtf16Str) {
+		self.units.extend_from_slice(s.as_code_units());
+	}
+
+	/// Decodes [WTF-8][wtf8-spec] bytes, like the ones from
+	/// [`Wtf16Str::to_wtf8()`], into a string.
+	///
+	/// Any UTF-8 is also WTF-8, so this decodes UTF-8 too.
+	///
+	/// [wtf8-spec]: https://simonsapin.github.io/wtf-8/#decoding-wtf-8
+	///
+	/// # Errors
+	/// Returns an [`InfraParseError`] at the first byte that starts an
+	/// invalid sequence, or that found the end of the input if the bytes
+	/// end in the middle of a codepoint. A leading surrogate that's directly
+	/// followed by a trailing surrogate is invalid too, since WTF-8 encodes
+	/// their surrogate pair as one codepoint; the error is at the trailing
+	/// one.
+	///
+	/// # Examples
+	/// ```
+	/// use whatwg_infra::{Found, Wtf16String};
+	///
+	/// let s = Wtf16String::from_wtf8(b"a\xED\xA0\x80").unwrap();
+	/// assert_eq!(s.as_code_units(), [0x61, 0xD800]);
+	///
+	/// let error = Wtf16String::from_wtf8(b"\xED\xA0\xBD\xED\xB0\xB1").unwrap_err();
+	/// assert_eq!((error.offset(), error.found()), (3, Found::Byte(0xED)));
+	/// ```
+	pub fn from_wtf8(bytes: &[u8]) -> Result<Self, InfraParseError> {
+		let mut string = Self::with_capacity(bytes.len());
+		let mut offset = 0;
+		while offset < bytes.len() {
+			let (value, len) = decode_wtf8(&bytes[offset..]).map_err(|found| {
+				InfraParseError::new("decode WTF-8", offset, "valid WTF-8", found)
+			})?;
+			let follows_leading_surrogate = string
+				.units
+				.last()
+				.map_or(false, |&unit| is_leading_surrogate_utf16(unit));
+			if follows_leading_surrogate && matches!(value, 0xDC00..=0xDFFF) {
+				let found = Found::Byte(bytes[offset]);
+				return Err(InfraParseError::new(
+					"decode WTF-8",
+					offset,
+					"valid WTF-8",
+					found,
+				));
+			}
+			match CodePoint::new(value) {
+				Some(cp) => string.push(cp),
+				None => unreachable!("WTF-8 sequences are at most U+10FFFF"),
+			}
+			offset += len;
+		}
+		Ok(string)
+	}
+}
+
+impl Deref for Wtf16String {
+	type Target = Wtf16Str;
+
+	fn deref(&self) -> &Wtf16Str {
+		self.as_wtf16_str()
+	}
+}
+
+impl core::ops::DerefMut for Wtf16String {
+	fn deref_mut(&mut self) -> &mut Wtf16Str {
+		Wtf16Str::from_code_units_mut(&mut self.units)
+	}
+}
+
+impl Borrow<Wtf16Str> for Wtf16String {
+	fn borrow(&self) -> &Wtf16Str {
+		self.as_wtf16_str()
+	}
+}
+
+impl AsRef<Wtf16Str> for Wtf16String {
+	fn as_ref(&self) -> &Wtf16Str {
+		self.as_wtf16_str()
+	}
+}
+
+impl AsRef<[u16]> for Wtf16String {
+	fn as_ref(&self) -> &[u16] {
+		&self.units
+	}
+}
+
+impl From<&str> for Wtf16String {
+	fn from(s: &str) -> Self {
+		let mut string = Self::with_capacity(s.len());
+		string.push_str(s);
+		string
+	}
+}
+
+impl From<&String> for Wtf16String {
+	fn from(s: &String) -> Self {
+		Self::from(s.as_str())
+	}
+}
+
+impl From<String> for Wtf16String {
+	fn from(s: String) -> Self {
+		Self::from(s.as_str())
+	}
+}
+
+impl From<Vec<u16>> for Wtf16String {
+	fn from(units: Vec<u16>) -> Self {
+		Self::from_code_units(units)
+	}
+}
+
+impl From<&[u16]> for Wtf16String {
+	fn from(units: &[u16]) -> Self {
+		Self::from_code_units(units.to_vec())
+	}
+}
+
+impl From<&Wtf16Str> for Wtf16String {
+	fn from(s: &Wtf16Str) -> Self {
+		s.to_owned()
+	}
+}
+
+impl From<Wtf16String> for Vec<u16> {
+	fn from(s: Wtf16String) -> Self {
+		s.into_code_units()
+	}
+}
+
+impl FromIterator<CodePoint> for Wtf16String {
+	fn from_iter<I: IntoIterator<Item = CodePoint>>(iter: I) -> Self {
+		let mut s = Self::new();
+		s.extend(iter);
+		s
+	}
+}
+
+impl FromIterator<char> for Wtf16String {
+	fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+		let mut s = Self::new();
+		s.extend(iter);
+		s
+	}
+}
+
+impl Extend<CodePoint> for Wtf16String {
+	/// Appends each codepoint of an iterator, like [`Wtf16String::push()`]
+	fn extend<I: IntoIterator<Item = CodePoint>>(&mut self, iter: I) {
+		for cp in iter {
+			self.push(cp);
+		}
+	}
+}
+
+impl Extend<char> for Wtf16String {
+	fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+		let mut buffer = [0; 2];
+		for c in iter {
+			self.units.extend_from_slice(c.encode_utf16(&mut buffer));
+		}
+	}
+}
+
+impl PartialEq<str> for Wtf16String {
+	fn eq(&self, other: &str) -> bool {
+		*self.as_wtf16_str() == *other
+	}
+}
+
+impl PartialEq<&str> for Wtf16String {
+	fn eq(&self, other: &&str) -> bool {
+		*self.as_wtf16_str() == **other
+	}
+}
+
+impl PartialEq<Wtf16Str> for Wtf16String {
+	fn eq(&self, other: &Wtf16Str) -> bool {
+		*self.as_wtf16_str() == *other
+	}
+}
+
+impl PartialEq<&Wtf16Str> for Wtf16String {
+	fn eq(&self, other: &&Wtf16Str) -> bool {
+		*self.as_wtf16_str() == **other
+	}
+}
+
+impl fmt::Debug for Wtf16String {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self.as_wtf16_str(), f)
+	}
+}
+
+/// An iterator over the codepoints of a [`Wtf16Str`], created by
+/// [`Wtf16Str::code_points()`]
+#[derive(Clone, Debug)]
+pub struct CodePoints<'a> {
+	units: &'a [u16],
+}
+
+impl<'a> CodePoints<'a> {
+	/// Returns the code units that the iterator hasn't returned yet
+	#[must_use]
+	pub fn as_wtf16_str(&self) -> &'a Wtf16Str {
+		Wtf16Str::from_code_units(self.units)
+	}
+}
+
+impl Iterator for CodePoints<'_> {
+	type Item = CodePoint;
+
+	fn next(&mut self) -> Option<CodePoint> {
+		let (cp, len) = match *self.units {
+			[] => return None,
+			[leading, trailing, ..] => pair_or_unit(leading, trailing, leading),
+			[unit] => (CodePoint::from(unit), 1),
+		};
+		self.units = &self.units[len..];
+		Some(cp)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.units.len();
+		((len + 1) / 2, Some(len))
+	}
+}
+
+impl DoubleEndedIterator for CodePoints<'_> {
+	fn next_back(&mut self) -> Option<CodePoint> {
+		let (cp, len) = match *self.units {
+			[] => return None,
+			[.., leading, trailing] => pair_or_unit(leading, trailing, trailing),
+			[unit] => (CodePoint::from(unit), 1),
+		};
+		self.units = &self.units[..self.units.len() - len];
+		Some(cp)
+	}
+}
+
+impl FusedIterator for CodePoints<'_> {}
+
+/// Returns the WTF-8 encoding of a codepoint, which is its UTF-8 encoding
+/// even if it's a surrogate, and its length
+const fn encode_wtf8(value: u32) -> ([u8; 4], usize) {
+	match value {
+		0..=0x7F => ([value as u8, 0, 0, 0], 1),
+		0x80..=0x7FF => ([0xC0 | (value >> 6) as u8, continuation(value), 0, 0], 2),
+		0x800..=0xFFFF => (
+			[
+				0xE0 | (value >> 12) as u8,
+				continuation(value >> 6),
+				continuation(value),
+				0,
+			],
+			3,
+		),
+		_ => (
+			[
+				0xF0 | (value >> 18) as u8,
+				continuation(value >> 12),
+				continuation(value >> 6),
+				continuation(value),
+			],
+			4,
+		),
+	}
+}
+
+/// Returns the continuation byte for the low 6 bits of a value
+const fn continuation(value: u32) -> u8 {
+	0x80 | (value & 0x3F) as u8
+}
+
+/// Decodes the WTF-8 sequence at the start of some bytes, and returns its
+/// codepoint and length, or what was found instead of a valid sequence.
+///
+/// It's UTF-8 decoding, except that the three-byte sequences of surrogates
+/// are allowed.
+fn decode_wtf8(bytes: &[u8]) -> Result<(u32, usize), Found> {
+	let first = bytes[0];
+	let (len, second_range) = match first {
+		0x00..=0x7F => return Ok((u32::from(first), 1)),
+		0xC2..=0xDF => (2, 0x80..=0xBF),
+		0xE0 => (3, 0xA0..=0xBF),
+		0xE1..=0xEF => (3, 0x80..=0xBF),
+		0xF0 => (4, 0x90..=0xBF),
+		0xF1..=0xF3 => (4, 0x80..=0xBF),
+		0xF4 => (4, 0x80..=0x8F),
+		_ => return Err(Found::Byte(first)),
+	};
+
+	let mut value = u32::from(first) & (0x7F >> len);
+	for i in 1..len {
+		let byte = match bytes.get(i) {
+			Some(&byte) => byte,
+			None => return Err(Found::EndOfInput),
+		};
+		let valid = if i == 1 {
+			second_range.contains(&byte)
+		} else {
+			matches!(byte, 0x80..=0xBF)
+		};
+		if !valid {
+			return Err(Found::Byte(first));
+		}
+		value = (value << 6) | u32::from(byte & 0x3F);
+	}
+	Ok((value, len))
+}
+
+/// Returns the codepoint of two code units and their length if they're a
+/// surrogate pair, or the codepoint of one of them on its own otherwise
+fn pair_or_unit(leading: u16, trailing: u16, unit: u16) -> (CodePoint, usize) {
+	match combine_surrogate_pair(leading, trailing).and_then(CodePoint::new) {
+		Some(cp) => (cp, 2),
+		None => (CodePoint::from(unit), 1),
+	}
+}
+
+/// Conversions between strings and the [`OsStr`](std::ffi::OsStr)s of
+/// Windows, which are also sequences of 16-bit code units that may have lone
+/// surrogates
+#[cfg(all(feature = "std", windows))]
+mod windows {
+	use std::ffi::{OsStr, OsString};
+	use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+	use super::{Wtf16Str, Wtf16String};
+
+	impl Wtf16String {
+		/// Creates a string from the code units of a Windows [`OsStr`],
+		/// without losing its lone surrogates
+		#[must_use]
+		pub fn from_os_str(s: &OsStr) -> Self {
+			Self::from_code_units(s.encode_wide().collect())
+		}
+	}
+
+	impl Wtf16Str {
+		/// Converts the string into a Windows [`OsString`] with the same code
+		/// units, which can be passed to the wide-character APIs of Windows
+		#[must_use]
+		pub fn to_os_string(&self) -> OsString {
+			OsString::from_wide(self.as_code_units())
+		}
+	}
+
+	impl From<&OsStr> for Wtf16String {
+		fn from(s: &OsStr) -> Self {
+			Self::from_os_str(s)
+		}
+	}
+
+	impl From<OsString> for Wtf16String {
+		fn from(s: OsString) -> Self {
+			Self::from_os_str(&s)
+		}
+	}
+
+	impl From<&Wtf16Str> for OsString {
+		fn from(s: &Wtf16Str) -> Self {
+			s.to_os_string()
+		}
+	}
+
+	impl From<Wtf16String> for OsString {
+		fn from(s: Wtf16String) -> Self {
+			s.to_os_string()
+		}
+	}
+
+	#[cfg(test)]
+	mod test {
+		use super::*;
+
+		#[test]
+		fn test_os_string_round_trip() {
+			let units = [0x61, 0xD800, 0xD83D, 0xDC31];
+			let os_string = OsString::from_wide(&units);
+			let s = Wtf16String::from(os_string.clone());
+			assert_eq!(s.as_code_units(), units);
+			assert_eq!(OsString::from(s), os_string);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use alloc::format;
+
+	fn code_points(s: &Wtf16Str) -> Vec<u32> {
+		s.code_points().map(CodePoint::value).collect()
+	}
+
+	#[test]
+	fn test_wtf16_code_points() {
+		let s = Wtf16Str::from_code_units(&[0xDC00, 0xD800, 0xD800, 0xDFFF, 0x61, 0xDBFF]);
+		assert_eq!(code_points(s), [0xDC00, 0xD800, 0x103FF, 0x61, 0xDBFF]);
+
+		let mut reversed: Vec<u32> = s.code_points().rev().map(CodePoint::value).collect();
+		reversed.reverse();
+		assert_eq!(reversed, code_points(s));
+
+		let mut iter = Wtf16Str::from_code_units(&[0xD83D, 0xDC31, 0xD83D]).code_points();
+		assert_eq!(iter.next_back(), Some(CodePoint::from(0xD83Du16)));
+		assert_eq!(iter.as_wtf16_str().as_code_units(), [0xD83D, 0xDC31]);
+		assert_eq!(iter.next_back(), Some(CodePoint::from('\u{1F431}')));
+		assert_eq!(iter.next(), None);
+		assert_eq!(code_points(Wtf16Str::from_code_units(&[])), []);
+	}
+
+	#[test]
+	fn test_wtf16_string_round_trip() {
+		let units = [0x61, 0xDC31, 0xD83D, 0x1F, 0xD83D, 0xDC31, 0xD800];
+		let s = Wtf16String::from(&units[..]);
+		assert_eq!(s.len(), units.len());
+		assert!(!s.is_well_formed());
+
+		// Pushing the codepoints again gives back the same code units
+		let rebuilt: Wtf16String = s.code_points().collect();
+		assert_eq!(rebuilt, s);
+		assert_eq!(rebuilt.into_code_units(), units);
+
+		let s = Wtf16String::from("caf\u{E9} \u{10FFFF}");
+		assert!(s.is_well_formed());
+		assert_eq!(s, "caf\u{E9} \u{10FFFF}");
+		assert_ne!(s, "caf\u{E9}");
+		let chars: Wtf16String = "caf\u{E9} \u{10FFFF}".chars().collect();
+		assert_eq!(chars, s);
+	}
+
+	#[test]
+	fn test_wtf16_string_push() {
+		let mut s = Wtf16String::new();
+		s.push(CodePoint::new(0xD83D).unwrap());
+		assert!(!s.is_well_formed());
+		// The trailing surrogate makes a pair with the lone leading one
+		s.push(CodePoint::new(0xDC31).unwrap());
+		assert_eq!(s, "\u{1F431}");
+		s.push(CodePoint::MAX);
+		s.push_wtf16_str(Wtf16Str::from_code_units(&[0xDFFF]));
+		assert_eq!(s.as_code_units(), [0xD83D, 0xDC31, 0xDBFF, 0xDFFF, 0xDFFF]);
+	}
+
+	#[test]
+	fn test_wtf16_ordering() {
+		// Code unit order puts U+FF61 after the surrogates of U+1F431,
+		// unlike codepoint order
+		let pair = Wtf16String::from("\u{1F431}");
+		let halfwidth = Wtf16String::from("\u{FF61}");
+		assert!(pair < halfwidth);
+		assert!(Wtf16Str::from_code_units(&[0x61]) < Wtf16Str::from_code_units(&[0x61, 0x62]));
+	}
+
+	#[test]
+	fn test_to_scalar_value_string() {
+		let s = Wtf16Str::from_code_units(&[0xDC00, 0x61, 0xD800, 0xD800, 0xDC00, 0xDBFF]);
+		assert_eq!(
+			s.to_scalar_value_string(),
+			"\u{FFFD}a\u{FFFD}\u{10000}\u{FFFD}"
+		);
+		assert_eq!(
+			s.to_scalar_value_string_with_policy(ViolationPolicy::Ignore),
+			Ok(String::from("a\u{10000}"))
+		);
+
+		let error = s
+			.to_scalar_value_string_with_policy(ViolationPolicy::Error)
+			.unwrap_err();
+		assert_eq!(
+			(error.offset(), error.found()),
+			(0, Found::CodeUnit(0xDC00))
+		);
+		let s = Wtf16Str::from_code_units(&s.as_code_units()[1..]);
+		let error = s
+			.to_scalar_value_string_with_policy(ViolationPolicy::Error)
+			.unwrap_err();
+		assert_eq!((error.offset(), error.code_point_index()), (1, Some(1)));
+
+		let s = Wtf16String::from("caf\u{E9} \u{1F431}");
+		assert_eq!(s.to_scalar_value_string(), "caf\u{E9} \u{1F431}");
+		assert_eq!(Wtf16Str::from_code_units(&[]).to_scalar_value_string(), "");
+	}
+
+	#[test]
+	fn test_wtf8_round_trip() {
+		let units = [
+			0x0, 0x7FF, 0x800, 0xD7FF, 0xDBFF, 0xE000, 0xDC00, 0xD800, 0xFFFF,
+		];
+		let s = Wtf16Str::from_code_units(&units);
+		let bytes = s.to_wtf8();
+		assert_eq!(Wtf16String::from_wtf8(&bytes).unwrap(), *s);
+
+		let s = Wtf16String::from("\u{7F}\u{80}\u{FFFF}\u{10000}\u{10FFFF}");
+		assert_eq!(
+			s.to_wtf8(),
+			"\u{7F}\u{80}\u{FFFF}\u{10000}\u{10FFFF}".as_bytes()
+		);
+		assert_eq!(Wtf16String::from_wtf8(&s.to_wtf8()).unwrap(), s);
+		// A lone trailing surrogate before a lone leading one stays lone
+		let s = Wtf16String::from_wtf8(b"\xED\xB0\x80\xED\xA0\x80").unwrap();
+		assert_eq!(s.as_code_units(), [0xDC00, 0xD800]);
+	}
+
+	#[cfg(feature = "allocator_api")]
+	#[test]
+	fn test_to_wtf8_in() {
+		use alloc::alloc::Global;
+
+		let s = Wtf16Str::from_code_units(&[0x61, 0xDC00, 0xD83D, 0xDC31, 0xD800]);
+		assert_eq!(*s.to_wtf8_in(Global), *s.to_wtf8());
+	}
+
+	#[test]
+	fn test_wtf8_errors() {
+		let error = |bytes: &[u8]| {
+			let error = Wtf16String::from_wtf8(bytes).unwrap_err();
+			(error.offset(), error.found())
+		};
+		assert_eq!(error(b"a\x80"), (1, Found::Byte(0x80)));
+		assert_eq!(error(b"\xC0\x80"), (0, Found::Byte(0xC0)));
+		assert_eq!(error(b"\xE0\x9F\xBF"), (0, Found::Byte(0xE0)));
+		assert_eq!(error(b"\xF4\x90\x80\x80"), (0, Found::Byte(0xF4)));
+		assert_eq!(error(b"ab\xF0\x9F\x90"), (2, Found::EndOfInput));
+		assert_eq!(error(b"\xED\xA0\x80\xED\xB0\x80"), (3, Found::Byte(0xED)));
+	}
+
+	#[test]
+	fn test_wtf16_debug() {
+		let s = Wtf16String::from_code_units(alloc::vec![0x27, 0x22, 0x0A, 0xDFFF]);
+		assert_eq!(format!("{:?}", s), r#""'\"\n\u{DFFF}""#);
+		assert_eq!(format!("{:?}", s.to_owned()), format!("{:?}", &*s));
+	}
+}